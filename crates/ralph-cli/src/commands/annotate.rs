@@ -0,0 +1,85 @@
+// ABOUTME: 'ralph annotate' command implementation
+// ABOUTME: Attaches human corrections to past iterations as new, linked ledger events
+
+use ralph_lib::{Config, Ledger, LedgerEvent, RalphError, Result};
+use std::collections::BTreeSet;
+
+/// Configuration for annotate command
+pub struct AnnotateConfig {
+    pub slug: String,
+    /// Iteration the annotation refers to
+    pub iteration: u32,
+    /// Requirement to annotate when the iteration touched several
+    pub requirement: Option<String>,
+    pub message: String,
+    pub verbose: bool,
+    pub project: Config,
+}
+
+/// Append an annotation on an earlier iteration to a feature's ledger
+pub fn run(config: &AnnotateConfig) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let ledger_path = cwd
+        .join(config.project.paths.tasks())
+        .join(&config.slug)
+        .join("ledger.jsonl");
+
+    if !ledger_path.exists() {
+        return Err(RalphError::Ledger(format!(
+            "No ledger found at {}",
+            ledger_path.display()
+        )));
+    }
+    if config.message.trim().is_empty() {
+        return Err(RalphError::Ledger("Annotation text is empty".to_string()));
+    }
+
+    let mut ledger = Ledger::from_file(&ledger_path)?;
+    let touched: BTreeSet<&str> = ledger
+        .events()
+        .iter()
+        .filter(|e| e.iteration == config.iteration && e.kind.is_requirement())
+        .map(|e| e.requirement.as_str())
+        .collect();
+    let requirement = match (&config.requirement, touched.len()) {
+        (_, 0) => {
+            return Err(RalphError::Ledger(format!(
+                "No events for iteration {} in '{}'",
+                config.iteration, config.slug
+            )))
+        }
+        (Some(req_id), _) if touched.contains(req_id.as_str()) => req_id.clone(),
+        (Some(req_id), _) => {
+            return Err(RalphError::Ledger(format!(
+                "Iteration {} has no events for {req_id}",
+                config.iteration
+            )))
+        }
+        (None, 1) => touched.first().map(ToString::to_string).unwrap_or_default(),
+        (None, _) => {
+            return Err(RalphError::Ledger(format!(
+                "Iteration {} touched {}; pass --req to pick one",
+                config.iteration,
+                touched.into_iter().collect::<Vec<_>>().join(", ")
+            )))
+        }
+    };
+
+    let event = LedgerEvent::annotation(
+        ledger.latest_iteration(),
+        &requirement,
+        config.iteration,
+        config.message.trim(),
+    );
+    ledger.append(event)?;
+
+    println!(
+        "📎 Annotated iteration {} ({requirement})",
+        config.iteration
+    );
+    if config.verbose {
+        let count = ledger.annotations_for_iteration(config.iteration).len();
+        println!("   {count} annotation(s) on this iteration");
+    }
+    Ok(())
+}
@@ -0,0 +1,128 @@
+// ABOUTME: 'ralph export' command implementation
+// ABOUTME: Exports a feature's ledger as CSV or OTLP traces, or its PRD as HTML/Confluence for sharing
+
+use ralph_lib::export::{prd_to_confluence, prd_to_html};
+use ralph_lib::otlp::{ledger_to_otlp, send_otlp, OTLP_ENDPOINT_ENV};
+use ralph_lib::{prd_path, Config, Ledger, MarkdownPrd, Prd, RalphError, Result};
+use std::fs;
+
+/// Configuration for export command
+pub struct ExportConfig {
+    pub slug: String,
+    /// Output format ("csv" or "otlp")
+    pub format: String,
+    /// Write to this file instead of stdout
+    pub output: Option<String>,
+    /// OTLP collector to send traces to instead of writing them
+    pub endpoint: Option<String>,
+    pub verbose: bool,
+    pub project: Config,
+}
+
+/// Export a feature's ledger
+pub fn run(config: &ExportConfig) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let ledger_path = cwd
+        .join(config.project.paths.tasks())
+        .join(&config.slug)
+        .join("ledger.jsonl");
+
+    if !ledger_path.exists() {
+        return Err(RalphError::Ledger(format!(
+            "No ledger found for '{}'",
+            config.slug
+        )));
+    }
+
+    let ledger = Ledger::from_file(&ledger_path)?;
+    let content = match config.format.as_str() {
+        "csv" => ledger.to_csv(),
+        "otlp" => {
+            let traces = ledger_to_otlp(&ledger, &config.slug)?;
+            // An explicit --output wins over a collector from the environment
+            let endpoint = config.endpoint.clone().or_else(|| {
+                config
+                    .output
+                    .is_none()
+                    .then(|| std::env::var(OTLP_ENDPOINT_ENV).ok())
+                    .flatten()
+            });
+            if let Some(endpoint) = endpoint {
+                send_otlp(&endpoint, &traces)?;
+                println!("📡 Sent '{}' traces to {endpoint}", config.slug);
+                return Ok(());
+            }
+            format!("{}\n", serde_json::to_string_pretty(&traces)?)
+        }
+        other => {
+            return Err(RalphError::Command(format!(
+                "Unsupported export format: {other}"
+            )))
+        }
+    };
+
+    write_output(&content, config.output.as_deref(), config.verbose)
+}
+
+/// Configuration for PRD export
+pub struct PrdExportConfig {
+    pub slug: String,
+    /// Output format ("html" or "confluence")
+    pub format: String,
+    /// Write to this file instead of stdout
+    pub output: Option<String>,
+    pub verbose: bool,
+    pub project: Config,
+}
+
+/// Render a feature's PRD (status, criteria, planning log) for stakeholders
+pub fn run_prd(config: &PrdExportConfig) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let prd_path = prd_path(cwd.join(config.project.paths.tasks()).join(&config.slug));
+    let md_path = cwd
+        .join(config.project.paths.docs())
+        .join(&config.slug)
+        .join("prd.md");
+
+    if !prd_path.exists() {
+        return Err(RalphError::PrdValidation(format!(
+            "Feature '{}' not found",
+            config.slug
+        )));
+    }
+
+    let prd = Prd::from_file(&prd_path)?;
+    let markdown = if md_path.exists() {
+        Some(MarkdownPrd::from_file(&md_path)?)
+    } else {
+        None
+    };
+    let planning_log = markdown
+        .as_ref()
+        .and_then(|md| md.get_section("PLANNING_LOG"));
+
+    let content = match config.format.as_str() {
+        "html" => prd_to_html(&prd, planning_log),
+        "confluence" => prd_to_confluence(&prd, planning_log),
+        other => {
+            return Err(RalphError::Command(format!(
+                "Unsupported export format: {other}"
+            )))
+        }
+    };
+
+    write_output(&content, config.output.as_deref(), config.verbose)
+}
+
+fn write_output(content: &str, output: Option<&str>, verbose: bool) -> Result<()> {
+    match output {
+        Some(path) => {
+            fs::write(path, content)?;
+            if verbose {
+                println!("Exported to {path}");
+            }
+        }
+        None => print!("{content}"),
+    }
+    Ok(())
+}
@@ -1,11 +1,62 @@
 // ABOUTME: 'ralph implement' command implementation
 // ABOUTME: Runs unattended implementation loop with GitHub Copilot CLI
 
+/// `println!` for human-readable output, which moves to stderr while stdout carries the
+/// JSON progress stream
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if $crate::commands::implement::HUMAN_TO_STDERR
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+mod parallel;
+mod queue;
+mod snapshot;
+
+pub use queue::{run_queue, Queue};
+
+use super::hook::VERIFIED_TRAILER;
+use ralph_lib::api::{ApiDiff, ApiSurface};
+use ralph_lib::budget::{format_duration, Dollars, RunSpend};
+use ralph_lib::checkpoint::{InFlight, Phase};
+use ralph_lib::config::{PathConfig, SummarizerMode};
+use ralph_lib::ledger::{AUDIT_REQUIREMENT, CHORE_REQUIREMENT, NO_OP_MESSAGE, RUN_REQUIREMENT};
+use ralph_lib::notify::{Notifier, NotifyConfig, NotifyTarget};
+use ralph_lib::progress::{ProgressEvent, ProgressFormat, ProgressRecord};
+use ralph_lib::prompt::{PromptKind, PromptTemplate, PromptVars};
+use ralph_lib::prompt_budget::{self, Flexible, Trim, CHARS_PER_TOKEN};
+use ralph_lib::redact::Redactor;
+use ralph_lib::repo_map::{self, RepoMapQuery};
+use ralph_lib::sync::{LedgerSync, SyncConfig};
+use ralph_lib::throttle::{self, Throttle};
+use ralph_lib::usage::{model_pricing, parse_copilot_session, parse_copilot_usage, TokenUsage};
+use ralph_lib::validation::{
+    kill_process_tree, CharLimit, OutputLimits, Shell, TIMEOUT_POLL_INTERVAL,
+};
+use ralph_lib::{handoff, judge, pull_request, report, scratchpad, summary};
 use ralph_lib::{
-    EventStatus, Ledger, LedgerEvent, Prd, RequirementStatus, Result, ValidationConfig,
+    prd_path, CaptureOptions, Checkpoint, Config, EventPayload, EventStatus, Ledger, LedgerEvent,
+    Prd, RalphError, Reproducibility, RequirementStatus, Result, SecretResolver, ValidationCache,
+    ValidationConfig, ValidationResult, ValidationStage, WorkspaceActivity, WorkspaceLedger,
 };
-use std::path::Path;
-use std::process::Command;
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Message recorded when a passing iteration's diff is rejected in review
+const REVIEW_REJECTED_MESSAGE: &str = "diff rejected in review";
+
+/// Set for `--progress-format json`, whose stream has stdout to itself
+pub(crate) static HUMAN_TO_STDERR: AtomicBool = AtomicBool::new(false);
 
 /// Configuration for implement command
 pub struct ImplementConfig {
@@ -14,51 +65,233 @@ pub struct ImplementConfig {
     pub verbose: bool,
     /// Enable continuous looping until success or max iterations (default: true)
     pub loop_enabled: bool,
-    /// Maximum number of iterations before stopping (default: 10)
-    pub max_iterations: u32,
+    /// Append a docs/CHANGELOG requirement once all planned requirements are done
+    pub docs_requirement: bool,
+    /// Labels recorded on every ledger event of this run (for experiment comparison)
+    pub labels: Vec<String>,
+    /// Judge model to score the final diff against acceptance criteria (None disables)
+    pub judge_model: Option<String>,
+    /// Failed attempts after which a requirement is escalated to Blocked (None disables)
+    pub max_attempts: Option<u32>,
+    /// File a GitHub issue with the hand-off document when escalating
+    pub open_issue: bool,
+    /// Link each new ledger event to the previous one by hash (tamper-evident audit trail)
+    pub hash_chain: bool,
+    /// Run one housekeeping iteration with this description instead of a requirement
+    pub chore: Option<String>,
+    /// Only iterate on this requirement, reopening it if it was done or blocked
+    pub requirement: Option<String>,
+    /// Guidance for the requirement's next prompt, recorded in the ledger as a hint
+    pub hint: Option<String>,
+    /// Requirements to add to the PRD's skip list, which the loop leaves for a human
+    pub skip: Vec<String>,
+    /// Diff the public API (rustdoc JSON) before and after the run
+    pub api_diff: bool,
+    /// Spacing, concurrency cap, and rate-limit retries for agent calls
+    pub throttle: Throttle,
+    /// Validation stages that already passed during this run, by tree state
+    pub validation_cache: ValidationCache,
+    /// Write JUnit XML and SARIF reports of each validation run into this directory
+    pub report_dir: Option<PathBuf>,
+    /// Capture validation output without also streaming it to the console
+    pub quiet: bool,
+    /// Text progress, or JSON lifecycle events on stdout with everything else on stderr
+    pub progress_format: ProgressFormat,
+    /// Continue the interrupted run recorded in the feature's checkpoint
+    pub resume: bool,
+    /// Unattended run: stay on the checked-out branch unless `allow_branch_switch` is set
+    pub ci: bool,
+    /// In CI mode, still check out (or create) the feature branch
+    pub allow_branch_switch: bool,
+    /// Models, loop limits, selection strategy, branch template, and paths (flags over config files)
+    pub project: Config,
 }
 
 /// Run the implementation loop
 pub fn run(config: &ImplementConfig) -> Result<()> {
+    HUMAN_TO_STDERR.store(
+        config.progress_format == ProgressFormat::Json,
+        Ordering::Relaxed,
+    );
     let cwd = std::env::current_dir()?;
-    let task_dir = cwd.join("ralph/tasks").join(&config.slug);
-    let prd_path = task_dir.join("prd.json");
+    let task_dir = cwd.join(config.project.paths.tasks()).join(&config.slug);
+    let prd_path = prd_path(&task_dir);
     let ledger_path = task_dir.join("ledger.jsonl");
-    let validation_path = cwd.join("ralph/validation.json");
+    let validation_path = cwd.join(config.project.paths.validation());
 
     // Verify PRD exists
     if !prd_path.exists() {
-        println!("❌ Error: PRD not found at {}", prd_path.display());
-        println!("   Run 'ralph plan {}' first", config.slug);
+        say!("❌ Error: PRD not found at {}", prd_path.display());
+        say!("   Run 'ralph plan {}' first", config.slug);
         return Ok(());
     }
 
     // Check for uncommitted changes
     if has_uncommitted_changes() {
-        println!("⚠️  Warning: You have uncommitted changes");
+        say!("⚠️  Warning: You have uncommitted changes");
         if config.verbose {
-            println!("   Consider committing or stashing before implementation");
+            say!("   Consider committing or stashing before implementation");
         }
     }
 
     let mut prd = Prd::from_file(&prd_path)?;
+    for id in config.requirement.iter().chain(&config.skip) {
+        if !prd.requirements.iter().any(|r| &r.id == id) {
+            return Err(RalphError::Command(format!(
+                "Requirement {id} not found in {}",
+                prd_path.display()
+            )));
+        }
+    }
+    // --skip adds to the PRD's skip list, so later runs leave those requirements alone too
+    let newly_skipped: Vec<String> = config
+        .skip
+        .iter()
+        .filter(|id| !prd.is_skipped(id))
+        .cloned()
+        .collect();
+    if !newly_skipped.is_empty() {
+        prd.skip.extend(newly_skipped.iter().cloned());
+        if config.dry_run {
+            say!("[dry-run] Would skip {}", newly_skipped.join(", "));
+        } else {
+            prd.save(&prd_path)?;
+            say!(
+                "⏭️  Skipping {} (recorded in the PRD's skip list)",
+                newly_skipped.join(", ")
+            );
+        }
+    }
     let mut ledger = if ledger_path.exists() {
         Ledger::from_file(&ledger_path)?
     } else {
         Ledger::create(&ledger_path)?
     };
+    if config.hash_chain {
+        ledger.enable_hash_chain();
+    }
+
+    // Push events to the remote targets configured for the project
+    let sync_path = cwd.join(config.project.paths.sync());
+    if sync_path.exists() && !config.dry_run {
+        let project = cwd.file_name().map_or_else(
+            || "ralph".to_string(),
+            |name| name.to_string_lossy().to_string(),
+        );
+        ledger.enable_sync(LedgerSync::new(
+            SyncConfig::from_file(&sync_path)?,
+            project,
+            &config.slug,
+            SecretResolver::for_repo(&cwd),
+        ));
+    }
+
+    // Announce failed iterations, finished requirements, and the run's end
+    let mut notify_config = NotifyConfig::open(&cwd.join(config.project.paths.notify()))?;
+    if config.project.implement.desktop_notify() {
+        notify_config
+            .targets
+            .push(NotifyTarget::Desktop { events: None });
+    }
+    if !notify_config.targets.is_empty() && !config.dry_run {
+        ledger.enable_notify(Notifier::new(
+            notify_config,
+            &config.slug,
+            prd.requirements.len(),
+            SecretResolver::for_repo(&cwd),
+        ));
+    }
 
-    // Ensure we're on the correct branch
-    let branch_name = format!("ralph/{}/{}", config.slug, prd.active_run_id);
+    // Ensure we're on the correct branch (the checkpoint's, when resuming)
+    let branch_name = config
+        .project
+        .implement
+        .branch_name(&config.slug, &prd.active_run_id);
+    let branch_name = if config.ci && !config.allow_branch_switch {
+        let current = current_branch();
+        if current != branch_name {
+            say!(
+                "🔒 CI mode: working on {current} instead of {branch_name} \
+                 (--allow-branch-switch to switch)"
+            );
+        }
+        current
+    } else {
+        branch_name
+    };
+    let mut checkpoint = open_checkpoint(config, &task_dir, &prd, &branch_name)?;
+    let branch_name = checkpoint.branch.clone();
     ensure_branch(&branch_name, config.dry_run, config.verbose)?;
 
-    // Load validation config
-    let validation_config = if validation_path.exists() {
-        Some(ValidationConfig::from_file(&validation_path)?)
+    // Audit branch commits for hook bypasses before starting new work
+    if !config.dry_run {
+        let flagged = audit_branch_commits(&branch_name, &mut ledger)?;
+        if flagged > 0 {
+            say!("⚠️  Warning: {flagged} commit(s) flagged by audit (see 'ralph status')");
+        }
+        record_outside_commits(&cwd, &mut ledger, &config.labels)?;
+        WorkspaceLedger::record(
+            cwd.join(config.project.paths.workspace_ledger()),
+            &config.slug,
+            WorkspaceActivity::RunStarted {
+                run_id: prd.active_run_id.clone(),
+            },
+        )?;
+    }
+
+    // Remember where this run started so the judge can review the full diff
+    let run_start_sha = git_head_sha(&cwd);
+    let api_before = if config.api_diff && !config.dry_run && config.chore.is_none() {
+        capture_api_surface(&cwd, config.verbose)
     } else {
         None
     };
 
+    // Load validation config (built-in profiles only if the project has none)
+    let validation_config = Some(if validation_path.exists() {
+        ValidationConfig::from_file(&validation_path)?
+    } else {
+        ValidationConfig::builtin()
+    });
+
+    // Probe validation tools up front instead of failing on the first iteration
+    if let Some(vc) = &validation_config {
+        for (name, profile) in vc.profiles_for(&prd.validation_profiles, &cwd) {
+            for tool in profile.missing_tools() {
+                say!(
+                    "⚠️  Warning: '{}' not found on PATH ({} stage of profile '{}')",
+                    tool.program,
+                    tool.stage.as_str(),
+                    name
+                );
+                if config.verbose {
+                    say!("   Command: {}", tool.command);
+                }
+            }
+        }
+    }
+
+    if let Some(description) = &config.chore {
+        run_chore_iteration(
+            config,
+            &cwd,
+            &prd_path,
+            &prd,
+            &mut ledger,
+            validation_config.as_ref(),
+            description,
+        )?;
+        return finish_run(config, &cwd, &prd, &mut ledger);
+    }
+
+    // A targeted requirement is worked on even if it was finished or given up on
+    if let Some(id) = &config.requirement {
+        reopen_requirement(config, &prd_path, &mut prd, &mut ledger, id)?;
+        if let Some(hint) = &config.hint {
+            record_hint(config, &mut ledger, id, hint)?;
+        }
+    }
+
     // Count requirements by status
     let total_reqs = prd.requirements.len();
     let done_reqs = prd
@@ -69,46 +302,128 @@ pub fn run(config: &ImplementConfig) -> Result<()> {
     let remaining_reqs = total_reqs - done_reqs;
 
     if config.verbose {
-        println!("Implementing feature: {}", config.slug);
-        println!("PRD: {}", prd_path.display());
-        println!("Ledger: {}", ledger_path.display());
-        println!("Current iteration: {}", ledger.latest_iteration() + 1);
+        say!("Implementing feature: {}", config.slug);
+        say!("PRD: {}", prd_path.display());
+        say!("Ledger: {}", ledger_path.display());
+        say!("Current iteration: {}", ledger.latest_iteration() + 1);
     }
 
-    println!(
+    say!(
         "📊 Progress: {}/{} requirements complete ({} remaining)",
-        done_reqs, total_reqs, remaining_reqs
+        done_reqs,
+        total_reqs,
+        remaining_reqs
     );
 
     if config.loop_enabled {
-        println!(
-            "🔄 Starting implementation loop (max {} iterations)",
-            config.max_iterations
-        );
-        println!();
+        let max_iterations = config.project.implement.max_iterations();
+        let budget = config.project.implement.budget();
+        let mut limits = vec![format!("max {max_iterations} iterations")];
+        if let Some(limit) = budget.max_duration {
+            limits.push(format_duration(limit));
+        }
+        if let Some(limit) = budget.max_cost {
+            limits.push(Dollars(limit).to_string());
+            let model = config.project.models.implementer();
+            if model_pricing(model).is_none() {
+                say!(
+                    "⚠️  Warning: no pricing known for {model}; its calls don't count toward the cost budget"
+                );
+            }
+        }
+        say!("🔄 Starting implementation loop ({})", limits.join(", "));
+        say!();
 
         // Autonomous loop mode - iterate through requirements until all done or max iterations
-        let mut iteration_count = 0;
+        let mut iteration_count = checkpoint.iterations_used;
         loop {
             iteration_count += 1;
 
             // Check safety limit
-            if iteration_count > config.max_iterations {
-                println!(
-                    "⛔ Max iterations ({}) reached - stopping",
-                    config.max_iterations
-                );
+            if iteration_count > max_iterations {
+                say!("⛔ Max iterations ({}) reached - stopping", max_iterations);
                 let remaining = prd
                     .requirements
                     .iter()
                     .filter(|r| r.status != RequirementStatus::Done)
                     .count();
                 if remaining > 0 {
-                    println!("   {} requirements still incomplete", remaining);
+                    say!("   {} requirements still incomplete", remaining);
+                }
+                if !config.dry_run {
+                    ledger.append(
+                        LedgerEvent::new(
+                            ledger.latest_iteration(),
+                            RUN_REQUIREMENT,
+                            EventStatus::InProgress,
+                        )
+                        .with_labels(&config.labels)
+                        .with_payload(EventPayload::RunAborted {
+                            reason: format!(
+                                "max iterations ({}) reached with {remaining} requirement(s) incomplete",
+                                max_iterations
+                            ),
+                        }),
+                    )?;
                 }
                 break;
             }
 
+            // Check the run's time and cost budgets (measured from its start, resumes included)
+            let spend = RunSpend::since(&ledger, checkpoint.started_at, chrono::Utc::now());
+            if let Some(reason) = budget.exceeded(&spend) {
+                let remaining = prd
+                    .requirements
+                    .iter()
+                    .filter(|r| r.status != RequirementStatus::Done)
+                    .count();
+                say!("⛔ Budget reached: {reason} - stopping");
+                say!(
+                    "   Run used {} and ~{} over {} iteration(s); {} requirement(s) still incomplete",
+                    format_duration(spend.elapsed),
+                    Dollars(spend.cost),
+                    iteration_count - 1,
+                    remaining
+                );
+                if !config.dry_run {
+                    ledger.append(
+                        LedgerEvent::new(
+                            ledger.latest_iteration(),
+                            RUN_REQUIREMENT,
+                            EventStatus::InProgress,
+                        )
+                        .with_labels(&config.labels)
+                        .with_payload(EventPayload::RunAborted {
+                            reason: format!("{reason} with {remaining} requirement(s) incomplete"),
+                        }),
+                    )?;
+                }
+                break;
+            }
+
+            // Run requirements with disjoint paths concurrently, each in its own worktree
+            if config.project.implement.parallel() > 1 && config.requirement.is_none() {
+                let used = parallel::run_round(
+                    config,
+                    &cwd,
+                    &prd_path,
+                    &mut prd,
+                    &mut ledger,
+                    validation_config.as_ref(),
+                )?;
+                if used > 0 {
+                    iteration_count += used - 1;
+                    checkpoint.iterations_used = iteration_count;
+                    if config.dry_run {
+                        say!("[dry-run] Stopping: later iterations depend on this round's outcome");
+                        break;
+                    }
+                    checkpoint.save()?;
+                    say!();
+                    continue;
+                }
+            }
+
             // Run one iteration
             let all_done = run_single_iteration(
                 config,
@@ -117,16 +432,71 @@ pub fn run(config: &ImplementConfig) -> Result<()> {
                 &mut prd,
                 &mut ledger,
                 validation_config.as_ref(),
+                &mut checkpoint,
             )?;
+            checkpoint.iterations_used = iteration_count;
+            if !config.dry_run {
+                checkpoint.save()?;
+            }
+
+            // Requirements escalated to Blocked need a human before the feature can finish
+            let blocked = prd
+                .requirements
+                .iter()
+                .filter(|r| r.status == RequirementStatus::Blocked)
+                .filter(|r| config.requirement.as_ref().map_or(true, |id| &r.id == id))
+                .count();
+            if all_done && blocked > 0 {
+                say!("⛔ No implementable requirements left ({blocked} blocked, see hand-offs in artifacts/)");
+                if !config.dry_run {
+                    ledger.append(
+                        LedgerEvent::new(
+                            ledger.latest_iteration(),
+                            RUN_REQUIREMENT,
+                            EventStatus::InProgress,
+                        )
+                        .with_labels(&config.labels)
+                        .with_payload(EventPayload::RunAborted {
+                            reason: format!("{blocked} requirement(s) blocked"),
+                        }),
+                    )?;
+                }
+                break;
+            }
+
+            // Nothing changed, so the next dry-run iteration would preview the same work
+            if config.dry_run && !all_done {
+                say!("[dry-run] Stopping: later iterations depend on this one's outcome");
+                break;
+            }
 
             // If all requirements are complete, we're done
             if all_done {
-                println!("✅ All requirements complete!");
+                let skipped = prd
+                    .requirements
+                    .iter()
+                    .filter(|r| r.status != RequirementStatus::Done && prd.is_skipped(&r.id))
+                    .count();
+                match &config.requirement {
+                    Some(id) => say!("✅ {id} complete!"),
+                    None if skipped > 0 => {
+                        say!("✅ All requirements complete! ({skipped} skipped, left to you)");
+                    }
+                    None => say!("✅ All requirements complete!"),
+                }
+                let api_diff = api_before
+                    .as_ref()
+                    .and_then(|before| write_api_diff(&cwd, &task_dir, before, config.verbose));
+                if let (Some(model), Some(base)) = (&config.judge_model, &run_start_sha) {
+                    if !config.dry_run {
+                        run_judge(&prd, &task_dir, base, model, api_diff);
+                    }
+                }
                 break;
             }
 
             // Continue to next requirement
-            println!();
+            say!();
         }
     } else {
         // Single iteration mode (--once flag)
@@ -137,12 +507,324 @@ pub fn run(config: &ImplementConfig) -> Result<()> {
             &mut prd,
             &mut ledger,
             validation_config.as_ref(),
+            &mut checkpoint,
         )?;
     }
 
+    // The run ended on its own terms; there is nothing left to resume
+    if !config.dry_run {
+        checkpoint.remove()?;
+    }
+    if config.project.implement.draft_pr() {
+        open_draft_pr(config, &cwd, &task_dir, &prd, &ledger, &checkpoint);
+    }
+    finish_run(config, &cwd, &prd, &mut ledger)
+}
+
+/// Set a done or blocked requirement targeted with `--req` back to todo so it is redone
+fn reopen_requirement(
+    config: &ImplementConfig,
+    prd_path: &Path,
+    prd: &mut Prd,
+    ledger: &mut Ledger,
+    id: &str,
+) -> Result<()> {
+    let was = match prd
+        .requirements
+        .iter()
+        .find(|r| r.id == id)
+        .map(|r| &r.status)
+    {
+        Some(RequirementStatus::Done) => "done",
+        Some(RequirementStatus::Blocked) => "blocked",
+        _ => return Ok(()),
+    };
+    if config.dry_run {
+        say!("[dry-run] Would reopen {id} (currently {was})");
+        return Ok(());
+    }
+    prd.update_requirement_status(id, RequirementStatus::Todo);
+    prd.save(prd_path)?;
+    ledger.append(
+        LedgerEvent::new(ledger.latest_iteration(), id, EventStatus::InProgress)
+            .with_labels(&config.labels)
+            .with_payload(EventPayload::PlanUpdated {
+                description: format!("reopened {id} (was {was}) for --req"),
+            }),
+    )?;
+    say!("🔁 Reopened {id} (was {was})");
     Ok(())
 }
 
+/// Record `--hint` for requirement `id`, so its next prompt carries it
+fn record_hint(config: &ImplementConfig, ledger: &mut Ledger, id: &str, hint: &str) -> Result<()> {
+    let hint = hint.trim();
+    if hint.is_empty() {
+        return Err(RalphError::Command("Hint is empty".to_string()));
+    }
+    if config.dry_run {
+        say!("[dry-run] Would give {id} the hint: {hint}");
+        return Ok(());
+    }
+    ledger.append(
+        LedgerEvent::hint(ledger.latest_iteration(), id, hint).with_labels(&config.labels),
+    )?;
+    say!("💡 Hint for {id} recorded; its next prompt will carry it");
+    Ok(())
+}
+
+/// The checkpoint to resume with `--resume`, or a fresh one for a new run on `branch`
+fn open_checkpoint(
+    config: &ImplementConfig,
+    task_dir: &Path,
+    prd: &Prd,
+    branch: &str,
+) -> Result<Checkpoint> {
+    match Checkpoint::load(task_dir)? {
+        Some(checkpoint) if config.resume => {
+            if checkpoint.run_id != prd.active_run_id {
+                return Err(RalphError::Command(format!(
+                    "The checkpoint is for run {} but the PRD's active run is {}; \
+                     start a new run without --resume",
+                    checkpoint.run_id, prd.active_run_id
+                )));
+            }
+            say!(
+                "⏯️  Resuming run {} on {} ({} iteration(s) used)",
+                checkpoint.run_id,
+                checkpoint.branch,
+                checkpoint.iterations_used
+            );
+            Ok(checkpoint)
+        }
+        None if config.resume => Err(RalphError::Command(format!(
+            "No interrupted run to resume for '{}' ({} not found)",
+            config.slug,
+            task_dir
+                .join(ralph_lib::checkpoint::CHECKPOINT_FILE)
+                .display()
+        ))),
+        previous => {
+            if previous.is_some() && config.chore.is_none() {
+                say!(
+                    "⚠️  Warning: replacing the checkpoint of an interrupted run (use --resume to continue it)"
+                );
+            }
+            Ok(Checkpoint::new(task_dir, &prd.active_run_id, branch))
+        }
+    }
+}
+
+/// Push the run's branch and open a draft pull request describing it, unless one is open
+fn open_draft_pr(
+    config: &ImplementConfig,
+    cwd: &Path,
+    task_dir: &Path,
+    prd: &Prd,
+    ledger: &Ledger,
+    checkpoint: &Checkpoint,
+) {
+    let branch = checkpoint.branch.as_str();
+    if config.dry_run {
+        say!("[dry-run] Would push {branch} and open a draft pull request");
+        return;
+    }
+
+    let existing = Command::new("gh")
+        .args(["pr", "view", branch, "--json", "url,state"])
+        .args(["--jq", r#"select(.state == "OPEN") | .url"#])
+        .current_dir(cwd)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|url| !url.is_empty());
+    if let Some(url) = existing {
+        say!("📬 Pull request already open: {url}");
+        return;
+    }
+
+    say!("⬆️  Pushing {branch}...");
+    match Command::new("git")
+        .args(["push", "--set-upstream", "origin", branch])
+        .current_dir(cwd)
+        .output()
+    {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => {
+            eprintln!(
+                "⚠️  Failed to push {branch}: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            return;
+        }
+        Err(e) => {
+            eprintln!("⚠️  Failed to run git push: {e}");
+            return;
+        }
+    }
+
+    let body_path = task_dir.join("artifacts").join("pull-request.md");
+    let written = std::fs::create_dir_all(task_dir.join("artifacts")).and_then(|()| {
+        std::fs::write(
+            &body_path,
+            pull_request::render_pr_body(prd, ledger, checkpoint.started_at),
+        )
+    });
+    if let Err(e) = written {
+        eprintln!("⚠️  Failed to write pull request body: {e}");
+        return;
+    }
+
+    let title = pull_request::render_pr_title(prd);
+    let output = Command::new("gh")
+        .args([
+            "pr", "create", "--draft", "--head", branch, "--title", &title,
+        ])
+        .arg("--body-file")
+        .arg(&body_path)
+        .current_dir(cwd)
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            say!(
+                "📬 Opened draft pull request: {}",
+                String::from_utf8_lossy(&output.stdout).trim()
+            );
+        }
+        Ok(output) => eprintln!(
+            "⚠️  Failed to open pull request: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(e) => eprintln!("⚠️  Failed to run gh: {e}"),
+    }
+}
+
+/// How a run left its feature, which `--ci` reports as the exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RunOutcome {
+    /// Every requirement is done
+    Complete,
+    /// Requirements are left to do
+    Incomplete,
+    /// A requirement is blocked and needs a human
+    Blocked,
+}
+
+impl RunOutcome {
+    /// Outcome for `prd`'s current requirement statuses
+    #[must_use]
+    pub fn of(prd: &Prd) -> Self {
+        if prd
+            .requirements
+            .iter()
+            .any(|r| r.status == RequirementStatus::Blocked)
+        {
+            Self::Blocked
+        } else if prd
+            .requirements
+            .iter()
+            .all(|r| r.status == RequirementStatus::Done)
+        {
+            Self::Complete
+        } else {
+            Self::Incomplete
+        }
+    }
+
+    /// Process exit code: 0 complete, 2 incomplete, 3 blocked
+    #[must_use]
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Complete => 0,
+            Self::Incomplete => 2,
+            Self::Blocked => 3,
+        }
+    }
+
+    /// Name used in progress output
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Complete => "complete",
+            Self::Incomplete => "incomplete",
+            Self::Blocked => "blocked",
+        }
+    }
+}
+
+/// Outcome of the feature `config` names, from its PRD as the run left it
+///
+/// # Errors
+///
+/// Returns an error if the PRD cannot be read.
+pub fn feature_outcome(config: &ImplementConfig) -> Result<RunOutcome> {
+    let task_dir = std::env::current_dir()?
+        .join(config.project.paths.tasks())
+        .join(&config.slug);
+    Ok(RunOutcome::of(&Prd::from_file(prd_path(&task_dir))?))
+}
+
+/// Flush remote sync, send the run-finished notification, and note the run's outcome in the
+/// workspace ledger
+fn finish_run(config: &ImplementConfig, cwd: &Path, prd: &Prd, ledger: &mut Ledger) -> Result<()> {
+    report_sync_backlog(ledger);
+    if config.dry_run {
+        return Ok(());
+    }
+    let (done, total) = (
+        prd.requirements
+            .iter()
+            .filter(|r| r.status == RequirementStatus::Done)
+            .count(),
+        prd.requirements.len(),
+    );
+    emit(
+        config,
+        ProgressEvent::RunFinished {
+            slug: config.slug.clone(),
+            done,
+            total,
+            outcome: RunOutcome::of(prd).as_str().to_string(),
+        },
+    );
+    notify_run_finished(ledger, done, total);
+    WorkspaceLedger::record(
+        cwd.join(config.project.paths.workspace_ledger()),
+        &config.slug,
+        WorkspaceActivity::RunFinished { done, total },
+    )
+}
+
+/// Notify that the run ended, with the reason if it was aborted, and warn about delivery errors
+fn notify_run_finished(ledger: &mut Ledger, done: usize, total: usize) {
+    let reason = match ledger.events().last().and_then(|e| e.payload.as_ref()) {
+        Some(EventPayload::RunAborted { reason }) => Some(reason.clone()),
+        _ => None,
+    };
+    let Some(mut notifier) = ledger.notifier_mut().cloned() else {
+        return;
+    };
+    notifier.run_finished(ledger, done, total, reason.as_deref());
+    if let Some(error) = notifier.last_error() {
+        say!("⚠️  Some notifications could not be delivered: {error}");
+    }
+}
+
+/// Retry queued remote sync deliveries and warn about any still undelivered
+fn report_sync_backlog(ledger: &mut Ledger) {
+    let pending = ledger.flush_sync();
+    if pending > 0 {
+        say!(
+            "⚠️  {pending} ledger event(s) not synced to remote targets: {}",
+            ledger
+                .sync()
+                .and_then(|sync| sync.last_error())
+                .unwrap_or("unknown error")
+        );
+    }
+}
+
 /// Run a single iteration of the implementation loop
 ///
 /// Returns Ok(true) if all requirements are complete, Ok(false) if there's more work to do
@@ -153,75 +835,351 @@ fn run_single_iteration(
     prd: &mut Prd,
     ledger: &mut Ledger,
     validation_config: Option<&ValidationConfig>,
+    checkpoint: &mut Checkpoint,
 ) -> Result<bool> {
+    // Pick up an interrupted iteration first, unless its requirement has moved on since (or
+    // isn't the one targeted with --req, or has been skipped)
+    let resumed = checkpoint.current.take().filter(|in_flight| {
+        config.requirement.as_ref().map_or_else(
+            || !prd.is_skipped(&in_flight.requirement),
+            |id| *id == in_flight.requirement,
+        ) && prd.requirements.iter().any(|r| {
+            r.id == in_flight.requirement
+                && matches!(
+                    r.status,
+                    RequirementStatus::Todo | RequirementStatus::InProgress
+                )
+        })
+    });
+
     // Find next requirement to implement
-    let next_req = prd
-        .requirements
-        .iter()
-        .find(|r| r.status == RequirementStatus::Todo || r.status == RequirementStatus::InProgress)
-        .cloned();
+    let next_req = match &resumed {
+        Some(in_flight) => prd
+            .requirements
+            .iter()
+            .find(|r| r.id == in_flight.requirement)
+            .cloned(),
+        None => match &config.requirement {
+            Some(id) => prd
+                .requirements
+                .iter()
+                .find(|r| {
+                    &r.id == id
+                        && matches!(
+                            r.status,
+                            RequirementStatus::Todo | RequirementStatus::InProgress
+                        )
+                })
+                .cloned(),
+            None => config
+                .project
+                .implement
+                .selection()
+                .strategy()
+                .select(prd, ledger)
+                .cloned(),
+        },
+    };
 
     let Some(req) = next_req else {
+        // All planned requirements are done - optionally queue the documentation pass
+        if config.docs_requirement && config.requirement.is_none() {
+            if config.dry_run {
+                if prd.append_docs_requirement().is_some() {
+                    say!("[dry-run] Would append documentation requirement");
+                }
+                return Ok(true);
+            }
+            if let Some(id) = prd.append_docs_requirement() {
+                prd.save(prd_path)?;
+                ledger.append(
+                    LedgerEvent::new(ledger.latest_iteration(), &id, EventStatus::InProgress)
+                        .with_labels(&config.labels)
+                        .with_payload(EventPayload::PlanUpdated {
+                            description: "added documentation requirement".to_string(),
+                        }),
+                )?;
+                say!("📝 Added documentation requirement {id}");
+                return Ok(false);
+            }
+        }
         // No more requirements to implement
         return Ok(true);
     };
 
-    let iteration = ledger.latest_iteration() + 1;
-    let run_full_tests = iteration % 5 == 0;
-
-    println!(
-        "🔄 Iteration {} - Implementing {}: {}",
-        iteration, req.id, req.title
-    );
+    let (iteration, run_full_tests) = match &resumed {
+        Some(in_flight) => {
+            say!(
+                "⏯️  Iteration {} - Resuming {}: {}",
+                in_flight.iteration,
+                req.id,
+                req.title
+            );
+            (in_flight.iteration, in_flight.run_full_tests)
+        }
+        None => {
+            let iteration = ledger.latest_iteration() + 1;
+            say!(
+                "🔄 Iteration {} - Implementing {}: {}",
+                iteration,
+                req.id,
+                req.title
+            );
+            (
+                iteration,
+                req.risk.unwrap_or_default().runs_full_tests(iteration),
+            )
+        }
+    };
 
     if config.dry_run {
-        println!("[dry-run] Would run implementation for {}", req.id);
-        println!("[dry-run] Would run validation (full_tests: {run_full_tests})");
+        say!("[dry-run] Would run implementation for {}", req.id);
+        let agent_finished = matches!(
+            resumed,
+            Some(InFlight {
+                phase: Phase::Validation { .. },
+                ..
+            })
+        );
+        if !agent_finished {
+            let prompt = generate_prompt(
+                config,
+                prd,
+                ledger,
+                &PromptScope {
+                    req: &req,
+                    iteration,
+                    run_full_tests,
+                    scratchpad: &dry_run_scratchpad(prd_path),
+                },
+                validation_config,
+            )?;
+            print_dry_run_prompt(&req.id, &prompt);
+        }
+        say!("[dry-run] Would run validation (full_tests: {run_full_tests})");
         // In dry-run, simulate success but indicate more work remains
         return Ok(false);
     }
 
+    emit(
+        config,
+        ProgressEvent::IterationStarted {
+            iteration,
+            requirement: req.id.clone(),
+            title: req.title.clone(),
+            full_tests: run_full_tests,
+        },
+    );
+
     // Mark requirement as in progress
     prd.update_requirement_status(&req.id, RequirementStatus::InProgress);
     prd.save(prd_path)?;
 
-    // Log start event
-    ledger.append(LedgerEvent::new(iteration, &req.id, EventStatus::Started))?;
-
-    // Generate prompt and launch Copilot
-    let prompt = generate_prompt(prd, &req, ledger, iteration, run_full_tests);
-
-    println!("📝 Launching Copilot implementer...");
-    let copilot_success = launch_copilot_implementer(cwd, &prompt, config.verbose);
+    let (copilot_success, usage, transcript, session, base_sha, snapshot) = match resumed {
+        Some(InFlight {
+            phase: Phase::Validation { agent_succeeded },
+            base_sha,
+            snapshot,
+            ..
+        }) => {
+            say!("⏭️  The agent had already finished; validating its changes");
+            (agent_succeeded, None, None, None, base_sha, snapshot)
+        }
+        resumed => {
+            // Generate prompt and capture what's needed to reproduce this iteration
+            let scratchpad =
+                prepare_scratchpad(prd_path, config.verbose, config.project.models.summarizer())?;
+            let prompt = generate_prompt(
+                config,
+                prd,
+                ledger,
+                &PromptScope {
+                    req: &req,
+                    iteration,
+                    run_full_tests,
+                    scratchpad: &scratchpad,
+                },
+                validation_config,
+            )?;
+            let reproducibility =
+                capture_reproducibility(cwd, &prompt, config.project.models.implementer());
+            let seed = reproducibility.seed;
+
+            // Log start event (a resumed iteration already has one)
+            let (base_sha, snapshot) = match resumed {
+                Some(in_flight) => (in_flight.base_sha, in_flight.snapshot),
+                None => {
+                    ledger.append(
+                        LedgerEvent::new(iteration, &req.id, EventStatus::Started)
+                            .with_labels(&config.labels)
+                            .with_reproducibility(reproducibility)
+                            .with_payload(EventPayload::IterationStarted {
+                                full_tests: Some(run_full_tests),
+                            }),
+                    )?;
+                    (git_head_sha(cwd), snapshot::take(config, cwd))
+                }
+            };
+            checkpoint.current = Some(InFlight {
+                requirement: req.id.clone(),
+                iteration,
+                run_full_tests,
+                base_sha: base_sha.clone(),
+                snapshot: snapshot.clone(),
+                phase: Phase::Agent,
+            });
+            checkpoint.save()?;
+
+            let before = worktree_fingerprint(cwd, &config.project.paths);
+            let session = previous_session(config, ledger, &req.id);
+            say!("📝 Launching Copilot implementer...");
+            let AgentRun {
+                success: copilot_success,
+                timed_out,
+                usage,
+                launch_error,
+                elapsed,
+                transcript,
+                session,
+            } = launch_copilot_implementer(
+                cwd,
+                &prompt,
+                seed,
+                config.verbose,
+                &config.throttle,
+                &config.project,
+                session.as_deref(),
+            );
+            let transcript = save_transcript(ledger, iteration, &transcript, validation_config);
+            emit(
+                config,
+                ProgressEvent::AgentFinished {
+                    iteration,
+                    requirement: req.id.clone(),
+                    success: copilot_success,
+                    duration_secs: elapsed.as_secs_f64(),
+                },
+            );
 
-    // Run validation
-    let (validation_passed, validation_output) = if let Some(vc) = validation_config {
-        if let Some(profile) = prd.validation_profiles.first().and_then(|p| vc.get(p)) {
-            println!("🔍 Running validation...");
-            let results = profile.run_all(cwd, run_full_tests);
-            let all_passed = results.iter().all(|r| r.success);
-
-            // Capture output from first failed stage
-            let failed_output = results
-                .iter()
-                .find(|r| !r.success)
-                .map(|r| format!("Stage: {:?}\n\n{}", r.stage, r.output));
+            // Nothing ran, so there is no result to record; the checkpoint still holds the
+            // iteration, and '--resume' relaunches it
+            if let Some(reason) = launch_error {
+                return Err(RalphError::Copilot(reason));
+            }
 
-            for result in &results {
-                let icon = if result.success { "✅" } else { "❌" };
-                println!("  {} {:?}", icon, result.stage);
+            // A hung agent was killed; its partial work stays for the next iteration unless
+            // failed iterations are rolled back
+            if let Some(limit) = timed_out {
+                let timeout = LedgerEvent::new(iteration, &req.id, EventStatus::InProgress)
+                    .with_labels(&config.labels)
+                    .with_payload(EventPayload::AgentTimedOut {
+                        seconds: limit.as_secs(),
+                    });
+                let mut event = LedgerEvent::new(iteration, &req.id, EventStatus::Failed)
+                    .with_message(format!("agent timed out after {}s", limit.as_secs()))
+                    .with_labels(&config.labels)
+                    .with_payload(EventPayload::IterationFinished { success: false });
+                if let Some(transcript) = &transcript {
+                    event = event.with_transcript(transcript);
+                }
+                if let Some(session) = &session {
+                    event = event.with_agent_session(session);
+                }
+                let event = with_head_commit(event, cwd, base_sha.as_deref());
+                ledger.append_batch(&[timeout, event])?;
+                checkpoint.current = None;
+                say!(
+                    "⚠️  Iteration {iteration} timed out; {} stays in progress",
+                    req.id
+                );
+                escalate_if_exhausted(config, cwd, prd_path, prd, ledger, &req.id)?;
+                snapshot::roll_back(
+                    config,
+                    cwd,
+                    prd_path,
+                    ledger,
+                    snapshot.as_ref(),
+                    LedgerEvent::new(iteration, &req.id, EventStatus::InProgress)
+                        .with_labels(&config.labels),
+                )?;
+                return Ok(false);
             }
 
-            (all_passed, failed_output)
-        } else {
-            (true, None)
+            // An agent that "succeeds" without touching the tree must not complete the requirement
+            if copilot_success
+                && before.is_some()
+                && worktree_fingerprint(cwd, &config.project.paths) == before
+            {
+                let mut event = LedgerEvent::new(iteration, &req.id, EventStatus::Failed)
+                    .with_message(NO_OP_MESSAGE)
+                    .with_labels(&config.labels)
+                    .with_payload(EventPayload::IterationFinished { success: false });
+                event = with_head_commit(event, cwd, base_sha.as_deref());
+                if let Some(usage) = &usage {
+                    event = event.with_usage(usage);
+                }
+                if let Some(transcript) = &transcript {
+                    event = event.with_transcript(transcript);
+                }
+                if let Some(session) = &session {
+                    event = event.with_agent_session(session);
+                }
+                ledger.append(event)?;
+                checkpoint.current = None;
+                say!(
+                    "⚠️  Iteration {iteration} made no changes; {} stays in progress",
+                    req.id
+                );
+                escalate_if_exhausted(config, cwd, prd_path, prd, ledger, &req.id)?;
+                return Ok(false);
+            }
+            (
+                copilot_success,
+                usage,
+                transcript,
+                session,
+                base_sha,
+                snapshot,
+            )
         }
-    } else {
-        (true, None)
     };
 
+    // A run killed from here on resumes at validation instead of rerunning the agent
+    checkpoint.current = Some(InFlight {
+        requirement: req.id.clone(),
+        iteration,
+        run_full_tests,
+        base_sha: base_sha.clone(),
+        snapshot: snapshot.clone(),
+        phase: Phase::Validation {
+            agent_succeeded: copilot_success,
+        },
+    });
+    checkpoint.save()?;
+
+    // Run validation
+    let (validation_passed, validation_output) = run_validation(
+        config,
+        cwd,
+        prd,
+        prd_path,
+        validation_config,
+        &ValidationScope {
+            requirement: &req.id,
+            iteration,
+            base_sha: base_sha.as_deref(),
+            run_full_tests,
+        },
+    );
+
+    // Large or unexpected changes wait for a human before the requirement is marked done
+    let accepted = copilot_success
+        && validation_passed
+        && review_accepted(config, cwd, base_sha.as_deref().unwrap_or("HEAD"), &req.id);
+    let rejected = copilot_success && validation_passed && !accepted;
+
     // Update status based on results
-    let (final_status, event_status) = if copilot_success && validation_passed {
+    let (final_status, event_status) = if accepted {
         (RequirementStatus::Done, EventStatus::Done)
     } else {
         (RequirementStatus::InProgress, EventStatus::Failed)
@@ -229,79 +1187,908 @@ fn run_single_iteration(
 
     prd.update_requirement_status(&req.id, final_status);
     prd.save(prd_path)?;
+    if accepted {
+        emit(
+            config,
+            ProgressEvent::RequirementDone {
+                iteration,
+                requirement: req.id.clone(),
+            },
+        );
+    }
+
+    let commit_error = accepted
+        .then(|| auto_commit(config, cwd, &format!("{}: {}", req.id, req.title)))
+        .flatten();
+
+    let mut events = iteration_details(
+        cwd,
+        base_sha.as_deref(),
+        has_validation_profile(cwd, prd, validation_config)
+            .then_some((validation_passed, validation_output.as_deref())),
+        || {
+            LedgerEvent::new(iteration, &req.id, EventStatus::InProgress)
+                .with_labels(&config.labels)
+        },
+    );
 
     // Build ledger event with validation output if available
-    let mut event =
-        LedgerEvent::new(iteration, &req.id, event_status).with_validation(validation_passed);
+    let mut event = LedgerEvent::new(iteration, &req.id, event_status)
+        .with_validation(validation_passed)
+        .with_labels(&config.labels)
+        .with_payload(EventPayload::IterationFinished { success: accepted });
+    event = with_head_commit(event, cwd, base_sha.as_deref());
+    if let Some(usage) = &usage {
+        event = event.with_usage(usage);
+    }
+    if let Some(transcript) = &transcript {
+        event = event.with_transcript(transcript);
+    }
+    if let Some(session) = &session {
+        event = event.with_agent_session(session);
+    }
+    if rejected {
+        event = event.with_message(REVIEW_REJECTED_MESSAGE);
+    }
+    if let Some(error) = commit_error {
+        event = event.with_message(error);
+    }
     if let Some(output) = validation_output {
-        // Summarize validation output to keep it concise and avoid API request body size issues
-        let summary = summarize_validation_output(&output, config.verbose);
-        event = event.with_validation_output(summary);
+        event = attach_validation_output(ledger, event, &output, config, validation_config)?;
     }
-    ledger.append(event)?;
-
-    if validation_passed {
-        println!("✅ Iteration {iteration} complete");
+    events.push(event);
+    ledger.append_batch(&events)?;
+    checkpoint.current = None;
+
+    if rejected {
+        say!(
+            "🚫 Iteration {iteration} rejected in review; {} stays in progress",
+            req.id
+        );
+    } else if validation_passed {
+        say!("✅ Iteration {iteration} complete");
     } else {
-        println!("❌ Iteration {iteration} failed validation");
+        say!("❌ Iteration {iteration} failed validation");
+    }
+    if !accepted {
+        escalate_if_exhausted(config, cwd, prd_path, prd, ledger, &req.id)?;
+        snapshot::roll_back(
+            config,
+            cwd,
+            prd_path,
+            ledger,
+            snapshot.as_ref(),
+            LedgerEvent::new(iteration, &req.id, EventStatus::InProgress)
+                .with_labels(&config.labels),
+        )?;
     }
 
     // Return false to indicate there may be more requirements to process
     Ok(false)
 }
 
+/// Run a housekeeping iteration that is not tied to any requirement
+///
+/// Chores leave requirement statuses untouched and are recorded as chore events.
+fn run_chore_iteration(
+    config: &ImplementConfig,
+    cwd: &Path,
+    prd_path: &Path,
+    prd: &Prd,
+    ledger: &mut Ledger,
+    validation_config: Option<&ValidationConfig>,
+    description: &str,
+) -> Result<()> {
+    let iteration = ledger.latest_iteration() + 1;
+    say!("🧹 Iteration {iteration} - Chore: {description}");
+
+    if config.dry_run {
+        say!("[dry-run] Would run chore: {description}");
+        let prompt = generate_chore_prompt(
+            config,
+            prd,
+            description,
+            iteration,
+            &dry_run_scratchpad(prd_path),
+        )?;
+        print_dry_run_prompt("chore", &prompt);
+        return Ok(());
+    }
+
+    let scratchpad =
+        prepare_scratchpad(prd_path, config.verbose, config.project.models.summarizer())?;
+    let prompt = generate_chore_prompt(config, prd, description, iteration, &scratchpad)?;
+    let reproducibility =
+        capture_reproducibility(cwd, &prompt, config.project.models.implementer());
+    let seed = reproducibility.seed;
+
+    ledger.append(
+        LedgerEvent::chore(iteration, EventStatus::Started)
+            .with_message(description)
+            .with_labels(&config.labels)
+            .with_reproducibility(reproducibility)
+            .with_payload(EventPayload::IterationStarted {
+                full_tests: Some(false),
+            }),
+    )?;
+
+    emit(
+        config,
+        ProgressEvent::IterationStarted {
+            iteration,
+            requirement: CHORE_REQUIREMENT.to_string(),
+            title: description.to_string(),
+            full_tests: false,
+        },
+    );
+
+    let before = worktree_fingerprint(cwd, &config.project.paths);
+    let base_sha = git_head_sha(cwd);
+    let snapshot = snapshot::take(config, cwd);
+    say!("📝 Launching Copilot implementer...");
+    let AgentRun {
+        success: copilot_success,
+        timed_out,
+        usage,
+        launch_error,
+        elapsed,
+        transcript,
+        ..
+    } = launch_copilot_implementer(
+        cwd,
+        &prompt,
+        seed,
+        config.verbose,
+        &config.throttle,
+        &config.project,
+        None,
+    );
+    let transcript = save_transcript(ledger, iteration, &transcript, validation_config);
+    emit(
+        config,
+        ProgressEvent::AgentFinished {
+            iteration,
+            requirement: CHORE_REQUIREMENT.to_string(),
+            success: copilot_success,
+            duration_secs: elapsed.as_secs_f64(),
+        },
+    );
+    if let Some(reason) = launch_error {
+        return Err(RalphError::Copilot(reason));
+    }
+
+    let mut events = Vec::new();
+    let mut event = if let Some(limit) = timed_out {
+        say!("❌ Chore timed out");
+        events.push(
+            LedgerEvent::chore(iteration, EventStatus::InProgress)
+                .with_labels(&config.labels)
+                .with_payload(EventPayload::AgentTimedOut {
+                    seconds: limit.as_secs(),
+                }),
+        );
+        LedgerEvent::chore(iteration, EventStatus::Failed)
+            .with_message(format!("agent timed out after {}s", limit.as_secs()))
+            .with_payload(EventPayload::IterationFinished { success: false })
+    } else if copilot_success
+        && before.is_some()
+        && worktree_fingerprint(cwd, &config.project.paths) == before
+    {
+        say!("⚠️  Chore made no changes");
+        LedgerEvent::chore(iteration, EventStatus::Failed)
+            .with_message(NO_OP_MESSAGE)
+            .with_payload(EventPayload::IterationFinished { success: false })
+    } else {
+        let (validation_passed, validation_output) = run_validation(
+            config,
+            cwd,
+            prd,
+            prd_path,
+            validation_config,
+            &ValidationScope {
+                requirement: CHORE_REQUIREMENT,
+                iteration,
+                base_sha: base_sha.as_deref(),
+                run_full_tests: false,
+            },
+        );
+        let accepted = copilot_success
+            && validation_passed
+            && review_accepted(config, cwd, base_sha.as_deref().unwrap_or("HEAD"), "chore");
+        let commit_error = accepted
+            .then(|| auto_commit(config, cwd, &format!("CHORE: {description}")))
+            .flatten();
+        events = iteration_details(
+            cwd,
+            base_sha.as_deref(),
+            has_validation_profile(cwd, prd, validation_config)
+                .then_some((validation_passed, validation_output.as_deref())),
+            || LedgerEvent::chore(iteration, EventStatus::InProgress).with_labels(&config.labels),
+        );
+        let status = if accepted {
+            say!("✅ Chore complete");
+            EventStatus::Done
+        } else if copilot_success && validation_passed {
+            say!("🚫 Chore rejected in review");
+            EventStatus::Failed
+        } else {
+            say!("❌ Chore failed");
+            EventStatus::Failed
+        };
+        let mut event = LedgerEvent::chore(iteration, status.clone())
+            .with_message(match commit_error {
+                Some(error) => format!("{description} ({error})"),
+                None => description.to_string(),
+            })
+            .with_validation(validation_passed)
+            .with_payload(EventPayload::IterationFinished {
+                success: status == EventStatus::Done,
+            });
+        if let Some(output) = validation_output {
+            event = attach_validation_output(ledger, event, &output, config, validation_config)?;
+        }
+        event
+    }
+    .with_labels(&config.labels);
+    event = with_head_commit(event, cwd, base_sha.as_deref());
+    if let Some(usage) = &usage {
+        event = event.with_usage(usage);
+    }
+    if let Some(transcript) = transcript {
+        event = event.with_transcript(transcript);
+    }
+    let failed = event.status == EventStatus::Failed;
+    events.push(event);
+    ledger.append_batch(&events)?;
+    if failed {
+        snapshot::roll_back(
+            config,
+            cwd,
+            prd_path,
+            ledger,
+            snapshot.as_ref(),
+            LedgerEvent::chore(iteration, EventStatus::InProgress).with_labels(&config.labels),
+        )?;
+    }
+    Ok(())
+}
+
+/// Escalate a requirement to Blocked once it has used up its attempt budget or is stuck
+/// failing validation
+///
+/// Writes a hand-off document under artifacts and optionally files a GitHub issue.
+fn escalate_if_exhausted(
+    config: &ImplementConfig,
+    cwd: &Path,
+    prd_path: &Path,
+    prd: &mut Prd,
+    ledger: &mut Ledger,
+    req_id: &str,
+) -> Result<()> {
+    let attempts = handoff::failed_attempts(ledger, req_id);
+    let exhausted = config
+        .max_attempts
+        .is_some_and(|max_attempts| attempts >= max_attempts as usize);
+    let streak = handoff::consecutive_validation_failures(ledger, req_id);
+    let stuck = config
+        .project
+        .implement
+        .stuck_after()
+        .is_some_and(|stuck_after| streak >= stuck_after as usize);
+    if !exhausted && !stuck {
+        return Ok(());
+    }
+    let Some(req) = prd.requirements.iter().find(|r| r.id == req_id).cloned() else {
+        return Ok(());
+    };
+
+    let diff = Command::new("git")
+        .args(["diff", "HEAD"])
+        .args(snapshot::excludes(&config.project.paths))
+        .current_dir(cwd)
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+        .unwrap_or_default();
+    let artifacts = prd_path.with_file_name("artifacts");
+    std::fs::create_dir_all(&artifacts)?;
+    let handoff_path = artifacts.join(format!("handoff-{req_id}.md"));
+    std::fs::write(
+        &handoff_path,
+        handoff::render_handoff(prd, &req, ledger, &diff),
+    )?;
+
+    let reason = if exhausted {
+        format!("{attempts} failed attempt(s)")
+    } else {
+        format!(
+            "{streak} consecutive validation failures: {}",
+            handoff::failure_summary(ledger, req_id, streak)
+        )
+    };
+    prd.update_requirement_status(req_id, RequirementStatus::Blocked);
+    prd.save(prd_path)?;
+    ledger.append(
+        LedgerEvent::new(ledger.latest_iteration(), req_id, EventStatus::InProgress)
+            .with_message(format!(
+                "{} after {reason}; hand-off at {}",
+                handoff::ESCALATED_MESSAGE,
+                handoff_path.display()
+            ))
+            .with_labels(&config.labels),
+    )?;
+    say!(
+        "🚫 {req_id} blocked after {reason}; hand-off: {}",
+        handoff_path.display()
+    );
+
+    if config.open_issue {
+        let title = format!("[ralph] {} {req_id}: {}", prd.slug, req.title);
+        let output = Command::new("gh")
+            .args(["issue", "create", "--title", &title, "--body-file"])
+            .arg(&handoff_path)
+            .current_dir(cwd)
+            .output();
+        match output {
+            Ok(output) if output.status.success() => {
+                say!(
+                    "📮 Opened issue: {}",
+                    String::from_utf8_lossy(&output.stdout).trim()
+                );
+            }
+            Ok(output) => eprintln!(
+                "⚠️  Failed to open GitHub issue: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Err(e) => eprintln!("⚠️  Failed to run gh: {e}"),
+        }
+    }
+    Ok(())
+}
+
+/// The iteration a validation run checks
+struct ValidationScope<'a> {
+    /// Requirement ID, or [`CHORE_REQUIREMENT`] for chores
+    requirement: &'a str,
+    iteration: u32,
+    /// Commit the iteration started from; incremental commands cover changes since it
+    base_sha: Option<&'a str>,
+    run_full_tests: bool,
+}
+
+/// Run every validation profile the PRD lists (or every detected one if it lists none)
+///
+/// Commands have `{slug}`, `{requirement}`, `{iteration}`, and `{branch}` expanded.
+/// Stages that already passed on the same tree state during this run are skipped, and
+/// stages with incremental commands are scoped to the files changed since `base_sha`.
+/// Secrets are redacted from stage output before anything is recorded or summarized.
+/// Returns whether all profiles passed and the output of each profile's first failed
+/// stage, joined in profile order. Failures of `allowFailure` stages don't count against
+/// passing; their output follows, headed `Allowed failure: <stage>`, so it is still
+/// recorded in the ledger.
+fn run_validation(
+    config: &ImplementConfig,
+    cwd: &Path,
+    prd: &Prd,
+    prd_path: &Path,
+    validation_config: Option<&ValidationConfig>,
+    scope: &ValidationScope,
+) -> (bool, Option<String>) {
+    let Some(vc) = validation_config else {
+        return (true, None);
+    };
+    let profiles = vc.profiles_for(&prd.validation_profiles, cwd);
+    if profiles.is_empty() {
+        return (true, None);
+    }
+
+    // Secrets printed by failing commands must not reach the ledger, the summarizer, or
+    // the full output kept under artifacts/
+    let redactor = vc.redactor().unwrap_or_else(|e| {
+        eprintln!("⚠️  {e}; redacting built-in token patterns only");
+        Redactor::default()
+    });
+    // The JSON progress stream has stdout to itself
+    let capture = CaptureOptions {
+        stream: !config.quiet && config.progress_format == ProgressFormat::Text,
+        redactor,
+        ..vc.capture_options(prd_path.with_file_name("artifacts"))
+    };
+    let paths = &config.project.paths;
+    let tree_state = tree_state(cwd, paths);
+    let changed = scope
+        .base_sha
+        .and_then(|base| changed_files(cwd, paths, base));
+    let iteration = scope.iteration.to_string();
+    let branch = config
+        .project
+        .implement
+        .branch_name(&prd.slug, &prd.active_run_id);
+    let vars = [
+        ("slug", prd.slug.as_str()),
+        ("requirement", scope.requirement),
+        ("iteration", iteration.as_str()),
+        ("branch", branch.as_str()),
+    ];
+    let mut failures = Vec::new();
+    let mut allowed_failures = Vec::new();
+    let mut reports = Vec::new();
+    for &(name, profile) in &profiles {
+        let profile = profile.with_vars(&vars);
+        let scoped = changed
+            .as_deref()
+            .map(|changed| profile.incremental(cwd, changed));
+        let profile = scoped.as_ref().unwrap_or(&profile);
+        if profiles.len() > 1 {
+            say!("🔍 Running validation ({name})...");
+        } else {
+            say!("🔍 Running validation...");
+        }
+        let results = match &tree_state {
+            Some(state) => profile.run_all_cached(
+                cwd,
+                scope.run_full_tests,
+                &capture,
+                &config.validation_cache,
+                state,
+            ),
+            None => profile.run_all_with(cwd, scope.run_full_tests, &capture),
+        };
+        let profile_name = (profiles.len() > 1).then_some(name);
+        // Capture output from first failed stage (an excerpt if it was oversized)
+        if let Some(r) = results.iter().find(|r| r.is_blocking()) {
+            failures.push(failure_output("Stage", r, profile_name));
+        }
+        for r in results.iter().filter(|r| r.allowed_failure) {
+            allowed_failures.push(failure_output("Allowed failure", r, profile_name));
+        }
+
+        for result in &results {
+            let icon = if result.success {
+                "✅"
+            } else if result.allowed_failure {
+                "⚠️"
+            } else {
+                "❌"
+            };
+            let attempts = if result.cached {
+                " (cached)".to_string()
+            } else if result.attempts > 1 {
+                format!(" ({} attempts)", result.attempts)
+            } else {
+                String::new()
+            };
+            say!("  {} {:?}{attempts}", icon, result.stage);
+            emit(
+                config,
+                ProgressEvent::ValidationStage {
+                    iteration: scope.iteration,
+                    requirement: scope.requirement.to_string(),
+                    profile: profile_name.map(str::to_string),
+                    stage: result.stage.as_str().to_string(),
+                    passed: result.success,
+                    allowed_failure: result.allowed_failure,
+                    cached: result.cached,
+                },
+            );
+        }
+        reports.push((name, results));
+    }
+
+    if let Some(dir) = &config.report_dir {
+        if let Err(e) = write_validation_reports(dir, &reports) {
+            eprintln!("⚠️  Failed to write validation reports: {e}");
+        }
+    }
+
+    let all_passed = failures.is_empty();
+    failures.extend(allowed_failures);
+    (
+        all_passed,
+        (!failures.is_empty()).then(|| failures.join("\n\n")),
+    )
+}
+
+/// Format a failed stage's output under a `<heading>: <stage>` first line
+fn failure_output(heading: &str, r: &ValidationResult, profile: Option<&str>) -> String {
+    let mut output = format!("{heading}: {:?}\n\n", r.stage);
+    if let Some(name) = profile {
+        output.push_str(&format!("Profile: {name}\n\n"));
+    }
+    // Precise locations first, so they survive summarizing and truncation
+    if !r.diagnostics.is_empty() {
+        output.push_str("Diagnostics:\n");
+        for diagnostic in &r.diagnostics {
+            output.push_str(&format!("- {diagnostic}\n"));
+        }
+        output.push_str("\nOutput:\n");
+    }
+    output.push_str(&r.output);
+    if let Some(path) = &r.full_output_path {
+        output.push_str(&format!("\n\nFull output: {}", path.display()));
+    }
+    output
+}
+
+/// Write the latest validation run as JUnit XML and SARIF for CI systems to ingest
+fn write_validation_reports(dir: &Path, reports: &[(&str, Vec<ValidationResult>)]) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(
+        dir.join("validation.junit.xml"),
+        report::to_junit_xml(reports),
+    )?;
+    std::fs::write(
+        dir.join("validation.sarif"),
+        serde_json::to_string_pretty(&report::to_sarif(reports))?,
+    )?;
+    Ok(())
+}
+
+/// Create the feature's scratchpad if needed and compact it when it has grown too large
+///
+/// Returns the scratchpad's absolute path so agents in parallel worktrees share one file.
+fn prepare_scratchpad(prd_path: &Path, verbose: bool, model: &str) -> Result<PathBuf> {
+    let path = scratchpad::scratchpad_path(prd_path.parent().unwrap_or(Path::new(".")));
+    scratchpad::ensure(&path)?;
+    let path = path.canonicalize().unwrap_or(path);
+
+    let content = std::fs::read_to_string(&path)?;
+    if content.len() > scratchpad::DEFAULT_MAX_BYTES {
+        if verbose {
+            say!("🗒️  Compacting scratchpad ({} bytes)...", content.len());
+        }
+        let prompt = format!(
+            "Condense these working notes from an implementation agent into a markdown \
+             scratchpad under {} bytes. Keep open TODOs, decisions, and gotchas; drop \
+             anything resolved. Output only the new scratchpad:\n\n{content}",
+            scratchpad::DEFAULT_MAX_BYTES / 2
+        );
+        let summary = Command::new("copilot")
+            .args(["-p", &prompt, "--model", model, "--silent"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|summary| {
+                !summary.is_empty() && summary.len() <= scratchpad::DEFAULT_MAX_BYTES
+            });
+        let compacted = summary.unwrap_or_else(|| {
+            scratchpad::truncate_to_tail(&content, scratchpad::DEFAULT_MAX_BYTES)
+        });
+        std::fs::write(&path, compacted)?;
+    }
+    Ok(path)
+}
+
+/// Attach failed validation output to an event: a summary inline, the full text in a sidecar log
+fn attach_validation_output(
+    ledger: &Ledger,
+    event: LedgerEvent,
+    output: &str,
+    config: &ImplementConfig,
+    validation_config: Option<&ValidationConfig>,
+) -> Result<LedgerEvent> {
+    let limits = output_limits(validation_config, output);
+    let summary = ledger_validation_output(output, config.verbose, &limits, &config.project);
+    let event = event.with_validation_output(summary);
+    Ok(match ledger.save_validation_log(event.iteration, output)? {
+        Some(log) => event.with_validation_log(log),
+        None => event,
+    })
+}
+
+/// Agent session to resume for `req_id`: the one its previous attempt recorded, unless
+/// sessions are turned off
+fn previous_session(config: &ImplementConfig, ledger: &Ledger, req_id: &str) -> Option<String> {
+    if !config.project.implement.agent_sessions() {
+        return None;
+    }
+    let session = ledger.last_agent_session(req_id)?;
+    say!("🔁 Resuming the agent session from {req_id}'s last attempt");
+    Some(session)
+}
+
+/// Save an agent's output, redacted, next to the ledger, returning its path for the
+/// iteration's event
+///
+/// A transcript only helps post-mortems, so failing to write one just warns.
+fn save_transcript(
+    ledger: &Ledger,
+    iteration: u32,
+    transcript: &str,
+    validation_config: Option<&ValidationConfig>,
+) -> Option<String> {
+    if transcript.is_empty() {
+        return None;
+    }
+    let redactor = validation_config
+        .map_or_else(|| Ok(Redactor::default()), ValidationConfig::redactor)
+        .unwrap_or_default();
+    ledger
+        .save_transcript(iteration, &redactor.redact(transcript))
+        .unwrap_or_else(|e| {
+            say!("⚠️  Failed to save the agent transcript: {e}");
+            None
+        })
+}
+
+/// Output limits for a failure, by the stage named in its `Stage: <stage>` header
+fn output_limits(validation_config: Option<&ValidationConfig>, output: &str) -> OutputLimits {
+    let stage = output
+        .lines()
+        .next()
+        .and_then(|line| line.split_once(": "))
+        .and_then(|(_, stage)| {
+            ValidationStage::all()
+                .iter()
+                .copied()
+                .find(|s| s.as_str().eq_ignore_ascii_case(stage.trim()))
+        });
+    validation_config.map_or_else(OutputLimits::default, |vc| vc.output_limits(stage))
+}
+
+/// Write a lifecycle event to stdout if the JSON progress stream is on
+fn emit(config: &ImplementConfig, event: ProgressEvent) {
+    if config.progress_format == ProgressFormat::Json {
+        println!("{}", ProgressRecord::now(event).to_json_line());
+    }
+}
+
+/// Scratchpad a prompt names, without creating or compacting it
+fn dry_run_scratchpad(prd_path: &Path) -> PathBuf {
+    let path = scratchpad::scratchpad_path(prd_path.parent().unwrap_or(Path::new(".")));
+    path.canonicalize().unwrap_or(path)
+}
+
+/// Print the prompt a dry run would have sent, with its estimated size
+fn print_dry_run_prompt(label: &str, prompt: &str) {
+    let rule = "─".repeat(72);
+    say!(
+        "[dry-run] Prompt for {label} (~{} tokens):",
+        prompt_budget::estimate_tokens(prompt)
+    );
+    say!("{rule}\n{prompt}\n{rule}");
+}
+
+/// Summarize failed validation output for the ledger, unless it is within `limits`
+///
+/// The heuristic summarizer extracts errors locally; with summarization off, output over the
+/// limit is truncated to its first and last lines.
+fn ledger_validation_output(
+    output: &str,
+    verbose: bool,
+    limits: &OutputLimits,
+    project: &Config,
+) -> String {
+    if !limits.summarize_above_chars().exceeded_by(output.len()) {
+        return output.to_string();
+    }
+    // Summarize validation output to keep it concise and avoid API request body size issues
+    let summary = match project.summarizer.mode() {
+        SummarizerMode::Agent => {
+            summarize_validation_output(output, verbose, limits.prompt_chars(), project)
+        }
+        SummarizerMode::Heuristic => heuristic_summary(output, limits.prompt_chars()),
+        SummarizerMode::Off => return truncate_to_limit(output, limits.prompt_chars()),
+    };
+    // Keep the "Stage: ..." header so ledger analytics can attribute the failure
+    match output.lines().next() {
+        Some(stage) if !summary.starts_with(stage) => format!("{stage}\n\n{summary}"),
+        _ => summary,
+    }
+}
+
+/// The iteration a prompt is generated for
+struct PromptScope<'a> {
+    req: &'a ralph_lib::Requirement,
+    iteration: u32,
+    run_full_tests: bool,
+    /// Working memory file the agent reads and updates
+    scratchpad: &'a Path,
+}
+
+/// Render the implementer prompt from the repository's template (or the built-in one)
 fn generate_prompt(
+    config: &ImplementConfig,
     prd: &Prd,
-    req: &ralph_lib::Requirement,
     ledger: &Ledger,
-    iteration: u32,
-    run_full_tests: bool,
-) -> String {
-    let mut prompt = format!(
-        "Implement requirement {} for feature '{}' (iteration {}).\n\n\
-         Title: {}\n\n\
-         Acceptance Criteria:\n{}\n\n\
-         Validation: fmt -> lint -> typecheck{}\n\n\
-         Update PRD status only after validation passes.",
-        req.id,
-        prd.slug,
-        iteration,
-        req.title,
+    scope: &PromptScope,
+    validation_config: Option<&ValidationConfig>,
+) -> Result<String> {
+    let template = PromptTemplate::load(config.project.paths.prompts(), PromptKind::Implement)?;
+    let req = scope.req;
+    let mut vars = PromptVars::new();
+    vars.insert("slug", prd.slug.clone());
+    vars.insert("feature_title", prd.title.clone());
+    vars.insert("req_id", req.id.clone());
+    vars.insert("req_title", req.title.clone());
+    vars.insert("iteration", scope.iteration.to_string());
+    vars.insert(
+        "acceptance_criteria",
         req.acceptance_criteria
             .iter()
             .map(|ac| format!("- {ac}"))
             .collect::<Vec<_>>()
             .join("\n"),
-        if run_full_tests { " -> test" } else { "" }
+    );
+    vars.insert(
+        "validation_stages",
+        if scope.run_full_tests {
+            "fmt -> lint -> typecheck -> test"
+        } else {
+            "fmt -> lint -> typecheck"
+        }
+        .to_string(),
+    );
+    vars.insert("full_tests", flag(scope.run_full_tests));
+    if let Some(hints) = req.prompt_hints.as_ref().filter(|h| !h.is_empty()) {
+        vars.insert(
+            "prompt_hints",
+            format_prompt_hints(hints).trim_start().to_string(),
+        );
+    }
+    vars.insert(
+        "operator_hints",
+        ledger
+            .pending_hints(&req.id)
+            .iter()
+            .map(|hint| format!("- {}", hint.message.as_deref().unwrap_or_default()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    vars.insert("scratchpad", scope.scratchpad.display().to_string());
+    if config.project.implement.repo_map() {
+        vars.insert("repo_map", build_repo_map(config, req, ledger));
+    }
+    vars.insert(
+        "team_notes",
+        ledger
+            .notes_for_requirement(&req.id)
+            .iter()
+            .map(|note| {
+                format!(
+                    "- After iteration {}: {}",
+                    note.iteration,
+                    note.message.as_deref().unwrap_or_default()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    vars.insert(
+        "previous_noop",
+        flag(ledger.is_last_iteration_noop(&req.id)),
     );
 
-    // Add validation failure feedback if previous iteration failed
-    if iteration > 1 {
+    // Add validation failure feedback if previous iteration failed, capped by the stage's
+    // prompt limit (2000 chars by default, which should be enough to show the key errors)
+    let mut failure_tokens = None;
+    if scope.iteration > 1 {
         if let Some(validation_output) = ledger.get_last_validation_failure(&req.id) {
-            prompt.push_str("\n\n⚠️  PREVIOUS ITERATION FAILED VALIDATION:\n\n");
-
-            // Truncate validation output to prevent API request body size issues
-            // Keep first 2000 chars which should be enough to show the key errors
-            const MAX_VALIDATION_OUTPUT: usize = 2000;
-            if validation_output.len() > MAX_VALIDATION_OUTPUT {
-                prompt.push_str(&validation_output[..MAX_VALIDATION_OUTPUT]);
-                prompt.push_str(&format!(
-                    "\n\n... (truncated {} chars) ...\n",
-                    validation_output.len() - MAX_VALIDATION_OUTPUT
-                ));
-            } else {
-                prompt.push_str(&validation_output);
-            }
-
-            prompt.push_str(
-                "\n\n🚨 YOU MUST FIX THESE ERRORS BEFORE FINISHING.\n\
-                 Read the error output above and fix the root cause.\n\
-                 DO NOT finish your work until validation passes.",
-            );
+            let limit = output_limits(validation_config, &validation_output).prompt_chars();
+            failure_tokens = limit.max_chars().map(|max| max / CHARS_PER_TOKEN);
+            vars.insert("validation_failure", validation_output);
         }
     }
 
-    prompt
+    // Requirement, criteria, and hints stay whole; the rest shares what's left of the budget
+    let flexible = [
+        Flexible {
+            name: "validation_failure",
+            weight: 5,
+            trim: Trim::HeadAndTail,
+            max_tokens: failure_tokens,
+        },
+        Flexible {
+            name: "repo_map",
+            weight: 3,
+            trim: Trim::FirstLines,
+            max_tokens: None,
+        },
+        Flexible {
+            name: "team_notes",
+            weight: 2,
+            trim: Trim::LastLines,
+            max_tokens: None,
+        },
+    ];
+    Ok(prompt_budget::fit(
+        &template,
+        vars,
+        config.project.implement.prompt_tokens(),
+        &flexible,
+    ))
+}
+
+/// Map of the tracked files most likely relevant to `req`, empty outside a git repository
+fn build_repo_map(
+    config: &ImplementConfig,
+    req: &ralph_lib::Requirement,
+    ledger: &Ledger,
+) -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+    };
+    let tasks = format!(":(exclude){}", config.project.paths.tasks().display());
+    let docs = format!(":(exclude){}", config.project.paths.docs().display());
+    let Some(files) = git(&["ls-files", "--", ".", &tasks, &docs]) else {
+        return String::new();
+    };
+
+    // Files the requirement's latest iterations changed, most recent first
+    let changed: Vec<String> = ledger
+        .events_for_requirement(&req.id)
+        .iter()
+        .rev()
+        .filter_map(|event| event.diff_range.as_deref())
+        .take(3)
+        .filter_map(|range| git(&["diff", "--name-only", range]))
+        .flatten()
+        .collect();
+    let query = RepoMapQuery::for_requirement(req, ledger, &files, &changed);
+    repo_map::build(Path::new("."), &files, &query)
+}
+
+/// Render the chore prompt from the repository's template (or the built-in one)
+fn generate_chore_prompt(
+    config: &ImplementConfig,
+    prd: &Prd,
+    description: &str,
+    iteration: u32,
+    scratchpad: &Path,
+) -> Result<String> {
+    let template = PromptTemplate::load(config.project.paths.prompts(), PromptKind::Chore)?;
+    let mut vars = PromptVars::new();
+    vars.insert("slug", prd.slug.clone());
+    vars.insert("feature_title", prd.title.clone());
+    vars.insert("iteration", iteration.to_string());
+    vars.insert("description", description.to_string());
+    vars.insert("scratchpad", scratchpad.display().to_string());
+    Ok(template.render(&vars))
+}
+
+/// Template value for a condition: non-empty when true, so `{{#if name}}` keeps its body
+fn flag(value: bool) -> String {
+    if value {
+        "true".to_string()
+    } else {
+        String::new()
+    }
+}
+
+/// Render a requirement's prompt hints as extra prompt sections
+fn format_prompt_hints(hints: &ralph_lib::PromptHints) -> String {
+    let mut section = String::new();
+    for (heading, items) in [
+        ("Additional instructions", &hints.instructions),
+        ("Constraints", &hints.constraints),
+        (
+            "Forbidden approaches (do NOT do these)",
+            &hints.forbidden_approaches,
+        ),
+    ] {
+        if items.is_empty() {
+            continue;
+        }
+        section.push_str(&format!("\n\n{heading}:\n"));
+        section.push_str(
+            &items
+                .iter()
+                .map(|item| format!("- {item}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+    }
+    section
 }
 
 /// Smart truncation of validation output
@@ -348,9 +2135,28 @@ fn smart_truncate_validation_output(output: &str, max_chars: usize) -> String {
     result
 }
 
-/// Summarize validation output using copilot CLI
+/// Smart truncation to a configured limit (none for `"full"`)
+fn truncate_to_limit(output: &str, limit: CharLimit) -> String {
+    match limit.max_chars() {
+        Some(max_chars) => smart_truncate_validation_output(output, max_chars),
+        None => output.to_string(),
+    }
+}
+
+/// Errors and failing tests extracted from validation output without a model, or its
+/// smart truncation if none are recognized
+fn heuristic_summary(output: &str, fallback_limit: CharLimit) -> String {
+    summary::summarize(output).unwrap_or_else(|| truncate_to_limit(output, fallback_limit))
+}
+
+/// Summarize validation output using copilot CLI, or the configured summarizer command
 /// Returns a concise summary (3-5 bullet points) of the validation errors
-fn summarize_validation_output(validation_output: &str, verbose: bool) -> String {
+fn summarize_validation_output(
+    validation_output: &str,
+    verbose: bool,
+    fallback_limit: CharLimit,
+    project: &Config,
+) -> String {
     if validation_output.is_empty() {
         return String::new();
     }
@@ -362,20 +2168,28 @@ fn summarize_validation_output(validation_output: &str, verbose: bool) -> String
         validation_output
     );
 
+    let model = project.models.summarizer();
+    let command = project.summarizer.command.as_deref();
     if verbose {
-        println!("🤖 Summarizing validation output with copilot...");
+        say!(
+            "🤖 Summarizing validation output with {}...",
+            command.unwrap_or("copilot")
+        );
     }
 
-    let result = Command::new("copilot")
-        .args([
-            "-p",
-            &prompt,
-            "--model",
-            "gpt-5-mini",
-            "--silent",
-            "--allow-all-tools",
-        ])
-        .output();
+    let result = match command {
+        Some(command) => run_summarizer_command(&command.replace("{model}", model), &prompt),
+        None => Command::new("copilot")
+            .args([
+                "-p",
+                &prompt,
+                "--model",
+                model,
+                "--silent",
+                "--allow-all-tools",
+            ])
+            .output(),
+    };
 
     match result {
         Ok(cmd_output) if cmd_output.status.success() => {
@@ -383,7 +2197,7 @@ fn summarize_validation_output(validation_output: &str, verbose: bool) -> String
                 .trim()
                 .to_string();
             if verbose {
-                println!("✅ Validation summary generated ({} chars)", summary.len());
+                say!("✅ Validation summary generated ({} chars)", summary.len());
             }
             summary
         }
@@ -392,27 +2206,244 @@ fn summarize_validation_output(validation_output: &str, verbose: bool) -> String
                 "⚠️  Failed to summarize validation output: {}",
                 String::from_utf8_lossy(&cmd_output.stderr)
             );
-            // Fallback: smart truncation
-            smart_truncate_validation_output(validation_output, 2000)
+            // Fallback: summarize offline
+            heuristic_summary(validation_output, fallback_limit)
         }
         Err(e) => {
-            eprintln!("⚠️  Error calling copilot for summarization: {e}");
-            // Fallback: smart truncation
-            smart_truncate_validation_output(validation_output, 2000)
+            eprintln!(
+                "⚠️  Error calling {} for summarization: {e}",
+                command.unwrap_or("copilot")
+            );
+            // Fallback: summarize offline
+            heuristic_summary(validation_output, fallback_limit)
+        }
+    }
+}
+
+/// Run a `[summarizer] command` through the shell with `prompt` on its stdin
+fn run_summarizer_command(command: &str, prompt: &str) -> std::io::Result<std::process::Output> {
+    let mut child = Shell::platform_default()
+        .command(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(prompt.as_bytes())?;
+    }
+    child.wait_with_output()
+}
+
+/// How an implementer agent call ended
+#[derive(Default)]
+struct AgentRun {
+    /// The agent exited successfully
+    success: bool,
+    /// The agent was killed after running this long (on its last attempt)
+    timed_out: Option<Duration>,
+    /// Token usage the agent reported
+    usage: Option<TokenUsage>,
+    /// Why the agent could not be run at all; the iteration says nothing about the requirement
+    launch_error: Option<String>,
+    /// Wall-clock time across all attempts
+    elapsed: Duration,
+    /// Everything the agent printed, attempt by attempt
+    transcript: String,
+    /// Session the agent ran in, for the requirement's next attempt to resume
+    session: Option<String>,
+}
+
+/// How a single agent process ended
+enum AgentExit {
+    /// The agent exited on its own, successfully or not
+    Exited(bool),
+    /// The agent was killed at the timeout
+    TimedOut,
+    /// The agent process could not be started
+    LaunchFailed(std::io::Error),
+}
+
+/// Launch the implementer agent, streaming its output while capturing token usage
+///
+/// Transient failures (rate limits, network errors, spawn errors) are retried with jittered
+/// exponential backoff, and calls that run past the configured agent timeout are killed and
+/// retried up to the configured number of times. A missing `copilot` binary, or a transient
+/// failure that outlasts the retries, is reported in `launch_error`.
+///
+/// With a `session`, the agent resumes it and keeps the context of the earlier attempt; a
+/// session copilot no longer knows is dropped and the call retried cold.
+fn launch_copilot_implementer(
+    working_dir: &Path,
+    prompt: &str,
+    seed: u64,
+    verbose: bool,
+    throttle: &Throttle,
+    project: &Config,
+    session: Option<&str>,
+) -> AgentRun {
+    let model = project.models.implementer();
+    let timeout = project.implement.agent_timeout();
+    let started = Instant::now();
+    let mut attempt = 0;
+    let mut timeouts = 0;
+    let mut transcript = String::new();
+    let mut session = session.map(str::to_string);
+    loop {
+        let (exit, captured) = {
+            let _permit = throttle.acquire();
+            run_copilot_implementer(
+                working_dir,
+                prompt,
+                seed,
+                verbose,
+                model,
+                timeout,
+                session.as_deref(),
+            )
+        };
+        let ended = match &exit {
+            AgentExit::Exited(true) => "exited successfully".to_string(),
+            AgentExit::Exited(false) => "exited with failure".to_string(),
+            AgentExit::TimedOut => "timed out".to_string(),
+            AgentExit::LaunchFailed(e) => format!("failed to launch: {e}"),
+        };
+        transcript.push_str(&format!(
+            "=== attempt {} ({ended}) ===\n{captured}",
+            attempt + timeouts + 1
+        ));
+
+        if matches!(exit, AgentExit::Exited(false))
+            && session.is_some()
+            && unknown_session(&captured)
+        {
+            say!("⚠️  Agent session could not be resumed; starting a new one");
+            session = None;
+            continue;
+        }
+        if let Some(id) = parse_copilot_session(&captured) {
+            session = Some(id);
         }
+
+        let transient = match &exit {
+            AgentExit::Exited(success) => (!success)
+                .then(|| throttle::transient_failure(&captured))
+                .flatten()
+                .map(|failure| failure.to_string()),
+            AgentExit::TimedOut => None,
+            AgentExit::LaunchFailed(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                say!("❌ Error: 'copilot' command not found");
+                return AgentRun {
+                    launch_error: Some("'copilot' command not found".to_string()),
+                    elapsed: started.elapsed(),
+                    transcript,
+                    ..AgentRun::default()
+                };
+            }
+            AgentExit::LaunchFailed(e) => Some(format!("failed to launch copilot: {e}")),
+        };
+
+        // Back off and retry instead of burning an iteration on a failure that isn't the agent's
+        if let Some(reason) = transient {
+            if attempt < throttle.max_retries() {
+                let delay = Throttle::backoff_with_jitter(attempt);
+                say!(
+                    "⏸️  Copilot call failed ({reason}); retrying in {}s ({}/{})",
+                    delay.as_secs(),
+                    attempt + 1,
+                    throttle.max_retries()
+                );
+                throttle.pause(delay);
+                attempt += 1;
+                continue;
+            }
+            say!("❌ Copilot call still failing after {attempt} retries ({reason})");
+            return AgentRun {
+                launch_error: Some(format!("{reason} after {attempt} retries")),
+                elapsed: started.elapsed(),
+                transcript,
+                ..AgentRun::default()
+            };
+        }
+
+        let AgentExit::Exited(success) = exit else {
+            let limit = timeout.unwrap_or_default();
+            if timeouts < project.implement.agent_timeout_retries() {
+                timeouts += 1;
+                say!(
+                    "⏱️  Copilot timed out after {}s; retrying ({}/{})",
+                    limit.as_secs(),
+                    timeouts,
+                    project.implement.agent_timeout_retries()
+                );
+                continue;
+            }
+            say!("⏱️  Copilot timed out after {}s", limit.as_secs());
+            return AgentRun {
+                timed_out: Some(limit),
+                elapsed: started.elapsed(),
+                transcript,
+                session,
+                ..AgentRun::default()
+            };
+        };
+
+        let usage = parse_copilot_usage(&captured).map(|mut usage| {
+            usage.model.get_or_insert_with(|| model.to_string());
+            usage
+        });
+        return AgentRun {
+            success,
+            usage,
+            elapsed: started.elapsed(),
+            transcript,
+            session,
+            ..AgentRun::default()
+        };
     }
 }
 
-fn launch_copilot_implementer(working_dir: &Path, prompt: &str, verbose: bool) -> bool {
+/// Whether copilot's output says the session it was asked to resume doesn't exist
+fn unknown_session(output: &str) -> bool {
+    output.lines().any(|line| {
+        let line = line.to_lowercase();
+        line.contains("session")
+            && [
+                "not found",
+                "no such",
+                "does not exist",
+                "expired",
+                "invalid",
+            ]
+            .iter()
+            .any(|phrase| line.contains(phrase))
+    })
+}
+
+/// Run the copilot implementer once, echoing its output
+///
+/// Returns how it ended and its combined stdout and stderr.
+fn run_copilot_implementer(
+    working_dir: &Path,
+    prompt: &str,
+    seed: u64,
+    verbose: bool,
+    model: &str,
+    timeout: Option<Duration>,
+    session: Option<&str>,
+) -> (AgentExit, String) {
     let mut args = vec![
         "-p",
         prompt,
         "--agent=ralph-implementer",
         "--model",
-        "claude-haiku-4.5",
+        model,
         "--allow-all-tools",
         "--allow-all-paths",
     ];
+    if let Some(session) = session {
+        args.push("--resume");
+        args.push(session);
+    }
 
     // Add debug logging when verbose is enabled
     if verbose {
@@ -420,22 +2451,539 @@ fn launch_copilot_implementer(working_dir: &Path, prompt: &str, verbose: bool) -
         args.push("debug");
     }
 
-    let status = Command::new("copilot")
+    let mut command = Command::new("copilot");
+    command
         .args(&args)
         .current_dir(working_dir)
-        .status();
+        .env("RALPH_SEED", seed.to_string())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    // Own process group, so a timeout also kills the tools the agent spawned
+    #[cfg(unix)]
+    if timeout.is_some() {
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    }
+
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => return (AgentExit::LaunchFailed(e), String::new()),
+    };
+
+    // Echo both streams as they arrive; the usage summary may land on either
+    let stdout = child
+        .stdout
+        .take()
+        .map(|stdout| echo_lines(stdout, |line| say!("{line}")));
+    let stderr = child
+        .stderr
+        .take()
+        .map(|stderr| echo_lines(stderr, |line| eprintln!("{line}")));
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let exit = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break AgentExit::Exited(status.success()),
+            Ok(None) => {}
+            Err(_) => break AgentExit::Exited(false),
+        }
+        let Some(deadline) = deadline else {
+            break AgentExit::Exited(child.wait().is_ok_and(|status| status.success()));
+        };
+        let now = Instant::now();
+        if now >= deadline {
+            kill_process_tree(&mut child);
+            break AgentExit::TimedOut;
+        }
+        std::thread::sleep(TIMEOUT_POLL_INTERVAL.min(deadline - now));
+    };
+
+    // The pipes close once the agent (and anything it spawned) exits
+    let mut captured = String::new();
+    for handle in [stdout, stderr].into_iter().flatten() {
+        captured.push_str(&handle.join().unwrap_or_default());
+    }
+    (exit, captured)
+}
+
+/// Echo a pipe a line at a time, returning everything read
+fn echo_lines(
+    pipe: impl std::io::Read + Send + 'static,
+    echo: fn(&str),
+) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut captured = String::new();
+        for line in BufReader::new(pipe).lines().map_while(std::io::Result::ok) {
+            echo(&line);
+            captured.push_str(&line);
+            captured.push('\n');
+        }
+        captured
+    })
+}
+
+/// Score the run's diff against every acceptance criterion with a judge model
+///
+/// Failures are reported but never fail the run: the judge is a second layer of assurance.
+fn run_judge(prd: &Prd, task_dir: &Path, base_sha: &str, model: &str, api_diff: Option<ApiDiff>) {
+    let diff = Command::new("git")
+        .args(["diff", base_sha])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+        .unwrap_or_default();
+    if diff.trim().is_empty() {
+        say!("⚖️  Skipping judge: no changes since the run started");
+        return;
+    }
+
+    say!("⚖️  Asking judge ({model}) to score acceptance criteria...");
+    let prompt = judge::build_judge_prompt(prd, &diff);
+    let output = Command::new("copilot")
+        .args(["-p", &prompt, "--model", model, "--silent"])
+        .output();
+
+    let scores = match output {
+        Ok(output) if output.status.success() => {
+            judge::parse_judge_output(&String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(output) => Err(RalphError::Copilot(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        )),
+        Err(e) => Err(e.into()),
+    };
+
+    match scores {
+        Ok(scores) => {
+            let report = judge::JudgeReport {
+                run_id: prd.active_run_id.clone(),
+                model: model.to_string(),
+                generated_at: chrono::Utc::now(),
+                scores,
+                api_diff,
+            };
+            for score in &report.scores {
+                say!(
+                    "  {} #{}: {}/{} {}",
+                    score.requirement,
+                    score.criterion,
+                    score.score,
+                    judge::MAX_SCORE,
+                    score.rationale
+                );
+            }
+            if let Some(average) = report.average() {
+                say!("  Average: {average:.1}/{}", judge::MAX_SCORE);
+            }
+            let report_path = task_dir.join("judge-report.json");
+            match report.save(&report_path) {
+                Ok(()) => say!("📄 Judge report: {}", report_path.display()),
+                Err(e) => eprintln!("⚠️  Failed to save judge report: {e}"),
+            }
+        }
+        Err(e) => eprintln!("⚠️  Judge evaluation failed: {e}"),
+    }
+}
+
+/// Extract the workspace's public API from nightly rustdoc JSON (None if unavailable)
+fn capture_api_surface(cwd: &Path, verbose: bool) -> Option<ApiSurface> {
+    if !cwd.join("Cargo.toml").exists() {
+        if verbose {
+            say!("Skipping API diff: no Cargo.toml in {}", cwd.display());
+        }
+        return None;
+    }
 
-    match status {
-        Ok(exit_status) => exit_status.success(),
+    say!("🔎 Extracting public API (rustdoc JSON)...");
+    let target_dir = cwd.join("target/ralph-api");
+    let output = Command::new("cargo")
+        .args([
+            "+nightly",
+            "doc",
+            "--no-deps",
+            "--workspace",
+            "--target-dir",
+        ])
+        .arg(&target_dir)
+        .env("RUSTDOCFLAGS", "-Z unstable-options --output-format json")
+        .current_dir(cwd)
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            match ApiSurface::from_rustdoc_dir(target_dir.join("doc")) {
+                Ok(surface) => Some(surface),
+                Err(e) => {
+                    eprintln!("⚠️  Failed to read rustdoc JSON: {e}");
+                    None
+                }
+            }
+        }
+        Ok(output) => {
+            eprintln!("⚠️  rustdoc JSON generation failed (requires a nightly toolchain)");
+            if verbose {
+                eprintln!("{}", String::from_utf8_lossy(&output.stderr).trim());
+            }
+            None
+        }
         Err(e) => {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                println!("❌ Error: 'copilot' command not found");
-            } else {
-                println!("❌ Error launching copilot: {e}");
+            eprintln!("⚠️  Failed to run cargo: {e}");
+            None
+        }
+    }
+}
+
+/// Compare the public API against the run's starting surface and write artifacts/api-diff.md
+fn write_api_diff(
+    cwd: &Path,
+    task_dir: &Path,
+    before: &ApiSurface,
+    verbose: bool,
+) -> Option<ApiDiff> {
+    let diff = before.diff(&capture_api_surface(cwd, verbose)?);
+    let artifacts = task_dir.join("artifacts");
+    let path = artifacts.join("api-diff.md");
+    match std::fs::create_dir_all(&artifacts)
+        .and_then(|()| std::fs::write(&path, diff.to_markdown()))
+    {
+        Ok(()) => say!(
+            "📐 API diff: {} added, {} removed ({})",
+            diff.added.len(),
+            diff.removed.len(),
+            path.display()
+        ),
+        Err(e) => eprintln!("⚠️  Failed to write API diff: {e}"),
+    }
+    Some(diff)
+}
+
+/// Whether validation will actually run (some profile applies to the PRD in `cwd`)
+fn has_validation_profile(
+    cwd: &Path,
+    prd: &Prd,
+    validation_config: Option<&ValidationConfig>,
+) -> bool {
+    validation_config.is_some_and(|vc| !vc.profiles_for(&prd.validation_profiles, cwd).is_empty())
+}
+
+/// Events for an iteration's validation run and the commits it created, ahead of its final event
+///
+/// `event` builds a fresh in-progress event for the iteration's requirement or chore.
+fn iteration_details(
+    cwd: &Path,
+    base_sha: Option<&str>,
+    validation: Option<(bool, Option<&str>)>,
+    event: impl Fn() -> LedgerEvent,
+) -> Vec<LedgerEvent> {
+    let mut events = Vec::new();
+    if let Some((passed, output)) = validation {
+        let failed_stage = output
+            .and_then(|output| output.lines().next())
+            .and_then(|line| line.strip_prefix("Stage: "))
+            .map(str::to_string);
+        events.push(event().with_payload(EventPayload::ValidationRun {
+            passed,
+            failed_stage,
+        }));
+    }
+    if let Some(base) = base_sha {
+        for (sha, summary) in commits_since(cwd, base) {
+            events.push(event().with_payload(EventPayload::CommitCreated { sha, summary }));
+        }
+    }
+    events
+}
+
+/// Record commits made on the branch since the loop last recorded HEAD (e.g., manual fixes)
+fn record_outside_commits(cwd: &Path, ledger: &mut Ledger, labels: &[String]) -> Result<()> {
+    let Some(last) = ledger
+        .events()
+        .iter()
+        .rev()
+        .find_map(|e| e.commit_sha.clone())
+    else {
+        return Ok(());
+    };
+    let commits = commits_since(cwd, &last);
+    if commits.is_empty() {
+        return Ok(());
+    }
+
+    say!(
+        "👤 {} commit(s) made outside the loop since the last iteration",
+        commits.len()
+    );
+    let event = LedgerEvent::new(
+        ledger.latest_iteration(),
+        RUN_REQUIREMENT,
+        EventStatus::InProgress,
+    )
+    .with_labels(labels)
+    .with_payload(EventPayload::HumanIntervention {
+        description: format!("{} commit(s) made outside the loop", commits.len()),
+    });
+    ledger.append(with_head_commit(event, cwd, Some(&last)))
+}
+
+/// Commits in `base..HEAD`, oldest first, as (SHA, subject) pairs
+fn commits_since(cwd: &Path, base: &str) -> Vec<(String, String)> {
+    Command::new("git")
+        .args([
+            "log",
+            "--reverse",
+            "--format=%H %s",
+            &format!("{base}..HEAD"),
+        ])
+        .current_dir(cwd)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(|line| {
+                    let (sha, summary) = line.split_once(' ').unwrap_or((line, ""));
+                    (sha.to_string(), summary.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Files changed since `base`, committed or not, relative to `cwd` (Ralph's own files excluded)
+fn changed_files(cwd: &Path, paths: &PathConfig, base: &str) -> Option<Vec<String>> {
+    let excludes = snapshot::excludes(paths);
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .args(&excludes)
+            .current_dir(cwd)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let diff = git(&["diff", "--name-only", "--relative", base])?;
+    let untracked = git(&["ls-files", "--others", "--exclude-standard"])?;
+    let mut files: Vec<String> = diff
+        .lines()
+        .chain(untracked.lines())
+        .map(str::to_string)
+        .collect();
+    files.sort();
+    files.dedup();
+    Some(files)
+}
+
+/// Show the changes since `base` and ask whether to accept them, when diff review is on
+///
+/// Diffs within the auto-accept size (added plus removed lines) pass without asking. Without
+/// a terminal to ask on, larger ones are rejected. `label` names the requirement or chore.
+fn review_accepted(config: &ImplementConfig, cwd: &Path, base: &str, label: &str) -> bool {
+    let implement = &config.project.implement;
+    if !implement.review() {
+        return true;
+    }
+    let tasks = format!(":(exclude){}", config.project.paths.tasks().display());
+    let docs = format!(":(exclude){}", config.project.paths.docs().display());
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .args(["--", ".", &tasks, &docs])
+            .current_dir(cwd)
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+            .unwrap_or_default()
+    };
+
+    // Binary files show as "-" in --numstat and count as no lines
+    let untracked = git(&["ls-files", "--others", "--exclude-standard"]);
+    let changed_lines: u64 = git(&["diff", "--numstat", base])
+        .lines()
+        .filter_map(|line| {
+            let mut counts = line.split('\t').map(|count| count.parse::<u64>().ok());
+            Some(counts.next()?? + counts.next()??)
+        })
+        .chain(untracked.lines().map(|path| {
+            std::fs::read_to_string(cwd.join(path))
+                .map_or(0, |content| content.lines().count() as u64)
+        }))
+        .sum();
+    if changed_lines <= implement.review_auto_accept() {
+        say!("👀 {label}: {changed_lines} changed line(s), accepted without review");
+        return true;
+    }
+
+    say!("👀 Review {label}: {changed_lines} changed line(s)");
+    print!("{}", git(&["diff", "--stat", base]));
+    for path in untracked.lines() {
+        say!(" {path} (new file)");
+    }
+    say!();
+    print!("{}", git(&["diff", base]));
+    say!();
+
+    if !std::io::stdin().is_terminal() {
+        say!("⚠️  No terminal to confirm the diff on; rejecting it");
+        return false;
+    }
+    print!("Accept these changes? [y/N] ");
+    let _ = std::io::stdout().flush();
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).is_ok()
+        && matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Commit a passing iteration's changes with `message` when auto-commit is on
+///
+/// Ralph's own task and docs files are left out. The commit goes through the commit-msg hook,
+/// so a rejected message fails the commit rather than bypassing it. Returns a note for the
+/// ledger if the commit failed.
+fn auto_commit(config: &ImplementConfig, cwd: &Path, message: &str) -> Option<String> {
+    if !config.project.implement.auto_commit() {
+        return None;
+    }
+    let git = |args: &[&str]| -> std::result::Result<String, String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .map_err(|e| e.to_string())?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+        }
+    };
+    let tasks = format!(":(exclude){}", config.project.paths.tasks().display());
+    let docs = format!(":(exclude){}", config.project.paths.docs().display());
+
+    let result = git(&["add", "-A", "--", ".", &tasks, &docs])
+        .and_then(|_| git(&["diff", "--cached", "--name-only"]))
+        .and_then(|staged| {
+            if staged.trim().is_empty() {
+                return Ok(None);
             }
-            false
+            git(&["commit", "-m", message]).map(|_| git_head_sha(cwd))
+        });
+    match result {
+        Ok(Some(sha)) => {
+            say!("📦 Committed {} {message}", &sha[..sha.len().min(7)]);
+            None
+        }
+        // The agent committed its work itself
+        Ok(None) => None,
+        Err(e) => {
+            say!("⚠️  Auto-commit failed, leaving changes uncommitted: {e}");
+            Some(format!("auto-commit failed: {e}"))
+        }
+    }
+}
+
+/// Link a finished iteration's event to the code state it produced
+fn with_head_commit(event: LedgerEvent, cwd: &Path, base_sha: Option<&str>) -> LedgerEvent {
+    match git_head_sha(cwd) {
+        Some(head) => event.with_commit(head, base_sha),
+        None => event,
+    }
+}
+
+fn git_head_sha(cwd: &Path) -> Option<String> {
+    Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(cwd)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Record the nondeterministic inputs of an iteration
+///
+/// The seed is exported to the agent (and the commands it runs) as `RALPH_SEED`.
+fn capture_reproducibility(cwd: &Path, prompt: &str, model: &str) -> Reproducibility {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let now = chrono::Utc::now();
+    let seed = (now.timestamp_nanos_opt().unwrap_or_default().unsigned_abs()
+        ^ u64::from(std::process::id()))
+        & (i64::MAX as u64);
+
+    let mut tool_versions =
+        BTreeMap::from([("ralph".to_string(), env!("CARGO_PKG_VERSION").to_string())]);
+    for tool in ["copilot", "git"] {
+        let version = Command::new(tool)
+            .arg("--version")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .next()
+                    .map(|line| line.trim().to_string())
+            });
+        if let Some(version) = version {
+            tool_versions.insert(tool.to_string(), version);
         }
     }
+
+    Reproducibility {
+        seed,
+        model: model.to_string(),
+        // copilot doesn't expose sampling temperature
+        temperature: None,
+        prompt_hash: Reproducibility::sha256(prompt),
+        tree_hash: git(&["rev-parse", "HEAD^{tree}"]),
+        dirty_hash: git(&["diff", "HEAD"])
+            .filter(|diff| !diff.is_empty())
+            .map(|diff| Reproducibility::sha256(&diff)),
+        tool_versions,
+    }
+}
+
+/// Snapshot of HEAD plus uncommitted and untracked changes outside Ralph's own files
+///
+/// Returns None if git is unavailable, in which case no-op detection is skipped.
+fn worktree_fingerprint(cwd: &Path, paths: &PathConfig) -> Option<String> {
+    let excludes = snapshot::excludes(paths);
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .args(&excludes)
+            .current_dir(cwd)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let head = git(&["log", "-1", "--format=%H"])?;
+    let diff = git(&["diff", "HEAD"])?;
+    let untracked = git(&["ls-files", "--others", "--exclude-standard"])?;
+    Some(format!("{head}\0{diff}\0{untracked}"))
+}
+
+/// Identity of the code under validation: the worktree fingerprint plus untracked files' contents
+///
+/// Returns None if git is unavailable, in which case validation is never cached.
+fn tree_state(cwd: &Path, paths: &PathConfig) -> Option<String> {
+    let mut state = worktree_fingerprint(cwd, paths)?;
+    let untracked = state.rsplit('\0').next().unwrap_or_default().to_string();
+    for path in untracked.lines() {
+        state.push('\0');
+        state.push_str(&String::from_utf8_lossy(
+            &std::fs::read(cwd.join(path)).unwrap_or_default(),
+        ));
+    }
+    Some(Reproducibility::sha256(&state))
 }
 
 fn has_uncommitted_changes() -> bool {
@@ -446,6 +2994,17 @@ fn has_uncommitted_changes() -> bool {
         .unwrap_or(false)
 }
 
+/// Name of the checked-out branch (`HEAD` when detached)
+fn current_branch() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default()
+}
+
 fn ensure_branch(branch_name: &str, dry_run: bool, verbose: bool) -> Result<()> {
     // Check if branch exists
     let branch_exists = Command::new("git")
@@ -454,48 +3013,101 @@ fn ensure_branch(branch_name: &str, dry_run: bool, verbose: bool) -> Result<()>
         .map(|output| output.status.success())
         .unwrap_or(false);
 
-    // Get current branch
-    let current_branch = Command::new("git")
-        .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .output()
-        .ok()
-        .and_then(|output| String::from_utf8(output.stdout).ok())
-        .map(|s| s.trim().to_string())
-        .unwrap_or_default();
-
-    if current_branch == branch_name {
+    if current_branch() == branch_name {
         if verbose {
-            println!("Already on branch: {branch_name}");
+            say!("Already on branch: {branch_name}");
         }
         return Ok(());
     }
 
     if dry_run {
         if branch_exists {
-            println!("[dry-run] Would checkout branch: {branch_name}");
+            say!("[dry-run] Would checkout branch: {branch_name}");
         } else {
-            println!("[dry-run] Would create and checkout branch: {branch_name}");
+            say!("[dry-run] Would create and checkout branch: {branch_name}");
         }
         return Ok(());
     }
 
     if branch_exists {
-        println!("📌 Checking out branch: {branch_name}");
+        say!("📌 Checking out branch: {branch_name}");
         let status = Command::new("git")
             .args(["checkout", branch_name])
             .status()?;
         if !status.success() {
-            println!("⚠️  Failed to checkout branch, continuing on current branch");
+            say!("⚠️  Failed to checkout branch, continuing on current branch");
         }
     } else {
-        println!("🌿 Creating branch: {branch_name}");
+        say!("🌿 Creating branch: {branch_name}");
         let status = Command::new("git")
             .args(["checkout", "-b", branch_name])
             .status()?;
         if !status.success() {
-            println!("⚠️  Failed to create branch, continuing on current branch");
+            say!("⚠️  Failed to create branch, continuing on current branch");
         }
     }
 
     Ok(())
 }
+
+/// Record commits unique to the feature branch that bypassed the commit-msg hook
+/// or lack a REQ reference. Already-recorded commits are skipped.
+///
+/// Returns the number of newly flagged findings.
+fn audit_branch_commits(branch_name: &str, ledger: &mut Ledger) -> Result<usize> {
+    let exclude = format!("--exclude={branch_name}");
+    let Ok(output) = Command::new("git")
+        .args([
+            "log",
+            "--format=%H%x1f%B%x1e",
+            "HEAD",
+            "--not",
+            &exclude,
+            "--branches",
+        ])
+        .output()
+    else {
+        return Ok(0);
+    };
+    if !output.status.success() {
+        return Ok(0);
+    }
+
+    let req_pattern = regex_lite::Regex::new(r"REQ-\d+").expect("valid regex");
+    let log = String::from_utf8_lossy(&output.stdout);
+    let iteration = ledger.latest_iteration();
+    let mut flagged = 0;
+
+    for record in log.split('\x1e') {
+        let Some((sha, body)) = record.trim().split_once('\x1f') else {
+            continue;
+        };
+        let short = &sha[..sha.len().min(12)];
+        let already_recorded = ledger
+            .audit_events()
+            .iter()
+            .any(|e| e.message.as_deref().is_some_and(|m| m.contains(short)));
+        if already_recorded {
+            continue;
+        }
+
+        let mut findings = Vec::new();
+        if !body.contains(VERIFIED_TRAILER) {
+            findings.push(format!(
+                "Commit {short} made without the commit-msg hook (--no-verify?)"
+            ));
+        }
+        if !req_pattern.is_match(body) {
+            findings.push(format!("Commit {short} has no REQ reference"));
+        }
+        for finding in findings {
+            ledger.append(
+                LedgerEvent::new(iteration, AUDIT_REQUIREMENT, EventStatus::Failed)
+                    .with_message(finding),
+            )?;
+            flagged += 1;
+        }
+    }
+
+    Ok(flagged)
+}
@@ -0,0 +1,464 @@
+// ABOUTME: Parallel mode for 'ralph implement' ('--parallel N' or [implement] parallel)
+// ABOUTME: Runs requirements with disjoint paths concurrently in git worktrees, merging one at a time
+
+use super::{
+    attach_validation_output, capture_reproducibility, dry_run_scratchpad, emit,
+    escalate_if_exhausted, generate_prompt, git_head_sha, has_validation_profile,
+    iteration_details, launch_copilot_implementer, prepare_scratchpad, previous_session,
+    print_dry_run_prompt, review_accepted, run_validation, save_transcript, snapshot,
+    with_head_commit, AgentRun, ImplementConfig, PromptScope, ValidationScope,
+    REVIEW_REJECTED_MESSAGE,
+};
+use ralph_lib::ledger::NO_OP_MESSAGE;
+use ralph_lib::progress::ProgressEvent;
+use ralph_lib::{
+    EventPayload, EventStatus, Ledger, LedgerEvent, Prd, RalphError, Requirement,
+    RequirementStatus, Result, ValidationConfig,
+};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+/// A requirement being implemented in its own worktree
+struct Lane {
+    req: Requirement,
+    iteration: u32,
+    run_full_tests: bool,
+    prompt: String,
+    seed: u64,
+    /// Agent session of the requirement's previous attempt, to resume
+    session: Option<String>,
+    branch: String,
+    worktree: PathBuf,
+}
+
+/// How a lane's work fared when brought back into the main tree
+enum Outcome {
+    AgentFailed,
+    AgentTimedOut(Duration),
+    NoChanges,
+    MergeFailed(String),
+    Rejected,
+    Validated {
+        passed: bool,
+        output: Option<String>,
+    },
+}
+
+/// Run one round of concurrent iterations over requirements with disjoint paths
+///
+/// Returns the number of iterations used, or 0 if fewer than two requirements could be
+/// batched (the caller then falls back to a sequential iteration).
+pub(super) fn run_round(
+    config: &ImplementConfig,
+    cwd: &Path,
+    prd_path: &Path,
+    prd: &mut Prd,
+    ledger: &mut Ledger,
+    validation_config: Option<&ValidationConfig>,
+) -> Result<u32> {
+    let batch: Vec<Requirement> = prd
+        .parallel_batch(config.project.implement.parallel())
+        .into_iter()
+        .cloned()
+        .collect();
+    if batch.len() < 2 {
+        return Ok(0);
+    }
+
+    let ids: Vec<&str> = batch.iter().map(|r| r.id.as_str()).collect();
+    if config.dry_run {
+        say!("[dry-run] Would implement in parallel: {}", ids.join(", "));
+        let iteration = ledger.latest_iteration() + 1;
+        for req in &batch {
+            let prompt = generate_prompt(
+                config,
+                prd,
+                ledger,
+                &PromptScope {
+                    req,
+                    iteration,
+                    run_full_tests: req.risk.unwrap_or_default().runs_full_tests(iteration),
+                    scratchpad: &dry_run_scratchpad(prd_path),
+                },
+                validation_config,
+            )?;
+            print_dry_run_prompt(&req.id, &prompt);
+        }
+        return Ok(batch.len() as u32);
+    }
+    say!("🔀 Implementing in parallel: {}", ids.join(", "));
+
+    let worktree_root = worktree_root(cwd, &config.slug);
+    let mut lanes = Vec::new();
+    for req in batch {
+        let branch = format!("ralph-parallel/{}/{}", config.slug, req.id);
+        let worktree = worktree_root.join(&req.id);
+        if let Err(e) = add_worktree(cwd, &branch, &worktree) {
+            say!("⚠️  Skipping {}: {e}", req.id);
+            continue;
+        }
+
+        let iteration = ledger.latest_iteration() + 1;
+        let run_full_tests = req.risk.unwrap_or_default().runs_full_tests(iteration);
+        let scratchpad =
+            prepare_scratchpad(prd_path, config.verbose, config.project.models.summarizer())?;
+        let prompt = generate_prompt(
+            config,
+            prd,
+            ledger,
+            &PromptScope {
+                req: &req,
+                iteration,
+                run_full_tests,
+                scratchpad: &scratchpad,
+            },
+            validation_config,
+        )?;
+        let reproducibility =
+            capture_reproducibility(&worktree, &prompt, config.project.models.implementer());
+        let seed = reproducibility.seed;
+
+        prd.update_requirement_status(&req.id, RequirementStatus::InProgress);
+        ledger.append(
+            LedgerEvent::new(iteration, &req.id, EventStatus::Started)
+                .with_message(format!("parallel worktree: {branch}"))
+                .with_labels(&config.labels)
+                .with_reproducibility(reproducibility)
+                .with_payload(EventPayload::IterationStarted {
+                    full_tests: Some(run_full_tests),
+                }),
+        )?;
+        emit(
+            config,
+            ProgressEvent::IterationStarted {
+                iteration,
+                requirement: req.id.clone(),
+                title: req.title.clone(),
+                full_tests: run_full_tests,
+            },
+        );
+        let session = previous_session(config, ledger, &req.id);
+        lanes.push(Lane {
+            req,
+            iteration,
+            run_full_tests,
+            prompt,
+            seed,
+            session,
+            branch,
+            worktree,
+        });
+    }
+    prd.save(prd_path)?;
+
+    say!(
+        "📝 Launching {} Copilot implementers in parallel...",
+        lanes.len()
+    );
+    let verbose = config.verbose;
+    let throttle = &config.throttle;
+    let project = &config.project;
+    let results: Vec<AgentRun> = std::thread::scope(|scope| {
+        let handles: Vec<_> = lanes
+            .iter()
+            .map(|lane| {
+                scope.spawn(move || {
+                    launch_copilot_implementer(
+                        &lane.worktree,
+                        &lane.prompt,
+                        lane.seed,
+                        verbose,
+                        throttle,
+                        project,
+                        lane.session.as_deref(),
+                    )
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_default())
+            .collect()
+    });
+
+    // Merge sequentially so every merge is validated against everything merged before it
+    let mut launch_error = None;
+    for (lane, agent) in lanes.iter().zip(results) {
+        emit(
+            config,
+            ProgressEvent::AgentFinished {
+                iteration: lane.iteration,
+                requirement: lane.req.id.clone(),
+                success: agent.success,
+                duration_secs: agent.elapsed.as_secs_f64(),
+            },
+        );
+
+        // Nothing ran in this lane; leave the requirement in progress and stop once the
+        // other lanes are merged
+        if let Some(reason) = agent.launch_error {
+            ledger.append(
+                LedgerEvent::new(lane.iteration, &lane.req.id, EventStatus::InProgress)
+                    .with_message(format!("agent not run: {reason}"))
+                    .with_labels(&config.labels),
+            )?;
+            remove_worktree(cwd, &lane.branch, &lane.worktree);
+            launch_error = Some(reason);
+            continue;
+        }
+
+        say!("🔀 Merging {}: {}", lane.req.id, lane.req.title);
+        let base_sha = git_head_sha(cwd);
+        let outcome = match agent.timed_out {
+            Some(limit) => Outcome::AgentTimedOut(limit),
+            None if agent.success => integrate(config, cwd, prd, prd_path, validation_config, lane),
+            None => Outcome::AgentFailed,
+        };
+
+        let mut events = match &outcome {
+            Outcome::Validated { passed, output } => iteration_details(
+                cwd,
+                base_sha.as_deref(),
+                has_validation_profile(cwd, prd, validation_config)
+                    .then_some((*passed, output.as_deref())),
+                || {
+                    LedgerEvent::new(lane.iteration, &lane.req.id, EventStatus::InProgress)
+                        .with_labels(&config.labels)
+                },
+            ),
+            Outcome::AgentTimedOut(limit) => {
+                vec![
+                    LedgerEvent::new(lane.iteration, &lane.req.id, EventStatus::InProgress)
+                        .with_labels(&config.labels)
+                        .with_payload(EventPayload::AgentTimedOut {
+                            seconds: limit.as_secs(),
+                        }),
+                ]
+            }
+            _ => Vec::new(),
+        };
+
+        let mut event = match outcome {
+            Outcome::Validated { passed, output } => {
+                let status = if passed {
+                    EventStatus::Done
+                } else {
+                    EventStatus::Failed
+                };
+                let mut event =
+                    LedgerEvent::new(lane.iteration, &lane.req.id, status).with_validation(passed);
+                if let Some(output) = output {
+                    event = attach_validation_output(
+                        ledger,
+                        event,
+                        &output,
+                        config,
+                        validation_config,
+                    )?;
+                }
+                event
+            }
+            Outcome::AgentFailed => {
+                LedgerEvent::new(lane.iteration, &lane.req.id, EventStatus::Failed)
+                    .with_message("agent failed in parallel worktree")
+            }
+            Outcome::AgentTimedOut(limit) => {
+                LedgerEvent::new(lane.iteration, &lane.req.id, EventStatus::Failed).with_message(
+                    format!(
+                        "agent timed out after {}s in parallel worktree",
+                        limit.as_secs()
+                    ),
+                )
+            }
+            Outcome::NoChanges => {
+                LedgerEvent::new(lane.iteration, &lane.req.id, EventStatus::Failed)
+                    .with_message(NO_OP_MESSAGE)
+            }
+            Outcome::MergeFailed(reason) => {
+                LedgerEvent::new(lane.iteration, &lane.req.id, EventStatus::Failed)
+                    .with_message(format!("merge failed: {reason}"))
+            }
+            Outcome::Rejected => {
+                LedgerEvent::new(lane.iteration, &lane.req.id, EventStatus::Failed)
+                    .with_validation(true)
+                    .with_message(REVIEW_REJECTED_MESSAGE)
+            }
+        }
+        .with_labels(&config.labels);
+        event = with_head_commit(event, cwd, base_sha.as_deref());
+        if let Some(usage) = &agent.usage {
+            event = event.with_usage(usage);
+        }
+        if let Some(transcript) =
+            save_transcript(ledger, lane.iteration, &agent.transcript, validation_config)
+        {
+            event = event.with_transcript(transcript);
+        }
+        if let Some(session) = &agent.session {
+            event = event.with_agent_session(session);
+        }
+
+        let done = event.status == EventStatus::Done;
+        event = event.with_payload(EventPayload::IterationFinished { success: done });
+        prd.update_requirement_status(
+            &lane.req.id,
+            if done {
+                RequirementStatus::Done
+            } else {
+                RequirementStatus::InProgress
+            },
+        );
+        prd.save(prd_path)?;
+        events.push(event);
+        ledger.append_batch(&events)?;
+
+        if done {
+            say!("✅ {} merged (iteration {})", lane.req.id, lane.iteration);
+            emit(
+                config,
+                ProgressEvent::RequirementDone {
+                    iteration: lane.iteration,
+                    requirement: lane.req.id.clone(),
+                },
+            );
+        } else {
+            say!(
+                "❌ {} not merged (iteration {})",
+                lane.req.id,
+                lane.iteration
+            );
+        }
+
+        remove_worktree(cwd, &lane.branch, &lane.worktree);
+        if !done {
+            escalate_if_exhausted(config, cwd, prd_path, prd, ledger, &lane.req.id)?;
+        }
+    }
+
+    match launch_error {
+        Some(reason) => Err(RalphError::Copilot(reason)),
+        None => Ok(lanes.len() as u32),
+    }
+}
+
+/// Commit a lane's work, merge it into the main tree, and validate the merge
+///
+/// The merge is only committed if validation passes and the diff review (if on) accepts it;
+/// otherwise it is aborted.
+fn integrate(
+    config: &ImplementConfig,
+    cwd: &Path,
+    prd: &Prd,
+    prd_path: &Path,
+    validation_config: Option<&ValidationConfig>,
+    lane: &Lane,
+) -> Outcome {
+    let message = format!("{}: {}", lane.req.id, lane.req.title);
+
+    // Stage everything except Ralph's own files, which the main tree owns
+    let staged = git(
+        &lane.worktree,
+        &["add", "-A"],
+        &snapshot::excludes(&config.project.paths),
+    )
+    .and_then(|_| git(&lane.worktree, &["diff", "--cached", "--name-only"], &[]));
+    match staged {
+        Ok(files) if !files.trim().is_empty() => {
+            if let Err(e) = git(&lane.worktree, &["commit", "-m", &message], &[]) {
+                return Outcome::MergeFailed(e.to_string());
+            }
+        }
+        Ok(_) => {}
+        Err(e) => return Outcome::MergeFailed(e.to_string()),
+    }
+
+    // The agent may also have committed on its own
+    let ahead = git(
+        cwd,
+        &["rev-list", "--count", &format!("HEAD..{}", lane.branch)],
+        &[],
+    );
+    match ahead.as_deref().map(str::trim) {
+        Ok("0") => return Outcome::NoChanges,
+        Ok(_) => {}
+        Err(e) => return Outcome::MergeFailed(e.to_string()),
+    }
+
+    if let Err(e) = git(cwd, &["merge", "--no-ff", "--no-commit", &lane.branch], &[]) {
+        let _ = git(cwd, &["merge", "--abort"], &[]);
+        return Outcome::MergeFailed(e.to_string());
+    }
+
+    // The merge is not committed yet, so HEAD is still the pre-merge base
+    let (passed, output) = run_validation(
+        config,
+        cwd,
+        prd,
+        prd_path,
+        validation_config,
+        &ValidationScope {
+            requirement: &lane.req.id,
+            iteration: lane.iteration,
+            base_sha: Some("HEAD"),
+            run_full_tests: lane.run_full_tests,
+        },
+    );
+    if !passed {
+        let _ = git(cwd, &["merge", "--abort"], &[]);
+        return Outcome::Validated { passed, output };
+    }
+    if !review_accepted(config, cwd, "HEAD", &lane.req.id) {
+        let _ = git(cwd, &["merge", "--abort"], &[]);
+        return Outcome::Rejected;
+    }
+
+    if let Err(e) = git(cwd, &["commit", "-m", &message], &[]) {
+        let _ = git(cwd, &["merge", "--abort"], &[]);
+        return Outcome::MergeFailed(e.to_string());
+    }
+    Outcome::Validated { passed, output }
+}
+
+/// Directory holding the feature's lane worktrees, inside the repository's git directory
+///
+/// Keeping them under `.git` avoids collisions between repositories with the same feature
+/// slug and keeps them out of the main tree's status.
+fn worktree_root(cwd: &Path, slug: &str) -> PathBuf {
+    let git_dir = git(cwd, &["rev-parse", "--git-common-dir"], &[])
+        .map(|dir| cwd.join(dir.trim()))
+        .unwrap_or_else(|_| std::env::temp_dir());
+    git_dir.join("ralph-worktrees").join(slug)
+}
+
+/// Create (or recreate) a worktree on a fresh branch at the current HEAD
+fn add_worktree(cwd: &Path, branch: &str, worktree: &Path) -> Result<()> {
+    remove_worktree(cwd, branch, worktree);
+    let path = worktree.to_string_lossy();
+    git(cwd, &["worktree", "add", "-B", branch, &path, "HEAD"], &[]).map(|_| ())
+}
+
+/// Remove a lane's worktree and branch, ignoring anything already gone
+fn remove_worktree(cwd: &Path, branch: &str, worktree: &Path) {
+    let path = worktree.to_string_lossy();
+    let _ = git(cwd, &["worktree", "remove", "--force", &path], &[]);
+    let _ = git(cwd, &["worktree", "prune"], &[]);
+    let _ = git(cwd, &["branch", "-D", branch], &[]);
+}
+
+fn git(dir: &Path, args: &[&str], pathspec: &[String]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .args(pathspec)
+        .current_dir(dir)
+        .output()?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(RalphError::Command(format!(
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
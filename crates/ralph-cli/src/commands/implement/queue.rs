@@ -0,0 +1,242 @@
+// ABOUTME: Queue mode for 'ralph implement' ('--all' or '--queue FILE')
+// ABOUTME: Runs the loop on each feature in turn, with the run limits applying per feature, then sums up
+
+use super::{run, ImplementConfig, RunOutcome, HUMAN_TO_STDERR};
+use ralph_lib::budget::format_duration;
+use ralph_lib::progress::ProgressFormat;
+use ralph_lib::{prd_path, EventPayload, Ledger, Prd, RalphError, RequirementStatus, Result};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+/// Which features a queued run works through
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Queue {
+    /// Every feature with remaining work, by slug
+    All,
+    /// The slugs listed in a file, one per line (`#` starts a comment)
+    File(PathBuf),
+}
+
+/// How one feature's run went
+struct FeatureOutcome {
+    slug: String,
+    /// Requirements done before and after the run, and in total
+    done_before: usize,
+    done_after: usize,
+    total: usize,
+    iterations: u32,
+    cost: f64,
+    elapsed: Duration,
+    /// Why the run stopped early
+    stopped: Option<String>,
+    /// What the run failed with
+    error: Option<String>,
+    /// How the run left the feature
+    outcome: RunOutcome,
+}
+
+/// Run the implementation loop on every feature in `queue`, one after another, returning
+/// the worst of their outcomes
+///
+/// A feature that fails is reported and the queue moves on to the next one.
+///
+/// # Errors
+///
+/// Returns an error if the queue file cannot be read or names a feature without a PRD, or
+/// if any feature's run failed.
+pub fn run_queue(mut config: ImplementConfig, queue: &Queue) -> Result<RunOutcome> {
+    HUMAN_TO_STDERR.store(
+        config.progress_format == ProgressFormat::Json,
+        Ordering::Relaxed,
+    );
+    let tasks_dir = std::env::current_dir()?.join(config.project.paths.tasks());
+    let slugs = match queue {
+        Queue::All => features_with_work(&tasks_dir)?,
+        Queue::File(path) => read_queue(path, &tasks_dir)?,
+    };
+    if slugs.is_empty() {
+        say!("✅ No features with remaining work");
+        return Ok(RunOutcome::Complete);
+    }
+
+    say!("📚 Queue: {}", slugs.join(", "));
+    let started = Instant::now();
+    let mut outcomes = Vec::new();
+    for (index, slug) in slugs.iter().enumerate() {
+        say!();
+        say!("━━━ [{}/{}] {slug} ━━━", index + 1, slugs.len());
+        config.slug.clone_from(slug);
+        outcomes.push(run_feature(&config, &tasks_dir, slug)?);
+    }
+
+    print_summary(&outcomes, started.elapsed());
+    let failed = outcomes.iter().filter(|o| o.error.is_some()).count();
+    if failed > 0 {
+        return Err(RalphError::Command(format!(
+            "{failed} of {} queued feature(s) failed",
+            outcomes.len()
+        )));
+    }
+    Ok(outcomes
+        .iter()
+        .map(|o| o.outcome)
+        .max()
+        .unwrap_or(RunOutcome::Complete))
+}
+
+/// Run one feature, measuring progress from its PRD and ledger before and after
+fn run_feature(config: &ImplementConfig, tasks_dir: &Path, slug: &str) -> Result<FeatureOutcome> {
+    let task_dir = tasks_dir.join(slug);
+    let ledger_path = task_dir.join("ledger.jsonl");
+    let (done_before, _) = progress(&Prd::from_file(prd_path(&task_dir))?);
+    let before = if ledger_path.exists() {
+        Ledger::from_file(&ledger_path)?.events().len()
+    } else {
+        0
+    };
+
+    let started = Instant::now();
+    let result = run(config);
+    let elapsed = started.elapsed();
+
+    let prd = Prd::from_file(prd_path(&task_dir))?;
+    let (done_after, total) = progress(&prd);
+    let ledger = if ledger_path.exists() {
+        Ledger::from_file(&ledger_path)?
+    } else {
+        Ledger::new()
+    };
+    let new_events = ledger.events().get(before..).unwrap_or_default();
+    let mut iterations: Vec<u32> = new_events.iter().map(|e| e.iteration).collect();
+    iterations.dedup();
+    let stopped = new_events.last().and_then(|e| match &e.payload {
+        Some(EventPayload::RunAborted { reason }) => Some(reason.clone()),
+        _ => None,
+    });
+    Ok(FeatureOutcome {
+        slug: slug.to_string(),
+        done_before,
+        done_after,
+        total,
+        iterations: u32::try_from(iterations.len()).unwrap_or(u32::MAX),
+        cost: new_events.iter().filter_map(|e| e.estimated_cost).sum(),
+        elapsed,
+        stopped,
+        error: result.err().map(|e| e.to_string()),
+        outcome: RunOutcome::of(&prd),
+    })
+}
+
+/// Requirements done, and in total
+fn progress(prd: &Prd) -> (usize, usize) {
+    let done = prd
+        .requirements
+        .iter()
+        .filter(|r| r.status == RequirementStatus::Done)
+        .count();
+    (done, prd.requirements.len())
+}
+
+/// Slugs of the features under `tasks_dir` with requirements left to do, sorted
+fn features_with_work(tasks_dir: &Path) -> Result<Vec<String>> {
+    let mut slugs = Vec::new();
+    if !tasks_dir.exists() {
+        return Ok(slugs);
+    }
+    for entry in std::fs::read_dir(tasks_dir)?.flatten() {
+        let prd_path = prd_path(entry.path());
+        if !prd_path.exists() {
+            continue;
+        }
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        match Prd::from_file(&prd_path) {
+            Ok(prd) if prd.has_remaining_work() => slugs.push(name),
+            Ok(_) => {}
+            Err(e) => say!("⚠️  Skipping {name}: {e}"),
+        }
+    }
+    slugs.sort();
+    Ok(slugs)
+}
+
+/// Slugs listed in a queue file, checked against the features under `tasks_dir`
+fn read_queue(path: &Path, tasks_dir: &Path) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| RalphError::Config(format!("Failed to read queue {}: {e}", path.display())))?;
+    let slugs = parse_queue(&content);
+    if let Some(missing) = slugs
+        .iter()
+        .find(|slug| !prd_path(tasks_dir.join(slug)).exists())
+    {
+        return Err(RalphError::Config(format!(
+            "{}: no PRD for '{missing}' in {}",
+            path.display(),
+            tasks_dir.display()
+        )));
+    }
+    Ok(slugs)
+}
+
+/// Non-empty lines of a queue file, without comments or repeats
+fn parse_queue(content: &str) -> Vec<String> {
+    let mut slugs: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let slug = line.split('#').next().unwrap_or_default().trim();
+        if !slug.is_empty() && !slugs.iter().any(|s| s == slug) {
+            slugs.push(slug.to_string());
+        }
+    }
+    slugs
+}
+
+fn print_summary(outcomes: &[FeatureOutcome], elapsed: Duration) {
+    let width = outcomes.iter().map(|o| o.slug.len()).max().unwrap_or(0);
+    let done: usize = outcomes.iter().map(|o| o.done_after).sum();
+    let total: usize = outcomes.iter().map(|o| o.total).sum();
+    let cost: f64 = outcomes.iter().map(|o| o.cost).sum();
+
+    say!();
+    say!(
+        "📊 Queue summary: {} feature(s), {done}/{total} requirements done, ${cost:.2}, {}",
+        outcomes.len(),
+        format_duration(elapsed)
+    );
+    for outcome in outcomes {
+        let icon = if outcome.error.is_some() {
+            "❌"
+        } else if outcome.done_after == outcome.total {
+            "✅"
+        } else {
+            "⏸️ "
+        };
+        let stopped = match (&outcome.error, &outcome.stopped) {
+            (Some(error), _) => format!(" — error: {error}"),
+            (None, Some(reason)) => format!(" — {reason}"),
+            (None, None) => String::new(),
+        };
+        say!(
+            "  {icon} {:width$}  {}/{} done (+{}), {} iteration(s), ${:.2}, {}{stopped}",
+            outcome.slug,
+            outcome.done_after,
+            outcome.total,
+            outcome.done_after.saturating_sub(outcome.done_before),
+            outcome.iterations,
+            outcome.cost,
+            format_duration(outcome.elapsed),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_queue() {
+        let content = "# overnight\nauth\n\nbilling  # after auth\nauth\n  search\n";
+        assert_eq!(parse_queue(content), vec!["auth", "billing", "search"]);
+    }
+}
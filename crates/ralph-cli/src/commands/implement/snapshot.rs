@@ -0,0 +1,201 @@
+// ABOUTME: Snapshot and rollback of the working tree around an iteration ('--rollback' or [implement] rollback)
+// ABOUTME: Restores the pre-agent state after a failed iteration so retries start clean
+
+use super::{git_head_sha, ImplementConfig};
+use ralph_lib::checkpoint::Snapshot;
+use ralph_lib::config::PathConfig;
+use ralph_lib::{Ledger, LedgerEvent, RalphError, Result};
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Command;
+
+/// Message prefix of the ledger event recorded when an iteration is rolled back
+const ROLLED_BACK_MESSAGE: &str = "rolled back";
+
+/// Capture the tree state before the agent runs, if rollback is on
+///
+/// Uncommitted changes to tracked files are kept in a `git stash create` commit without
+/// touching the working tree.
+pub(super) fn take(config: &ImplementConfig, cwd: &Path) -> Option<Snapshot> {
+    if !config.project.implement.rollback() {
+        return None;
+    }
+    capture(&config.project.paths, cwd)
+}
+
+/// Capture the tree state now, leaving the working tree as it is
+fn capture(paths: &PathConfig, cwd: &Path) -> Option<Snapshot> {
+    let head = git_head_sha(cwd)?;
+    let stash = git(cwd, &["stash", "create"], &[])
+        .ok()
+        .map(|sha| sha.trim().to_string())
+        .filter(|sha| !sha.is_empty());
+    let untracked = untracked_files(paths, cwd).ok()?;
+    Some(Snapshot {
+        head,
+        stash,
+        untracked,
+    })
+}
+
+/// Roll a failed iteration back to `snapshot`, saving the discarded changes as a patch
+///
+/// Ralph's own task and docs files are left as they are. `event`, an in-progress event for the
+/// iteration's requirement or chore, is recorded with where the patch went.
+pub(super) fn roll_back(
+    config: &ImplementConfig,
+    cwd: &Path,
+    prd_path: &Path,
+    ledger: &mut Ledger,
+    snapshot: Option<&Snapshot>,
+    event: LedgerEvent,
+) -> Result<()> {
+    let Some(snapshot) = snapshot else {
+        return Ok(());
+    };
+
+    let artifacts = prd_path.with_file_name("artifacts");
+    std::fs::create_dir_all(&artifacts)?;
+    let patch_path = artifacts.join(format!(
+        "rollback-{}-{}.patch",
+        event.requirement, event.iteration
+    ));
+    std::fs::write(
+        &patch_path,
+        git(
+            cwd,
+            &["diff", snapshot.source()],
+            &excludes(&config.project.paths),
+        )?,
+    )?;
+
+    if let Err(e) = restore(&config.project.paths, cwd, snapshot) {
+        say!("⚠️  Rollback failed, leaving the tree as the agent left it: {e}");
+        return Ok(());
+    }
+    let short = &snapshot.head[..snapshot.head.len().min(7)];
+    say!(
+        "⏪ Rolled back to {short}; discarded changes saved to {}",
+        patch_path.display()
+    );
+    ledger.append(event.with_message(format!(
+        "{ROLLED_BACK_MESSAGE} to {short}; discarded changes in {}",
+        patch_path.display()
+    )))
+}
+
+/// Put the working tree back the way it was when `snapshot` was taken
+fn restore(paths: &PathConfig, cwd: &Path, snapshot: &Snapshot) -> Result<()> {
+    // Drop commits made since the snapshot, keeping the tree for the steps below
+    if git_head_sha(cwd).as_deref() != Some(snapshot.head.as_str()) {
+        git(cwd, &["reset", "--mixed", "--quiet", &snapshot.head], &[])?;
+    }
+    git(
+        cwd,
+        &["restore", "--worktree", "--source", snapshot.source()],
+        &excludes(paths),
+    )?;
+
+    let kept: HashSet<&String> = snapshot.untracked.iter().collect();
+    for path in untracked_files(paths, cwd)? {
+        if !kept.contains(&path) {
+            std::fs::remove_file(cwd.join(&path))?;
+        }
+    }
+    Ok(())
+}
+
+/// Untracked, non-ignored files outside Ralph's own directories
+fn untracked_files(paths: &PathConfig, cwd: &Path) -> Result<Vec<String>> {
+    let output = git(
+        cwd,
+        &["ls-files", "--others", "--exclude-standard"],
+        &excludes(paths),
+    )?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Pathspec covering the repository except Ralph's task and docs directories
+pub(super) fn excludes(paths: &PathConfig) -> [String; 4] {
+    [
+        "--".to_string(),
+        ".".to_string(),
+        format!(":(exclude){}", paths.tasks().display()),
+        format!(":(exclude){}", paths.docs().display()),
+    ]
+}
+
+fn git(dir: &Path, args: &[&str], pathspec: &[String]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .args(pathspec)
+        .current_dir(dir)
+        .output()?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(RalphError::Command(format!(
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn run_git(dir: &Path, args: &[&str]) -> String {
+        git(dir, args, &[]).unwrap()
+    }
+
+    fn read(dir: &Path, path: &str) -> String {
+        std::fs::read_to_string(dir.join(path)).unwrap()
+    }
+
+    #[test]
+    fn test_restore() {
+        let temp = tempdir().unwrap();
+        let dir = temp.path();
+        run_git(dir, &["init", "--quiet"]);
+        run_git(dir, &["config", "user.name", "Ralph Test"]);
+        run_git(dir, &["config", "user.email", "ralph@example.com"]);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::create_dir_all(dir.join("ralph/tasks/auth")).unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "v1\n").unwrap();
+        std::fs::write(dir.join("src/main.rs"), "main v1\n").unwrap();
+        std::fs::write(dir.join("ralph/tasks/auth/ledger.jsonl"), "").unwrap();
+        run_git(dir, &["add", "-A"]);
+        run_git(dir, &["commit", "--quiet", "-m", "initial"]);
+
+        // State before the agent: an uncommitted edit and an untracked file of the user's
+        std::fs::write(dir.join("src/lib.rs"), "v1 + user edit\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "mine\n").unwrap();
+        let paths = PathConfig::default();
+        let snapshot = capture(&paths, dir).unwrap();
+        assert!(snapshot.stash.is_some());
+        assert_eq!(snapshot.untracked, vec!["notes.txt"]);
+
+        // The agent commits, edits a tracked file, adds a file, and Ralph updates its own files
+        std::fs::write(dir.join("src/main.rs"), "main v2\n").unwrap();
+        run_git(
+            dir,
+            &["commit", "--quiet", "-m", "agent commit", "src/main.rs"],
+        );
+        std::fs::write(dir.join("src/lib.rs"), "agent edit\n").unwrap();
+        std::fs::write(dir.join("src/new.rs"), "new\n").unwrap();
+        std::fs::write(dir.join("ralph/tasks/auth/ledger.jsonl"), "{}\n").unwrap();
+        std::fs::write(dir.join("ralph/tasks/auth/prd.json"), "{}\n").unwrap();
+
+        restore(&paths, dir, &snapshot).unwrap();
+        assert_eq!(run_git(dir, &["rev-parse", "HEAD"]).trim(), snapshot.head);
+        assert_eq!(read(dir, "src/main.rs"), "main v1\n");
+        assert_eq!(read(dir, "src/lib.rs"), "v1 + user edit\n");
+        assert!(!dir.join("src/new.rs").exists());
+        assert_eq!(read(dir, "notes.txt"), "mine\n");
+        assert_eq!(read(dir, "ralph/tasks/auth/ledger.jsonl"), "{}\n");
+        assert_eq!(read(dir, "ralph/tasks/auth/prd.json"), "{}\n");
+    }
+}
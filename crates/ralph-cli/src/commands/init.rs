@@ -1,20 +1,31 @@
 // ABOUTME: 'ralph init' command implementation
 // ABOUTME: Initializes a new Ralph project with templates and directory structure
 
-use ralph_lib::Result;
+use ralph_lib::validation::{builtin_profiles, DetectRules};
+use ralph_lib::{Config, Result, ValidationConfig, WorkspaceLedger};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Configuration for init command
 pub struct InitConfig {
     pub dry_run: bool,
     pub verbose: bool,
+    /// Where to place ralph/ when run inside a workspace member ("workspace" or "subproject")
+    pub scope: Option<String>,
+    /// Print the available validation profiles and exit
+    pub list_profiles: bool,
+    /// Populate validation.json with the detected built-in profiles
+    pub detect: bool,
+    pub project: Config,
 }
 
 /// Initialize a new Ralph project
 pub fn run(config: &InitConfig) -> Result<()> {
     let cwd = std::env::current_dir()?;
+    if config.list_profiles {
+        return list_profiles(&cwd, config.project.paths.validation());
+    }
 
     if config.verbose {
         println!("Initializing Ralph project in {}", cwd.display());
@@ -70,10 +81,33 @@ pub fn run(config: &InitConfig) -> Result<()> {
         println!("Detected git root: {}", git_root.display());
     }
 
-    let dirs = ["ralph/tasks", "docs/ralph", ".githooks"];
+    // Avoid split-brain setups: inside a workspace member, the user must pick where ralph/ lives
+    let base = match find_workspace_root(&cwd, &git_root) {
+        Some(root) => match config.scope.as_deref() {
+            Some("workspace") => root,
+            Some("subproject") => cwd.clone(),
+            _ => {
+                return Err(ralph_lib::RalphError::Command(format!(
+                    "{} is inside the workspace at {}. Re-run with --scope workspace to place \
+                     ralph/ at the workspace root, or --scope subproject to scope it to this directory",
+                    cwd.display(),
+                    root.display()
+                )));
+            }
+        },
+        None => cwd.clone(),
+    };
 
-    for dir in &dirs {
-        let path = cwd.join(dir);
+    if config.verbose && base != cwd {
+        println!("Using workspace root: {}", base.display());
+    }
+
+    let paths = &config.project.paths;
+    let hooks_dir = config.project.hooks.dir();
+    let dirs = [paths.tasks(), paths.docs(), hooks_dir];
+
+    for dir in dirs {
+        let path = base.join(dir);
         if config.dry_run {
             println!("[dry-run] Would create directory: {}", path.display());
         } else {
@@ -190,21 +224,36 @@ pub fn run(config: &InitConfig) -> Result<()> {
     }
 
     create_template_file(
-        &cwd,
-        ".githooks/commit-msg",
+        &base,
+        hooks_dir.join("commit-msg"),
         COMMIT_MSG_HOOK_TEMPLATE,
         config,
     )?;
 
     // Create validation.json if it doesn't exist
-    let validation_path = cwd.join("ralph/validation.json");
+    let validation_path = base.join(paths.validation());
     if !validation_path.exists() || config.dry_run {
-        create_template_file(
-            &cwd,
-            "ralph/validation.json",
-            VALIDATION_JSON_TEMPLATE,
-            config,
-        )?;
+        let content = if config.detect {
+            detected_validation_json(&base)?
+        } else {
+            VALIDATION_JSON_TEMPLATE.to_string()
+        };
+        create_template_file(&base, paths.validation(), &content, config)?;
+    } else if config.detect {
+        println!(
+            "⚠️  {} already exists; not replacing it with detected profiles",
+            validation_path.display()
+        );
+    }
+
+    // Opt the project into the workspace ledger of cross-feature activity
+    if config.dry_run {
+        println!(
+            "[dry-run] Would create workspace ledger: {}",
+            base.join(paths.workspace_ledger()).display()
+        );
+    } else {
+        WorkspaceLedger::create(base.join(paths.workspace_ledger()))?;
     }
 
     // Set commit-msg hook as executable
@@ -212,7 +261,7 @@ pub fn run(config: &InitConfig) -> Result<()> {
         #[cfg(unix)]
         {
             use std::os::unix::fs::PermissionsExt;
-            let hook_path = cwd.join(".githooks/commit-msg");
+            let hook_path = base.join(hooks_dir).join("commit-msg");
             if hook_path.exists() {
                 let mut perms = fs::metadata(&hook_path)?.permissions();
                 perms.set_mode(0o755);
@@ -226,16 +275,60 @@ pub fn run(config: &InitConfig) -> Result<()> {
         println!("Planner and Implementer agents installed");
         println!();
         println!("Next steps:");
-        println!("  1. Run: git config core.hooksPath .githooks");
+        println!(
+            "  1. Run: git config core.hooksPath {}",
+            hooks_path(&base, &git_root, hooks_dir)
+        );
         println!("  2. Create a feature: ralph plan <feature-slug>");
     }
 
     Ok(())
 }
 
+/// Find an enclosing Cargo or npm workspace root above `cwd`, stopping at the git root
+fn find_workspace_root(cwd: &Path, git_root: &Path) -> Option<PathBuf> {
+    let cwd = cwd.canonicalize().unwrap_or_else(|_| cwd.to_path_buf());
+    let git_root = git_root
+        .canonicalize()
+        .unwrap_or_else(|_| git_root.to_path_buf());
+    if !cwd.starts_with(&git_root) {
+        return None;
+    }
+
+    cwd.ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(&git_root))
+        .find(|dir| is_workspace_root(dir))
+        .map(Path::to_path_buf)
+}
+
+/// Check whether a directory declares a Cargo, npm/yarn, or pnpm workspace
+fn is_workspace_root(dir: &Path) -> bool {
+    let cargo_workspace = fs::read_to_string(dir.join("Cargo.toml"))
+        .is_ok_and(|content| content.lines().any(|line| line.trim() == "[workspace]"));
+    let npm_workspace = fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .is_some_and(|json| json.get("workspaces").is_some());
+
+    cargo_workspace || npm_workspace || dir.join("pnpm-workspace.yaml").exists()
+}
+
+/// Hooks directory relative to the git root, as `core.hooksPath` expects
+fn hooks_path(base: &Path, git_root: &Path, hooks_dir: &Path) -> String {
+    let base = base.canonicalize().unwrap_or_else(|_| base.to_path_buf());
+    let git_root = git_root
+        .canonicalize()
+        .unwrap_or_else(|_| git_root.to_path_buf());
+    match base.strip_prefix(&git_root) {
+        Ok(rel) if !rel.as_os_str().is_empty() => rel.join(hooks_dir).display().to_string(),
+        _ => hooks_dir.display().to_string(),
+    }
+}
+
 fn create_template_file(
     base: &Path,
-    relative_path: &str,
+    relative_path: impl AsRef<Path>,
     content: &str,
     config: &InitConfig,
 ) -> Result<()> {
@@ -287,8 +380,96 @@ If validation fails, read the validation output carefully, fix root causes, and
 
 Implement one requirement per iteration. Update PRD status only after validation passes.
 Append one ledger event per iteration. Full test sweep every 5th iteration.
+Read ralph/tasks/<slug>/scratchpad.md first each iteration; update it with notes and TODOs before finishing.
 "#;
 
+/// Print the built-in validation profiles and any defined in the project's validation file
+///
+/// `validation` is the file's path as configured, relative to `root` unless absolute; it is
+/// shown as configured.
+fn list_profiles(root: &Path, validation: &Path) -> Result<()> {
+    let validation_path = root.join(validation);
+    let project = if validation_path.exists() {
+        ValidationConfig::from_file(&validation_path)?
+    } else {
+        ValidationConfig::builtin()
+    };
+
+    println!("Built-in validation profiles:");
+    for (name, profile) in builtin_profiles() {
+        let overridden = if project.profiles.contains_key(name) {
+            format!(" (overridden in {})", validation.display())
+        } else {
+            String::new()
+        };
+        println!(
+            "  {name:<14} {}{overridden}",
+            profile.description.as_deref().unwrap_or_default()
+        );
+        println!("  {:<14} detects: {}", "", describe_detect(&profile.detect));
+    }
+
+    if !project.profiles.is_empty() {
+        let mut names: Vec<&String> = project.profiles.keys().collect();
+        names.sort();
+        println!();
+        println!("Profiles in {}:", validation.display());
+        for name in names {
+            let profile = &project.profiles[name];
+            match &profile.description {
+                Some(description) => println!("  {name:<14} {description}"),
+                None => println!("  {name}"),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// validation.json holding the built-in profiles detected in `base`
+///
+/// Falls back to the empty template (with a warning) if no built-in profile matches.
+fn detected_validation_json(base: &Path) -> Result<String> {
+    let builtin = ValidationConfig::builtin();
+    let mut detected = builtin.detect_profiles(base);
+    if detected.is_empty() {
+        println!("⚠️  No known project type detected; edit the \"default\" profile in ralph/validation.json");
+        return Ok(VALIDATION_JSON_TEMPLATE.to_string());
+    }
+    detected.sort_unstable();
+    println!("🔍 Detected validation profiles: {}", detected.join(", "));
+
+    let mut profiles = serde_json::Map::new();
+    for name in detected {
+        profiles.insert(
+            name.to_string(),
+            serde_json::to_value(&builtin_profiles()[name])?,
+        );
+    }
+    let config = serde_json::json!({
+        "schemaVersion": builtin.schema_version,
+        "profiles": profiles,
+    });
+    Ok(serde_json::to_string_pretty(&config)? + "\n")
+}
+
+/// Summarize detection rules on one line (e.g., `package.json; without yarn.lock`)
+fn describe_detect(rules: &DetectRules) -> String {
+    let mut parts = Vec::new();
+    if !rules.any_files_exist.is_empty() {
+        parts.push(rules.any_files_exist.join(" or "));
+    }
+    if !rules.all_files_exist.is_empty() {
+        parts.push(rules.all_files_exist.join(" and "));
+    }
+    if !rules.none_files_exist.is_empty() {
+        parts.push(format!("without {}", rules.none_files_exist.join(" or ")));
+    }
+    for rule in &rules.file_contains {
+        parts.push(format!("{} containing {}", rule.file, rule.contains));
+    }
+    parts.join("; ")
+}
+
 const COMMIT_MSG_HOOK_TEMPLATE: &str = r#"#!/usr/bin/env bash
 set -euo pipefail
 exec ralph hook commit-msg "$1"
@@ -309,3 +490,80 @@ const VALIDATION_JSON_TEMPLATE: &str = r#"{
   }
 }
 "#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_workspace_root() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let member = root.join("crates/member");
+        fs::create_dir_all(&member).unwrap();
+        fs::write(member.join("Cargo.toml"), "[package]\nname = \"member\"\n").unwrap();
+
+        assert!(find_workspace_root(&member, root).is_none());
+
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        let found = find_workspace_root(&member, root).unwrap();
+        assert_eq!(found, root.canonicalize().unwrap());
+        assert!(find_workspace_root(root, root).is_none());
+    }
+
+    #[test]
+    fn test_npm_workspace_detection() {
+        let temp = TempDir::new().unwrap();
+        assert!(!is_workspace_root(temp.path()));
+        fs::write(
+            temp.path().join("package.json"),
+            r#"{"name": "root", "workspaces": ["packages/*"]}"#,
+        )
+        .unwrap();
+        assert!(is_workspace_root(temp.path()));
+    }
+
+    #[test]
+    fn test_detected_validation_json() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(
+            detected_validation_json(temp.path()).unwrap(),
+            VALIDATION_JSON_TEMPLATE
+        );
+
+        fs::write(
+            temp.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\n",
+        )
+        .unwrap();
+        fs::write(temp.path().join("go.mod"), "module app\n").unwrap();
+        let config =
+            ValidationConfig::from_json(&detected_validation_json(temp.path()).unwrap()).unwrap();
+        let mut names: Vec<&String> = config.profiles.keys().collect();
+        names.sort();
+        assert_eq!(names, ["go", "rust-cargo"]);
+        assert_eq!(
+            config.profiles["rust-cargo"].commands.lint,
+            vec!["cargo clippy --all-targets -- -D warnings"]
+        );
+    }
+
+    #[test]
+    fn test_hooks_path() {
+        let temp = TempDir::new().unwrap();
+        let sub = temp.path().join("app");
+        fs::create_dir_all(&sub).unwrap();
+        let hooks = Path::new(".githooks");
+        assert_eq!(hooks_path(temp.path(), temp.path(), hooks), ".githooks");
+        assert_eq!(hooks_path(&sub, temp.path(), hooks), "app/.githooks");
+        assert_eq!(
+            hooks_path(&sub, temp.path(), Path::new("tools/hooks")),
+            "app/tools/hooks"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+// ABOUTME: 'ralph ledger' command implementations
+// ABOUTME: Reconciles ledgers that diverged across branches or machines
+
+use ralph_lib::{Config, Ledger, RalphError, Result};
+use std::process::Command;
+
+/// Configuration for ledger merge command
+pub struct MergeConfig {
+    pub slug: String,
+    /// Ledger file to merge in
+    pub other: Option<String>,
+    /// Git ref whose committed copy of the feature's ledger to merge in
+    pub git_ref: Option<String>,
+    pub dry_run: bool,
+    pub verbose: bool,
+    pub project: Config,
+}
+
+/// Merge another copy of a feature's ledger into the local one
+pub fn merge(config: &MergeConfig) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let relative = config
+        .project
+        .paths
+        .tasks()
+        .join(&config.slug)
+        .join("ledger.jsonl");
+    let ledger_path = cwd.join(&relative);
+
+    let mut ledger = if ledger_path.exists() {
+        Ledger::from_file(&ledger_path)?
+    } else {
+        Ledger::create(&ledger_path)?
+    };
+
+    let (other, source) = match (&config.other, &config.git_ref) {
+        (Some(path), _) => (Ledger::from_file(path)?, path.clone()),
+        (None, Some(git_ref)) => {
+            let spec = format!("{git_ref}:{}", relative.display());
+            let output = Command::new("git")
+                .args(["show", &spec])
+                .current_dir(&cwd)
+                .output()?;
+            if !output.status.success() {
+                return Err(RalphError::Git(format!(
+                    "git show {spec}: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )));
+            }
+            (
+                Ledger::from_jsonl(&String::from_utf8_lossy(&output.stdout))?,
+                spec,
+            )
+        }
+        (None, None) => {
+            return Err(RalphError::Ledger(
+                "Nothing to merge: pass a ledger file or --ref".to_string(),
+            ))
+        }
+    };
+
+    if config.verbose {
+        println!(
+            "Merging {} event(s) from {source} into {} event(s) at {}",
+            other.events().len(),
+            ledger.events().len(),
+            ledger_path.display()
+        );
+    }
+
+    let added = ledger.merge(&other);
+    if added == 0 {
+        println!("✅ Ledger already contains every event from {source}");
+        return Ok(());
+    }
+    if config.dry_run {
+        println!("[dry-run] Would add {added} event(s) from {source}");
+        return Ok(());
+    }
+
+    ledger.save(&ledger_path)?;
+    println!(
+        "🔀 Added {added} event(s) from {source} ({} total)",
+        ledger.events().len()
+    );
+    if ledger.events().iter().any(|e| e.prev_hash.is_some()) {
+        println!("⚠️  Merged events break the hash chain; 'ralph verify-ledger' will report them");
+    }
+    Ok(())
+}
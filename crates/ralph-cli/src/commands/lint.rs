@@ -0,0 +1,96 @@
+// ABOUTME: 'ralph lint' command implementation
+// ABOUTME: Reports PRD problems that would derail the implement loop
+
+use ralph_lib::lint::lint_prd;
+use ralph_lib::{prd_path, Config, Ledger, Prd, RalphError, Result, ValidationConfig};
+use std::fs;
+use std::path::Path;
+
+/// Configuration for lint command
+pub struct LintConfig {
+    pub slug: Option<String>,
+    pub verbose: bool,
+    pub project: Config,
+}
+
+/// Lint one feature's PRD, or every feature when no slug is given
+pub fn run(config: &LintConfig) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let tasks_dir = cwd.join(config.project.paths.tasks());
+    let validation_path = cwd.join(config.project.paths.validation());
+
+    if !tasks_dir.exists() {
+        println!("No Ralph tasks found. Run 'ralph init' first.");
+        return Ok(());
+    }
+
+    let validation = Some(if validation_path.exists() {
+        ValidationConfig::from_file(&validation_path)?
+    } else {
+        ValidationConfig::builtin()
+    });
+
+    let slugs = match &config.slug {
+        Some(slug) => vec![slug.clone()],
+        None => {
+            let mut slugs = Vec::new();
+            for entry in fs::read_dir(&tasks_dir)?.flatten() {
+                if prd_path(entry.path()).exists() {
+                    if let Some(name) = entry.file_name().to_str() {
+                        slugs.push(name.to_string());
+                    }
+                }
+            }
+            slugs.sort();
+            slugs
+        }
+    };
+
+    let mut total = 0;
+    for slug in &slugs {
+        total += lint_feature(&tasks_dir.join(slug), slug, validation.as_ref(), config)?;
+    }
+
+    if total > 0 {
+        return Err(RalphError::PrdValidation(format!(
+            "{total} lint warning(s)"
+        )));
+    }
+    println!("✅ No lint warnings");
+    Ok(())
+}
+
+fn lint_feature(
+    task_dir: &Path,
+    slug: &str,
+    validation: Option<&ValidationConfig>,
+    config: &LintConfig,
+) -> Result<usize> {
+    let prd_path = prd_path(task_dir);
+    let ledger_path = task_dir.join("ledger.jsonl");
+
+    if !prd_path.exists() {
+        println!("❌ Feature '{slug}' not found");
+        return Ok(1);
+    }
+
+    let prd = Prd::from_file(&prd_path)?;
+    let ledger = if ledger_path.exists() {
+        Some(Ledger::from_file(&ledger_path)?)
+    } else {
+        None
+    };
+
+    let warnings = lint_prd(&prd, ledger.as_ref(), validation);
+    if warnings.is_empty() {
+        if config.verbose {
+            println!("✅ {slug}");
+        }
+    } else {
+        println!("⚠️  {slug}");
+        for warning in &warnings {
+            println!("    {warning}");
+        }
+    }
+    Ok(warnings.len())
+}
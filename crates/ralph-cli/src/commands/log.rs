@@ -0,0 +1,189 @@
+// ABOUTME: 'ralph log' command implementation
+// ABOUTME: Lists a feature's ledger events with filters, as pretty text, JSON, or JSONL
+
+use chrono::{DateTime, NaiveDate, Utc};
+use ralph_lib::{Config, EventStatus, Ledger, LedgerEvent, RalphError, Result};
+use std::collections::VecDeque;
+use std::path::Path;
+
+/// Configuration for log command
+pub struct LogConfig {
+    pub slug: String,
+    /// Only events for this requirement
+    pub requirement: Option<String>,
+    /// Only events at or after this time (RFC 3339 or YYYY-MM-DD)
+    pub since: Option<String>,
+    /// Only events with this status (started, in_progress, done, failed)
+    pub status: Option<String>,
+    /// Only the most recent N matching events
+    pub limit: Option<usize>,
+    /// Output format: pretty, json, or jsonl
+    pub format: String,
+    pub verbose: bool,
+    pub project: Config,
+}
+
+/// Event filters parsed from the command line
+struct EventFilter {
+    requirement: Option<String>,
+    since: Option<DateTime<Utc>>,
+    status: Option<EventStatus>,
+}
+
+impl EventFilter {
+    fn from_config(config: &LogConfig) -> Result<Self> {
+        let since = config.since.as_deref().map(parse_since).transpose()?;
+        let status = config
+            .status
+            .as_deref()
+            .map(|status| {
+                serde_json::from_value(serde_json::Value::String(status.to_string()))
+                    .map_err(|_| RalphError::Ledger(format!("Unknown event status '{status}'")))
+            })
+            .transpose()?;
+        Ok(Self {
+            requirement: config.requirement.clone(),
+            since,
+            status,
+        })
+    }
+
+    fn matches(&self, event: &LedgerEvent) -> bool {
+        self.requirement
+            .as_ref()
+            .map_or(true, |req| &event.requirement == req)
+            && self.since.map_or(true, |since| event.timestamp >= since)
+            && self.status.as_ref().map_or(true, |s| &event.status == s)
+    }
+}
+
+/// Show a feature's ledger events, oldest first
+pub fn run(config: &LogConfig) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let task_dir = cwd.join(config.project.paths.tasks()).join(&config.slug);
+    let filter = EventFilter::from_config(config)?;
+    let events = matching_events(&task_dir, &filter, config.limit)?;
+
+    if config.verbose {
+        eprintln!(
+            "{} matching event(s) in {}",
+            events.len(),
+            task_dir.display()
+        );
+    }
+
+    match config.format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&events)?),
+        "jsonl" => {
+            for event in &events {
+                println!("{}", serde_json::to_string(event)?);
+            }
+        }
+        _ => {
+            if events.is_empty() {
+                println!("No matching ledger events for '{}'", config.slug);
+            }
+            for event in &events {
+                println!("{}", format_event(event));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Stream the feature's ledger, keeping only the last `limit` matches in memory
+fn matching_events(
+    task_dir: &Path,
+    filter: &EventFilter,
+    limit: Option<usize>,
+) -> Result<Vec<LedgerEvent>> {
+    let mut kept = VecDeque::new();
+    let mut keep = |event: LedgerEvent| {
+        if filter.matches(&event) {
+            if limit.is_some_and(|limit| kept.len() == limit) {
+                kept.pop_front();
+            }
+            if limit != Some(0) {
+                kept.push_back(event);
+            }
+        }
+    };
+
+    // Fall back to an archived AVRO ledger, like 'ralph status'
+    let ledger_path = task_dir.join("ledger.jsonl");
+    let avro_path = task_dir.join("ledger.avro");
+    if ledger_path.exists() {
+        for event in Ledger::stream(&ledger_path)? {
+            keep(event?);
+        }
+    } else if avro_path.exists() {
+        for event in Ledger::from_avro(&avro_path)?.events() {
+            keep(event.clone());
+        }
+    } else {
+        return Err(RalphError::Ledger(format!(
+            "No ledger found in {}",
+            task_dir.display()
+        )));
+    }
+    Ok(kept.into())
+}
+
+/// Parse `--since` as an RFC 3339 timestamp or a date (midnight UTC)
+fn parse_since(since: &str) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(since) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(since, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|time| time.and_utc())
+        .ok_or_else(|| {
+            RalphError::Ledger(format!(
+                "Invalid --since '{since}': expected YYYY-MM-DD or an RFC 3339 timestamp"
+            ))
+        })
+}
+
+/// One-line summary of an event
+pub fn format_event(event: &LedgerEvent) -> String {
+    let mut line = format!(
+        "  [{}] {} {} {:?}{}",
+        event.timestamp.format("%Y-%m-%d %H:%M"),
+        event.iteration,
+        event.requirement,
+        event.status,
+        event
+            .validation_passed
+            .map_or("", |v| if v { " ✅" } else { " ❌" })
+    );
+    if let (Some(sha), Some(_)) = (&event.commit_sha, &event.diff_range) {
+        line.push_str(&format!(" @{}", &sha[..sha.len().min(7)]));
+    }
+    if let Some(message) = &event.message {
+        line.push_str(&format!(" - {message}"));
+    } else if let Some(payload) = &event.payload {
+        line.push_str(&format!(" - {}", payload.describe()));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(
+            parse_since("2026-01-19").unwrap().to_rfc3339(),
+            "2026-01-19T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_since("2026-01-19T10:30:00+02:00")
+                .unwrap()
+                .to_rfc3339(),
+            "2026-01-19T08:30:00+00:00"
+        );
+        assert!(parse_since("yesterday").is_err());
+    }
+}
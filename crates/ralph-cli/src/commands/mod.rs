@@ -1,8 +1,16 @@
 // ABOUTME: Command implementations for Ralph CLI
-// ABOUTME: Submodules for init, plan, implement, status, and hook commands
+// ABOUTME: Submodules for init, plan, implement, status, log, lint, split, note, annotate, verify-ledger, ledger, export, and hook commands
 
+pub mod annotate;
+pub mod export;
 pub mod hook;
 pub mod implement;
 pub mod init;
+pub mod ledger;
+pub mod lint;
+pub mod log;
+pub mod note;
 pub mod plan;
+pub mod split;
 pub mod status;
+pub mod verify_ledger;
@@ -0,0 +1,59 @@
+// ABOUTME: 'ralph note' command implementation
+// ABOUTME: Records manual fixes made between agent iterations so later prompts can account for them
+
+use ralph_lib::ledger::RUN_REQUIREMENT;
+use ralph_lib::{prd_path, Config, Ledger, LedgerEvent, Prd, RalphError, Result};
+
+/// Configuration for note command
+pub struct NoteConfig {
+    pub slug: String,
+    /// Requirement the note applies to (the whole run if omitted)
+    pub requirement: Option<String>,
+    pub message: String,
+    pub verbose: bool,
+    pub project: Config,
+}
+
+/// Append a human note to a feature's ledger
+pub fn run(config: &NoteConfig) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let task_dir = cwd.join(config.project.paths.tasks()).join(&config.slug);
+    let prd_path = prd_path(&task_dir);
+
+    if !prd_path.exists() {
+        println!("❌ Feature '{}' not found", config.slug);
+        return Ok(());
+    }
+    if config.message.trim().is_empty() {
+        return Err(RalphError::Ledger("Note message is empty".to_string()));
+    }
+
+    let requirement = match &config.requirement {
+        Some(req_id) => {
+            let prd = Prd::from_file(&prd_path)?;
+            if !prd.requirements.iter().any(|r| &r.id == req_id) {
+                return Err(RalphError::PrdValidation(format!(
+                    "Requirement '{req_id}' not found in feature '{}'",
+                    config.slug
+                )));
+            }
+            req_id.clone()
+        }
+        None => RUN_REQUIREMENT.to_string(),
+    };
+
+    let ledger_path = task_dir.join("ledger.jsonl");
+    let mut ledger = Ledger::from_file(&ledger_path)?;
+    let iteration = ledger.latest_iteration();
+    ledger.append(LedgerEvent::note(
+        iteration,
+        &requirement,
+        config.message.trim(),
+    ))?;
+
+    println!("📝 Noted on {requirement} after iteration {iteration}");
+    if config.verbose {
+        println!("   Ledger: {}", ledger_path.display());
+    }
+    Ok(())
+}
@@ -1,7 +1,10 @@
 // ABOUTME: 'ralph plan' command implementation
 // ABOUTME: Launches interactive planning session with GitHub Copilot CLI
 
-use ralph_lib::{MarkdownPrd, Prd, Requirement, RequirementStatus, Result};
+use ralph_lib::{
+    prd_path, ClarifyingQuestion, Config, MarkdownPrd, Prd, Requirement, RequirementStatus, Result,
+    WorkspaceActivity, WorkspaceLedger,
+};
 use std::fs;
 use std::path::Path;
 use std::process::Command;
@@ -11,14 +14,30 @@ pub struct PlanConfig {
     pub slug: String,
     pub dry_run: bool,
     pub verbose: bool,
+    /// Have the planner write clarifying questions to questions.md instead of planning
+    pub questions: bool,
+    /// Answered questions file to record in the planning log before planning
+    pub answers: Option<String>,
+    /// File format for a newly created PRD ("json" or "toml")
+    pub format: String,
+    /// Planner model and task/doc paths
+    pub project: Config,
 }
 
 /// Start or resume a planning session
 pub fn run(config: &PlanConfig) -> Result<()> {
     let cwd = std::env::current_dir()?;
-    let task_dir = cwd.join("ralph/tasks").join(&config.slug);
-    let prd_path = task_dir.join("prd.json");
-    let md_path = cwd.join("docs/ralph").join(&config.slug).join("prd.md");
+    let task_dir = cwd.join(config.project.paths.tasks()).join(&config.slug);
+    let existing_prd = prd_path(&task_dir);
+    let prd_path = if existing_prd.exists() {
+        existing_prd
+    } else {
+        task_dir.join(format!("prd.{}", config.format))
+    };
+    let md_path = cwd
+        .join(config.project.paths.docs())
+        .join(&config.slug)
+        .join("prd.md");
 
     if config.verbose {
         println!("Planning feature: {}", config.slug);
@@ -46,6 +65,11 @@ pub fn run(config: &PlanConfig) -> Result<()> {
             println!("[dry-run] Would create PRD: {}", prd_path.display());
         } else {
             new_prd.save(&prd_path)?;
+            WorkspaceLedger::record(
+                cwd.join(config.project.paths.workspace_ledger()),
+                &config.slug,
+                WorkspaceActivity::FeatureCreated,
+            )?;
             if config.verbose {
                 println!("Created initial PRD: {}", prd_path.display());
             }
@@ -60,9 +84,74 @@ pub fn run(config: &PlanConfig) -> Result<()> {
         ensure_markdown_prd(&prd, &md_path)?;
     }
 
+    // Question capture: the planner writes questions for the user to answer offline
+    let questions_path = md_path.with_file_name("questions.md");
+    if config.questions {
+        if config.dry_run {
+            println!(
+                "[dry-run] Would ask planner to write questions to: {}",
+                questions_path.display()
+            );
+        } else {
+            launch_copilot_questions(
+                &cwd,
+                &config.slug,
+                &prd_path,
+                &questions_path,
+                config.project.models.planner(),
+            )?;
+            println!();
+            println!(
+                "Answer the questions inline in {}",
+                questions_path.display()
+            );
+            println!(
+                "Then run: ralph plan {} --answers {}",
+                config.slug,
+                questions_path.display()
+            );
+        }
+        return Ok(());
+    }
+
+    // Record answered questions in the planning log before resuming the session
+    let mut planner_note = None;
+    if let Some(answers_path) = &config.answers {
+        let questions = ClarifyingQuestion::parse_all(&fs::read_to_string(answers_path)?);
+        let unanswered = questions.iter().filter(|q| q.answer.is_empty()).count();
+        if unanswered > 0 {
+            println!("⚠️  Warning: {unanswered} question(s) left unanswered");
+        }
+        if config.dry_run {
+            println!(
+                "[dry-run] Would record {} answered question(s) in: {}",
+                questions.len() - unanswered,
+                md_path.display()
+            );
+        } else {
+            let mut md = MarkdownPrd::from_file(&md_path)?;
+            md.append_to_section(
+                "PLANNING_LOG",
+                &ClarifyingQuestion::to_planning_log(&questions),
+            );
+            md.save(&md_path)?;
+            println!(
+                "📝 Recorded {} question(s) in the planning log",
+                questions.len()
+            );
+        }
+        planner_note = Some(
+            "The user has answered your clarifying questions; the answers are in the \
+             latest 'Clarifying Q&A' entry of the Planning Log. Use them to draft the PRD.",
+        );
+    }
+
     // Launch Copilot planning session
     if config.dry_run {
-        println!("[dry-run] Would launch: copilot --agent=ralph-planner --model claude-opus-4.5");
+        println!(
+            "[dry-run] Would launch: copilot --agent=ralph-planner --model {}",
+            config.project.models.planner()
+        );
         println!("[dry-run] Working directory: {}", task_dir.display());
     } else {
         println!("🚀 Launching planning session for '{}'...", config.slug);
@@ -71,7 +160,14 @@ pub fn run(config: &PlanConfig) -> Result<()> {
         println!("Markdown doc: {}", md_path.display());
         println!();
 
-        launch_copilot_planner(&cwd, &config.slug, &prd_path, &md_path)?;
+        launch_copilot_planner(
+            &cwd,
+            &config.slug,
+            &prd_path,
+            &md_path,
+            planner_note,
+            config.project.models.planner(),
+        )?;
     }
 
     Ok(())
@@ -91,7 +187,14 @@ fn create_initial_prd(slug: &str) -> Prd {
             title: "Initial requirement".to_string(),
             status: RequirementStatus::Todo,
             acceptance_criteria: vec!["Define acceptance criteria during planning".to_string()],
+            risk: None,
+            prompt_hints: None,
+            paths: Vec::new(),
+            priority: None,
+            extra: serde_json::Map::new(),
         }],
+        skip: Vec::new(),
+        extra: serde_json::Map::new(),
     }
 }
 
@@ -117,9 +220,11 @@ fn launch_copilot_planner(
     slug: &str,
     prd_path: &Path,
     md_path: &Path,
+    note: Option<&str>,
+    model: &str,
 ) -> Result<()> {
     // Build initial prompt with context so user doesn't have to provide it
-    let prompt = format!(
+    let mut prompt = format!(
         "You are planning feature '{slug}'. \
          The PRD JSON is at @{prd} and the markdown doc is at @{md}. \
          Please read the PRD and begin the planning session.",
@@ -127,13 +232,17 @@ fn launch_copilot_planner(
         prd = prd_path.display(),
         md = md_path.display()
     );
+    if let Some(note) = note {
+        prompt.push(' ');
+        prompt.push_str(note);
+    }
 
     // Run copilot from repo root so it finds .github/agents/
     let status = Command::new("copilot")
         .args([
             "--agent=ralph-planner",
             "--model",
-            "claude-opus-4.5",
+            model,
             "--interactive",
             &prompt,
         ])
@@ -160,3 +269,52 @@ fn launch_copilot_planner(
 
     Ok(())
 }
+
+fn launch_copilot_questions(
+    repo_root: &Path,
+    slug: &str,
+    prd_path: &Path,
+    questions_path: &Path,
+    model: &str,
+) -> Result<()> {
+    let prompt = format!(
+        "You are planning feature '{slug}'. Read the PRD at @{prd}. \
+         Do not draft requirements yet. Instead write at least 10 clarifying questions to \
+         {questions}, starting with the heading '# Clarifying questions: {slug}'. \
+         Write each question as a heading of the form '## Q<n>: <question>' followed by \
+         a blank line where the user will type the answer. Do not answer the questions.",
+        prd = prd_path.display(),
+        questions = questions_path.display()
+    );
+
+    println!("❓ Generating clarifying questions for '{slug}'...");
+    let status = Command::new("copilot")
+        .args([
+            "-p",
+            &prompt,
+            "--agent=ralph-planner",
+            "--model",
+            model,
+            "--allow-all-tools",
+        ])
+        .current_dir(repo_root)
+        .status();
+
+    match status {
+        Ok(exit_status) if exit_status.success() => {
+            println!("✅ Questions written to {}", questions_path.display());
+        }
+        Ok(exit_status) => {
+            println!("⚠️  Question generation exited with status: {exit_status}");
+        }
+        Err(e) => {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                println!("❌ Error: 'copilot' command not found");
+            } else {
+                return Err(e.into());
+            }
+        }
+    }
+
+    Ok(())
+}
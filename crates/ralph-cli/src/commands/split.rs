@@ -0,0 +1,114 @@
+// ABOUTME: 'ralph split' command implementation
+// ABOUTME: Moves selected requirements out of a PRD into a new feature slug
+
+use ralph_lib::{
+    prd_path, Config, Ledger, LedgerEvent, MarkdownPrd, Prd, RalphError, Result, WorkspaceActivity,
+    WorkspaceLedger,
+};
+use std::path::Path;
+
+/// Configuration for split command
+pub struct SplitConfig {
+    pub slug: String,
+    pub new_slug: String,
+    pub requirements: Vec<String>,
+    pub dry_run: bool,
+    pub verbose: bool,
+    pub project: Config,
+}
+
+/// Split requirements from an existing feature into a new one
+pub fn run(config: &SplitConfig) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let tasks_dir = cwd.join(config.project.paths.tasks());
+    let prd_path = prd_path(tasks_dir.join(&config.slug));
+    let new_task_dir = tasks_dir.join(&config.new_slug);
+    let new_prd_path = new_task_dir.join(prd_path.file_name().expect("PRD path has a file name"));
+
+    if !prd_path.exists() {
+        println!("❌ Feature '{}' not found", config.slug);
+        return Ok(());
+    }
+    if new_prd_path.exists() {
+        return Err(RalphError::PrdValidation(format!(
+            "Feature '{}' already exists at {}",
+            config.new_slug,
+            new_prd_path.display()
+        )));
+    }
+
+    let mut prd = Prd::from_file(&prd_path)?;
+    let new_prd = prd.split_off(&config.new_slug, &config.requirements)?;
+    let moved = config.requirements.join(", ");
+
+    if config.dry_run {
+        println!(
+            "[dry-run] Would move {moved} from '{}' to '{}'",
+            config.slug, config.new_slug
+        );
+        println!("[dry-run] Would create PRD: {}", new_prd_path.display());
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(&new_task_dir)?;
+    new_prd.save(&new_prd_path)?;
+    prd.save(&prd_path)?;
+    if config.verbose {
+        println!("Created PRD: {}", new_prd_path.display());
+        println!("Updated PRD: {}", prd_path.display());
+    }
+
+    // Record the split in both ledgers
+    let note_out = format!("Split into feature '{}'", config.new_slug);
+    let note_in = format!("Split from feature '{}'", config.slug);
+    record_split(&tasks_dir.join(&config.slug), config, &note_out)?;
+    record_split(&new_task_dir, config, &note_in)?;
+    WorkspaceLedger::record(
+        cwd.join(config.project.paths.workspace_ledger()),
+        &config.new_slug,
+        WorkspaceActivity::FeatureSplit {
+            from: config.slug.clone(),
+        },
+    )?;
+
+    // Keep the markdown docs in step with the machine PRDs
+    let docs_dir = cwd.join(config.project.paths.docs());
+    new_prd.save_markdown(
+        docs_dir.join(&config.new_slug).join("prd.md"),
+        Some(&format!("{note_in} ({moved})")),
+    )?;
+    let md_path = docs_dir.join(&config.slug).join("prd.md");
+    if md_path.exists() {
+        let mut md = MarkdownPrd::from_file(&md_path)?;
+        md.append_to_section("PLANNING_LOG", &format!("{note_out} ({moved})"));
+        md.save(&md_path)?;
+    }
+
+    println!(
+        "✂️  Moved {moved} from '{}' to '{}'",
+        config.slug, config.new_slug
+    );
+
+    Ok(())
+}
+
+/// Note each moved requirement in the ledger of `task_dir`
+fn record_split(task_dir: &Path, config: &SplitConfig, message: &str) -> Result<()> {
+    let ledger_path = task_dir.join("ledger.jsonl");
+    let mut ledger = if ledger_path.exists() {
+        Ledger::from_file(&ledger_path)?
+    } else {
+        Ledger::create(&ledger_path)?
+    };
+    let iteration = ledger.latest_iteration();
+    for req_id in &config.requirements {
+        ledger.append(LedgerEvent::moved(
+            iteration,
+            req_id,
+            &config.slug,
+            &config.new_slug,
+            message,
+        ))?;
+    }
+    Ok(())
+}
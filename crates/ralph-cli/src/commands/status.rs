@@ -1,20 +1,28 @@
 // ABOUTME: 'ralph status' command implementation
-// ABOUTME: Displays PRD status, requirements, and ledger events
+// ABOUTME: Displays PRD status, requirements, and ledger events (optionally following new ones)
 
-use ralph_lib::{Ledger, Prd, RequirementStatus, Result};
+use super::log::format_event;
+use ralph_lib::{prd_path, Config, Ledger, Prd, RequirementStatus, Result, WorkspaceLedger};
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
+
+/// Workspace ledger events shown by 'ralph status' without a slug
+const RECENT_ACTIVITY: usize = 5;
 
 /// Configuration for status command
 pub struct StatusConfig {
     pub slug: Option<String>,
     pub verbose: bool,
+    /// Keep watching the ledger and print new events as they are appended
+    pub follow: bool,
+    pub project: Config,
 }
 
 /// Show status of PRD requirements and ledger
 pub fn run(config: &StatusConfig) -> Result<()> {
     let cwd = std::env::current_dir()?;
-    let tasks_dir = cwd.join("ralph/tasks");
+    let tasks_dir = cwd.join(config.project.paths.tasks());
 
     if !tasks_dir.exists() {
         println!("No Ralph tasks found. Run 'ralph init' first.");
@@ -22,14 +30,51 @@ pub fn run(config: &StatusConfig) -> Result<()> {
     }
 
     match &config.slug {
-        Some(slug) => show_feature_status(&cwd, slug, config.verbose)?,
-        None => show_all_features(&tasks_dir, config.verbose)?,
+        Some(slug) => {
+            show_feature_status(&tasks_dir, slug, config.verbose)?;
+            if config.follow {
+                follow_ledger(&tasks_dir.join(slug).join("ledger.jsonl"))?;
+            }
+        }
+        None => show_all_features(
+            &tasks_dir,
+            &cwd.join(config.project.paths.workspace_ledger()),
+            config.verbose,
+        )?,
     }
 
     Ok(())
 }
 
-fn show_all_features(tasks_dir: &Path, verbose: bool) -> Result<()> {
+/// Stream ledger events appended after startup until interrupted
+fn follow_ledger(ledger_path: &Path) -> Result<()> {
+    const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    let mut snapshot = if ledger_path.exists() {
+        Ledger::from_file(ledger_path)?.snapshot()
+    } else {
+        Ledger::new().snapshot()
+    };
+
+    println!();
+    println!("👀 Following {} (Ctrl-C to stop)", ledger_path.display());
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        if !ledger_path.exists() {
+            continue;
+        }
+        // The loop may be mid-write; retry on the next poll instead of failing
+        let Ok(ledger) = Ledger::from_file(ledger_path) else {
+            continue;
+        };
+        for event in ledger.diff(&snapshot) {
+            println!("{}", format_event(event));
+        }
+        snapshot = ledger.snapshot();
+    }
+}
+
+fn show_all_features(tasks_dir: &Path, workspace_ledger: &Path, verbose: bool) -> Result<()> {
     let entries = fs::read_dir(tasks_dir)?;
 
     let mut features: Vec<String> = Vec::new();
@@ -48,8 +93,10 @@ fn show_all_features(tasks_dir: &Path, verbose: bool) -> Result<()> {
 
     println!("📋 Ralph Features\n");
 
+    let workspace = WorkspaceLedger::open(workspace_ledger)?;
+
     for slug in &features {
-        let prd_path = tasks_dir.join(slug).join("prd.json");
+        let prd_path = prd_path(tasks_dir.join(slug));
         if prd_path.exists() {
             match Prd::from_file(&prd_path) {
                 Ok(prd) => {
@@ -59,11 +106,21 @@ fn show_all_features(tasks_dir: &Path, verbose: bool) -> Result<()> {
                     } else {
                         "0/0".to_string()
                     };
+                    // Only the tail of each ledger is read, however long the feature has run
+                    let iteration =
+                        Ledger::latest_iteration_fast(tasks_dir.join(slug).join("ledger.jsonl"))
+                            .unwrap_or(0);
+                    let iteration = if iteration > 0 {
+                        format!(" (iteration {iteration})")
+                    } else {
+                        String::new()
+                    };
                     println!(
-                        "  {} [{}] {}",
+                        "  {} [{}] {}{}",
                         status_icon(done, total),
                         progress,
-                        prd.title
+                        prd.title,
+                        iteration
                     );
 
                     if verbose {
@@ -84,12 +141,24 @@ fn show_all_features(tasks_dir: &Path, verbose: bool) -> Result<()> {
         }
     }
 
+    if let Some(workspace) = workspace.filter(|w| !w.events().is_empty()) {
+        println!();
+        println!("Recent activity:");
+        for event in workspace.events().iter().rev().take(RECENT_ACTIVITY) {
+            println!(
+                "  [{}] {}",
+                event.timestamp.format("%Y-%m-%d %H:%M"),
+                event.describe()
+            );
+        }
+    }
+
     Ok(())
 }
 
-fn show_feature_status(cwd: &Path, slug: &str, verbose: bool) -> Result<()> {
-    let task_dir = cwd.join("ralph/tasks").join(slug);
-    let prd_path = task_dir.join("prd.json");
+fn show_feature_status(tasks_dir: &Path, slug: &str, verbose: bool) -> Result<()> {
+    let task_dir = tasks_dir.join(slug);
+    let prd_path = prd_path(&task_dir);
     let ledger_path = task_dir.join("ledger.jsonl");
 
     if !prd_path.exists() {
@@ -109,10 +178,15 @@ fn show_feature_status(cwd: &Path, slug: &str, verbose: bool) -> Result<()> {
     println!("Requirements:");
     for req in &prd.requirements {
         println!(
-            "  {} {} - {}",
+            "  {} {} - {}{}",
             req_status_icon(&req.status),
             req.id,
-            req.title
+            req.title,
+            if prd.is_skipped(&req.id) {
+                " (skipped)"
+            } else {
+                ""
+            }
         );
         if verbose {
             for ac in &req.acceptance_criteria {
@@ -121,9 +195,16 @@ fn show_feature_status(cwd: &Path, slug: &str, verbose: bool) -> Result<()> {
         }
     }
 
-    // Show ledger summary if exists
-    if ledger_path.exists() {
-        let ledger = Ledger::from_file(&ledger_path)?;
+    // Show ledger summary if exists, falling back to an archived AVRO ledger
+    let avro_path = task_dir.join("ledger.avro");
+    let ledger = if ledger_path.exists() {
+        Some(Ledger::from_file(&ledger_path)?)
+    } else if avro_path.exists() {
+        Some(Ledger::from_avro(&avro_path)?)
+    } else {
+        None
+    };
+    if let Some(ledger) = ledger {
         let events = ledger.events();
 
         if !events.is_empty() {
@@ -131,21 +212,50 @@ fn show_feature_status(cwd: &Path, slug: &str, verbose: bool) -> Result<()> {
             println!("Ledger ({} events):", events.len());
             println!("  Latest iteration: {}", ledger.latest_iteration());
 
-            if verbose {
+            let audit = ledger.audit_events();
+            if !audit.is_empty() {
+                println!();
+                println!("⚠️  Audit ({} finding(s)):", audit.len());
+                for event in &audit {
+                    println!("  {}", event.message.as_deref().unwrap_or_default());
+                }
+            }
+
+            let usage = ledger.usage_totals();
+            if usage.prompt_tokens + usage.completion_tokens > 0 {
+                println!(
+                    "  Tokens: {} in / {} out (est. ${:.2})",
+                    usage.prompt_tokens, usage.completion_tokens, usage.estimated_cost
+                );
+                if verbose {
+                    for (model, totals) in ledger.usage_by_model() {
+                        println!(
+                            "    {model}: {} in / {} out (est. ${:.2})",
+                            totals.prompt_tokens, totals.completion_tokens, totals.estimated_cost
+                        );
+                    }
+                }
+            }
+
+            let label_metrics = ledger.metrics_by_label();
+            if !label_metrics.is_empty() {
                 println!();
-                for event in events.iter().rev().take(10) {
+                println!("Labels:");
+                for (label, metrics) in &label_metrics {
                     println!(
-                        "  [{}] {} {} {:?}{}",
-                        event.timestamp.format("%Y-%m-%d %H:%M"),
-                        event.iteration,
-                        event.requirement,
-                        event.status,
-                        event
-                            .validation_passed
-                            .map_or("", |v| if v { " ✅" } else { " ❌" })
+                        "  {label}: {} iterations, {} done, {} failed ({:.0}% success)",
+                        metrics.iterations,
+                        metrics.done,
+                        metrics.failed,
+                        metrics.success_rate() * 100.0
                     );
                 }
             }
+
+            if verbose {
+                println!();
+                println!("  Run 'ralph log {slug}' to browse events");
+            }
         }
     }
 
@@ -0,0 +1,65 @@
+// ABOUTME: 'ralph verify-ledger' command implementation
+// ABOUTME: Checks the hash chain of a feature's ledger to detect edited or deleted events
+
+use ralph_lib::{Config, Ledger, RalphError, Result};
+
+/// Configuration for verify-ledger command
+pub struct VerifyLedgerConfig {
+    pub slug: String,
+    pub verbose: bool,
+    pub project: Config,
+}
+
+/// Verify that a feature's hash-chained ledger is intact
+pub fn run(config: &VerifyLedgerConfig) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let ledger_path = cwd
+        .join(config.project.paths.tasks())
+        .join(&config.slug)
+        .join("ledger.jsonl");
+
+    if !ledger_path.exists() {
+        return Err(RalphError::Ledger(format!(
+            "No ledger found at {}",
+            ledger_path.display()
+        )));
+    }
+
+    let ledger = Ledger::from_file(&ledger_path)?;
+    let events = ledger.events();
+    let Some(start) = events.iter().position(|e| e.prev_hash.is_some()) else {
+        println!(
+            "⚠️  Ledger is not hash-chained ({} events). Run 'ralph implement --hash-chain' to start a chain.",
+            events.len()
+        );
+        return Ok(());
+    };
+
+    let breaks = ledger.verify_chain()?;
+    if breaks.is_empty() {
+        println!(
+            "✅ Ledger chain intact ({} chained of {} events)",
+            events.len() - start,
+            events.len()
+        );
+        if config.verbose {
+            println!("   Head: {}", ledger.head_hash()?);
+        }
+        return Ok(());
+    }
+
+    println!("❌ Ledger chain broken:");
+    for chain_break in &breaks {
+        println!(
+            "  event {} (iteration {}, {}): {}",
+            chain_break.index + 1,
+            chain_break.iteration,
+            chain_break.requirement,
+            chain_break.reason
+        );
+    }
+    Err(RalphError::Ledger(format!(
+        "{} broken link(s) in hash chain",
+        breaks.len()
+    )))
+}
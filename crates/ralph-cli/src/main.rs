@@ -1,9 +1,17 @@
 // ABOUTME: Ralph CLI entry point for PRD automation
-// ABOUTME: Provides subcommands: init, plan, implement, status, hook
+// ABOUTME: Provides subcommands: init, plan, implement, status, log, lint, split, note, annotate, verify-ledger, ledger, export, hook
 
 mod commands;
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use commands::implement::{Queue, RunOutcome};
+use ralph_lib::budget::{Dollars, HumanDuration};
+use ralph_lib::config::{LoopConfig, SummarizerConfig, SummarizerMode};
+use ralph_lib::progress::ProgressFormat;
+use ralph_lib::throttle::Throttle;
+use ralph_lib::{Config, ModelConfig, Selection, ValidationCache};
+use std::path::PathBuf;
+use std::time::Duration;
 
 /// Ralph CLI - Automated PRD implementation using GitHub Copilot
 #[derive(Parser)]
@@ -25,6 +33,15 @@ enum Commands {
         /// Preview actions without executing
         #[arg(long)]
         dry_run: bool,
+        /// Inside a workspace member, place ralph/ at the workspace root or in this subproject
+        #[arg(long, value_parser = ["workspace", "subproject"])]
+        scope: Option<String>,
+        /// List the built-in and project validation profiles instead of initializing
+        #[arg(long, conflicts_with_all = ["dry_run", "scope", "detect"])]
+        list_profiles: bool,
+        /// Write validation.json with the built-in profiles that match this project
+        #[arg(long)]
+        detect: bool,
     },
     /// Start or resume planning session for a feature
     Plan {
@@ -33,25 +50,117 @@ enum Commands {
         /// Preview actions without executing
         #[arg(long)]
         dry_run: bool,
+        /// Write clarifying questions to questions.md for offline answering
+        #[arg(long, conflicts_with = "answers")]
+        questions: bool,
+        /// Record answers from a questions file in the planning log, then plan
+        #[arg(long, value_name = "FILE")]
+        answers: Option<String>,
+        /// File format for a new PRD
+        #[arg(long, default_value = "json", value_parser = ["json", "toml"])]
+        format: String,
+        /// Model the planner runs on (overrides [models] planner in ralph.toml)
+        #[arg(long)]
+        model: Option<String>,
     },
     /// Run implementation loop for a feature
-    Implement {
-        /// Feature slug (URL-safe identifier)
+    Implement(Box<ImplementArgs>),
+    /// Show status of PRD requirements and ledger
+    Status {
+        /// Optional feature slug (shows all if omitted)
+        slug: Option<String>,
+        /// Keep running and stream new ledger events as they are appended
+        #[arg(long, short, requires = "slug")]
+        follow: bool,
+    },
+    /// List a feature's ledger events
+    Log {
+        /// Feature slug whose ledger to show
         slug: String,
+        /// Only events for this requirement
+        #[arg(long = "req")]
+        requirement: Option<String>,
+        /// Only events at or after this time (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only events with this status
+        #[arg(long, value_parser = ["started", "in_progress", "done", "failed"])]
+        status: Option<String>,
+        /// Only the most recent N matching events
+        #[arg(long, short = 'n')]
+        limit: Option<usize>,
+        /// Output format
+        #[arg(long, default_value = "pretty", value_parser = ["pretty", "json", "jsonl"])]
+        format: String,
+    },
+    /// Check PRDs for problems before running the implement loop
+    Lint {
+        /// Optional feature slug (lints all if omitted)
+        slug: Option<String>,
+    },
+    /// Move requirements out of a feature into a new feature
+    Split {
+        /// Feature slug to split requirements from
+        slug: String,
+        /// Slug for the new feature
+        new_slug: String,
+        /// Requirement ID to move (repeatable)
+        #[arg(long = "req", required = true)]
+        requirements: Vec<String>,
         /// Preview actions without executing
         #[arg(long)]
         dry_run: bool,
-        /// Run only one iteration instead of looping until success
+    },
+    /// Record a manual change in the ledger so later iterations know about it
+    Note {
+        /// Feature slug to add the note to
+        slug: String,
+        /// Requirement the note applies to (the whole run if omitted)
+        #[arg(long = "req")]
+        requirement: Option<String>,
+        /// What was done (e.g., "manually fixed the migration")
+        message: String,
+    },
+    /// Attach a correction to a past iteration (e.g., "this failure was a flaky test")
+    Annotate {
+        /// Feature slug whose ledger to annotate
+        slug: String,
+        /// Iteration the annotation refers to
         #[arg(long)]
-        once: bool,
-        /// Maximum number of iterations (default: 10)
-        #[arg(long, default_value = "10")]
-        max_iterations: u32,
+        iteration: u32,
+        /// Requirement to annotate when the iteration touched several
+        #[arg(long = "req")]
+        requirement: Option<String>,
+        /// Annotation text
+        message: String,
     },
-    /// Show status of PRD requirements and ledger
-    Status {
-        /// Optional feature slug (shows all if omitted)
+    /// Check that a hash-chained ledger has not been edited
+    VerifyLedger {
+        /// Feature slug whose ledger to verify
+        slug: String,
+    },
+    /// Maintain a feature's ledger
+    Ledger {
+        #[command(subcommand)]
+        action: LedgerAction,
+    },
+    /// Export a feature's ledger for analysis (or its PRD with 'export prd')
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    Export {
+        #[command(subcommand)]
+        target: Option<ExportTarget>,
+        /// Feature slug whose ledger to export
+        #[arg(required = true)]
         slug: Option<String>,
+        /// Output format
+        #[arg(long, default_value = "csv", value_parser = ["csv", "otlp"])]
+        format: String,
+        /// Write to a file instead of stdout
+        #[arg(long, short)]
+        output: Option<String>,
+        /// Send OTLP traces to this collector (defaults to $OTEL_EXPORTER_OTLP_ENDPOINT)
+        #[arg(long, value_name = "URL")]
+        endpoint: Option<String>,
     },
     /// Git hook handlers
     Hook {
@@ -60,6 +169,199 @@ enum Commands {
     },
 }
 
+// Flags of 'ralph implement', boxed in `Commands` to keep the other variants small (a doc
+// comment here would replace the subcommand's help text)
+#[derive(Args)]
+struct ImplementArgs {
+    /// Feature slug (URL-safe identifier)
+    #[arg(required_unless_present_any = ["all", "queue"])]
+    slug: Option<String>,
+    /// Run every feature with remaining work, one after another, then print a summary
+    /// (iteration, time, and cost limits apply to each feature)
+    #[arg(long, conflicts_with_all = ["slug", "queue", "chore", "requirement", "resume"])]
+    all: bool,
+    /// Like --all, but only the features listed in FILE (one slug per line), in order
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["slug", "chore", "requirement", "resume"])]
+    queue: Option<PathBuf>,
+    /// Preview the next iteration, printing the exact prompt the agent would get,
+    /// without running anything
+    #[arg(long)]
+    dry_run: bool,
+    /// Run only one iteration instead of looping until success
+    #[arg(long)]
+    once: bool,
+    /// Continue an interrupted run from its checkpoint instead of starting a new one
+    #[arg(long, conflicts_with = "chore")]
+    resume: bool,
+    /// Maximum number of iterations (default: 10, or [implement] max-iterations in ralph.toml)
+    #[arg(long)]
+    max_iterations: Option<u32>,
+    /// Stop between iterations once the run has taken this long, e.g. 2h or 90m
+    /// (default: no limit, or [implement] max-duration in ralph.toml)
+    #[arg(long, value_name = "DURATION", value_parser = str::parse::<HumanDuration>)]
+    max_duration: Option<HumanDuration>,
+    /// Stop between iterations once the run's estimated agent cost reaches this many USD, e.g. 5
+    /// (default: no limit, or [implement] max-cost in ralph.toml)
+    #[arg(long, value_name = "USD", value_parser = str::parse::<Dollars>)]
+    max_cost: Option<Dollars>,
+    /// Append a docs/CHANGELOG requirement once all planned requirements are done
+    #[arg(long)]
+    docs_requirement: bool,
+    /// Commit each iteration's changes as "REQ-xx: <title>" once validation passes
+    /// (default: off, or [implement] auto-commit in ralph.toml)
+    #[arg(long)]
+    auto_commit: bool,
+    /// Roll the working tree back to its pre-agent state when an iteration fails
+    /// (default: off, or [implement] rollback in ralph.toml)
+    #[arg(long)]
+    rollback: bool,
+    /// Show each passing iteration's diff and ask before marking the requirement done
+    /// (default: off, or [implement] review in ralph.toml)
+    #[arg(long)]
+    review: bool,
+    /// With --review, accept diffs of at most this many changed lines without asking
+    /// (default: 0, or [implement] review-auto-accept in ralph.toml)
+    #[arg(long, value_name = "LINES")]
+    review_auto_accept: Option<u64>,
+    /// Push the branch and open a draft pull request (via gh) when the run ends
+    /// (default: off, or [implement] draft-pr in ralph.toml)
+    #[arg(long, conflicts_with = "chore")]
+    draft_pr: bool,
+    /// Leave the map of likely-relevant files and symbols out of implementer prompts
+    /// (default: included, or [implement] repo-map in ralph.toml)
+    #[arg(long)]
+    no_repo_map: bool,
+    /// Estimated tokens an implementer prompt may use before failure output, the repo
+    /// map, and team notes are trimmed (default: 8000, or [implement] prompt-tokens in ralph.toml)
+    #[arg(long, value_name = "TOKENS")]
+    prompt_tokens: Option<usize>,
+    /// Show a desktop notification when the run ends or a requirement keeps failing
+    /// (default: off, or [implement] desktop-notify in ralph.toml)
+    #[arg(long)]
+    desktop_notify: bool,
+    /// Start each retry with a fresh agent session instead of resuming the previous
+    /// attempt's (default: resume, or [implement] agent-sessions in ralph.toml)
+    #[arg(long)]
+    no_agent_sessions: bool,
+    /// Label this run for experiment comparison (repeatable)
+    #[arg(long = "label")]
+    labels: Vec<String>,
+    /// Score the final diff against acceptance criteria with a judge model
+    #[arg(long, value_name = "MODEL", num_args = 0..=1, default_missing_value = "claude-opus-4.5")]
+    judge: Option<String>,
+    /// Implement up to N requirements with disjoint paths concurrently in git worktrees
+    /// (default: 1, or [implement] parallel in ralph.toml)
+    #[arg(long, value_name = "N")]
+    parallel: Option<usize>,
+    /// How to pick the next requirement: fifo, priority, fewest-failures, or round-robin
+    /// (default: fifo, or [implement] selection in ralph.toml)
+    #[arg(long, value_name = "STRATEGY", value_parser = str::parse::<Selection>)]
+    selection: Option<Selection>,
+    /// Kill an implementer call after this many seconds and fail the iteration
+    /// (default: no limit, or [implement] agent-timeout in ralph.toml)
+    #[arg(long, value_name = "SECS")]
+    agent_timeout: Option<u64>,
+    /// Escalate a requirement to blocked after N failed attempts, writing a hand-off document
+    #[arg(long, value_name = "N")]
+    max_attempts: Option<u32>,
+    /// Block a requirement after N consecutive validation failures, 0 to never
+    /// (default: never, or [implement] stuck-after in ralph.toml)
+    #[arg(long, value_name = "N")]
+    stuck_after: Option<u32>,
+    /// Also file a GitHub issue (via gh) with the hand-off document
+    #[arg(long, requires = "max_attempts")]
+    open_issue: bool,
+    /// Record a hash of the previous event on each ledger event (check with 'ralph verify-ledger')
+    #[arg(long)]
+    hash_chain: bool,
+    /// Run one housekeeping iteration not tied to a requirement (e.g., "update deps"), then stop
+    #[arg(long, value_name = "DESCRIPTION", conflicts_with_all = ["once", "parallel"])]
+    chore: Option<String>,
+    /// Only work on this requirement (e.g., REQ-03), reopening it if it is done or blocked
+    #[arg(long = "req", value_name = "ID", conflicts_with = "chore")]
+    requirement: Option<String>,
+    /// Guidance for the requirement's next attempt (e.g., "the flaky test is in
+    /// tests/io.rs, mock the clock"), recorded in the ledger
+    #[arg(long, value_name = "TEXT", requires = "requirement")]
+    hint: Option<String>,
+    /// Leave these requirements (e.g., REQ-02,REQ-05) to a human; remembered in the PRD's
+    /// skip list
+    #[arg(long, value_name = "IDS", value_delimiter = ',')]
+    skip: Vec<String>,
+    /// Diff the public Rust API (via nightly rustdoc JSON) before and after the run
+    #[arg(long)]
+    api_diff: bool,
+    /// Minimum seconds between the starts of consecutive agent calls
+    #[arg(long, value_name = "SECS", default_value = "0")]
+    min_delay: u64,
+    /// Maximum agent calls running at once (0 = no cap; relevant with --parallel)
+    #[arg(long, value_name = "N", default_value = "0")]
+    max_concurrent: usize,
+    /// Retries with jittered exponential backoff when the agent is rate limited, hits a network error, or fails to start
+    #[arg(long, value_name = "N", default_value = "3")]
+    rate_limit_retries: u32,
+    /// Write JUnit XML and SARIF reports of each validation run into this directory
+    #[arg(long, value_name = "DIR")]
+    report_dir: Option<PathBuf>,
+    /// Don't stream validation command output to the console while it runs
+    #[arg(long, short)]
+    quiet: bool,
+    /// Progress output: text, or json for one JSON lifecycle event per line on stdout
+    /// (human-readable output then goes to stderr)
+    #[arg(long, value_name = "FORMAT", default_value = "text", value_parser = str::parse::<ProgressFormat>)]
+    progress_format: ProgressFormat,
+    /// Unattended mode for CI jobs: no review prompts, JSON progress on stdout, no branch
+    /// switching, and exit code 0 when complete, 2 when work is left, 3 when blocked
+    #[arg(long, conflicts_with = "review")]
+    ci: bool,
+    /// With --ci, still check out (or create) the feature branch
+    #[arg(long, requires = "ci")]
+    allow_branch_switch: bool,
+    /// Model the implementer runs on (overrides [models] implementer in ralph.toml)
+    #[arg(long)]
+    model: Option<String>,
+    /// Model that summarizes validation failures (overrides [models] summarizer in ralph.toml)
+    #[arg(long, value_name = "MODEL")]
+    summarizer_model: Option<String>,
+    /// How validation failures are summarized for the ledger: agent, heuristic (offline
+    /// error extraction), or off to truncate them (default: agent, or [summarizer] mode in ralph.toml)
+    #[arg(long, value_name = "MODE", value_parser = str::parse::<SummarizerMode>)]
+    summarizer: Option<SummarizerMode>,
+}
+
+#[derive(Subcommand)]
+enum LedgerAction {
+    /// Reconcile a diverged copy of the ledger (a file or a git ref) into the local one
+    Merge {
+        /// Feature slug whose ledger receives the events
+        slug: String,
+        /// Ledger file to merge in
+        #[arg(required_unless_present = "git_ref", conflicts_with = "git_ref")]
+        other: Option<String>,
+        /// Merge the feature's ledger as committed on this git ref (e.g., origin/main)
+        #[arg(long = "ref", value_name = "REF")]
+        git_ref: Option<String>,
+        /// Preview actions without executing
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ExportTarget {
+    /// Render the PRD with status, criteria, and planning log for sharing
+    Prd {
+        /// Feature slug to export
+        slug: String,
+        /// Output format
+        #[arg(long, default_value = "html", value_parser = ["html", "confluence"])]
+        format: String,
+        /// Write to a file instead of stdout
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+}
+
 #[derive(Subcommand)]
 enum HookType {
     /// Validate commit message references a requirement
@@ -71,38 +373,314 @@ enum HookType {
 
 fn main() {
     let cli = Cli::parse();
+    let project = match Config::load(".") {
+        Ok(project) => project,
+        Err(e) => {
+            eprintln!("❌ Error: {e}");
+            std::process::exit(1);
+        }
+    };
 
     let result = match cli.command {
-        Commands::Init { dry_run } => commands::init::run(&commands::init::InitConfig {
+        Commands::Init {
+            dry_run,
+            scope,
+            list_profiles,
+            detect,
+        } => commands::init::run(&commands::init::InitConfig {
             dry_run,
             verbose: cli.verbose,
+            project,
+            scope,
+            list_profiles,
+            detect,
         }),
-        Commands::Plan { slug, dry_run } => commands::plan::run(&commands::plan::PlanConfig {
+        Commands::Plan {
+            slug,
+            dry_run,
+            questions,
+            answers,
+            format,
+            model,
+        } => commands::plan::run(&commands::plan::PlanConfig {
             slug,
             dry_run,
             verbose: cli.verbose,
+            questions,
+            answers,
+            format,
+            project: Config {
+                models: ModelConfig {
+                    planner: model,
+                    ..ModelConfig::default()
+                },
+                ..Config::default()
+            }
+            .or(project),
+        }),
+        Commands::Implement(args) => {
+            let ImplementArgs {
+                slug,
+                all,
+                queue,
+                dry_run,
+                once,
+                resume,
+                max_iterations,
+                max_duration,
+                max_cost,
+                docs_requirement,
+                auto_commit,
+                rollback,
+                review,
+                review_auto_accept,
+                draft_pr,
+                no_repo_map,
+                prompt_tokens,
+                desktop_notify,
+                no_agent_sessions,
+                labels,
+                judge,
+                parallel,
+                selection,
+                agent_timeout,
+                max_attempts,
+                stuck_after,
+                open_issue,
+                hash_chain,
+                chore,
+                requirement,
+                hint,
+                skip,
+                api_diff,
+                min_delay,
+                max_concurrent,
+                rate_limit_retries,
+                report_dir,
+                quiet,
+                progress_format,
+                ci,
+                allow_branch_switch,
+                model,
+                summarizer_model,
+                summarizer,
+            } = *args;
+            let config = commands::implement::ImplementConfig {
+                slug: slug.unwrap_or_default(),
+                dry_run,
+                verbose: cli.verbose,
+                loop_enabled: !once,
+                resume,
+                docs_requirement,
+                labels,
+                judge_model: judge,
+                max_attempts,
+                open_issue,
+                hash_chain,
+                chore,
+                requirement,
+                hint,
+                skip,
+                api_diff,
+                throttle: Throttle::new(
+                    Duration::from_secs(min_delay),
+                    max_concurrent,
+                    rate_limit_retries,
+                ),
+                validation_cache: ValidationCache::new(),
+                report_dir,
+                quiet,
+                progress_format: if ci {
+                    ProgressFormat::Json
+                } else {
+                    progress_format
+                },
+                ci,
+                allow_branch_switch,
+                project: Config {
+                    models: ModelConfig {
+                        implementer: model,
+                        summarizer: summarizer_model,
+                        ..ModelConfig::default()
+                    },
+                    implement: LoopConfig {
+                        max_iterations,
+                        parallel,
+                        selection,
+                        agent_timeout,
+                        max_duration,
+                        max_cost,
+                        auto_commit: auto_commit.then_some(true),
+                        stuck_after,
+                        rollback: rollback.then_some(true),
+                        // Nobody is there to answer a review prompt in CI
+                        review: if ci {
+                            Some(false)
+                        } else {
+                            review.then_some(true)
+                        },
+                        review_auto_accept,
+                        draft_pr: draft_pr.then_some(true),
+                        repo_map: no_repo_map.then_some(false),
+                        prompt_tokens,
+                        desktop_notify: desktop_notify.then_some(true),
+                        agent_sessions: no_agent_sessions.then_some(false),
+                        ..LoopConfig::default()
+                    },
+                    summarizer: SummarizerConfig {
+                        mode: summarizer,
+                        ..SummarizerConfig::default()
+                    },
+                    ..Config::default()
+                }
+                .or(project),
+            };
+            let queue = match (all, queue) {
+                (true, _) => Some(Queue::All),
+                (false, Some(file)) => Some(Queue::File(file)),
+                (false, None) => None,
+            };
+            let outcome = match queue {
+                Some(queue) => commands::implement::run_queue(config, &queue),
+                None => commands::implement::run(&config).and_then(|()| {
+                    if ci && !config.dry_run {
+                        commands::implement::feature_outcome(&config)
+                    } else {
+                        Ok(RunOutcome::Complete)
+                    }
+                }),
+            };
+            match outcome {
+                Ok(outcome) if ci && outcome != RunOutcome::Complete => {
+                    std::process::exit(outcome.exit_code())
+                }
+                other => other.map(|_| ()),
+            }
+        }
+        Commands::Status { slug, follow } => {
+            commands::status::run(&commands::status::StatusConfig {
+                slug,
+                verbose: cli.verbose,
+                project,
+                follow,
+            })
+        }
+        Commands::Lint { slug } => commands::lint::run(&commands::lint::LintConfig {
+            slug,
+            verbose: cli.verbose,
+            project,
         }),
-        Commands::Implement {
+        Commands::Split {
             slug,
+            new_slug,
+            requirements,
             dry_run,
-            once,
-            max_iterations,
-        } => commands::implement::run(&commands::implement::ImplementConfig {
+        } => commands::split::run(&commands::split::SplitConfig {
             slug,
+            new_slug,
+            requirements,
             dry_run,
             verbose: cli.verbose,
-            loop_enabled: !once,
-            max_iterations,
+            project,
+        }),
+        Commands::Log {
+            slug,
+            requirement,
+            since,
+            status,
+            limit,
+            format,
+        } => commands::log::run(&commands::log::LogConfig {
+            slug,
+            requirement,
+            since,
+            status,
+            limit,
+            format,
+            verbose: cli.verbose,
+            project,
+        }),
+        Commands::Note {
+            slug,
+            requirement,
+            message,
+        } => commands::note::run(&commands::note::NoteConfig {
+            slug,
+            requirement,
+            message,
+            verbose: cli.verbose,
+            project,
+        }),
+        Commands::Annotate {
+            slug,
+            iteration,
+            requirement,
+            message,
+        } => commands::annotate::run(&commands::annotate::AnnotateConfig {
+            slug,
+            iteration,
+            requirement,
+            message,
+            verbose: cli.verbose,
+            project,
+        }),
+        Commands::VerifyLedger { slug } => {
+            commands::verify_ledger::run(&commands::verify_ledger::VerifyLedgerConfig {
+                slug,
+                verbose: cli.verbose,
+                project,
+            })
+        }
+        Commands::Ledger { action } => match action {
+            LedgerAction::Merge {
+                slug,
+                other,
+                git_ref,
+                dry_run,
+            } => commands::ledger::merge(&commands::ledger::MergeConfig {
+                slug,
+                other,
+                git_ref,
+                dry_run,
+                verbose: cli.verbose,
+                project,
+            }),
+        },
+        Commands::Export {
+            target:
+                Some(ExportTarget::Prd {
+                    slug,
+                    format,
+                    output,
+                }),
+            ..
+        } => commands::export::run_prd(&commands::export::PrdExportConfig {
+            slug,
+            format,
+            output,
+            verbose: cli.verbose,
+            project,
         }),
-        Commands::Status { slug } => commands::status::run(&commands::status::StatusConfig {
+        Commands::Export {
+            target: None,
             slug,
+            format,
+            output,
+            endpoint,
+        } => commands::export::run(&commands::export::ExportConfig {
+            slug: slug.unwrap_or_default(),
+            format,
+            output,
+            endpoint,
             verbose: cli.verbose,
+            project,
         }),
         Commands::Hook { hook_type } => match hook_type {
             HookType::CommitMsg { file } => {
                 commands::hook::commit_msg(&commands::hook::CommitMsgConfig {
                     file,
                     verbose: cli.verbose,
+                    project,
                 })
             }
         },
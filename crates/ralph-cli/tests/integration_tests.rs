@@ -59,6 +59,7 @@ fn test_init_creates_structure() {
         .exists());
     assert!(temp.path().join(".githooks/commit-msg").exists());
     assert!(temp.path().join("ralph/validation.json").exists());
+    assert!(temp.path().join("ralph/ledger.jsonl").exists());
 }
 
 #[test]
@@ -78,6 +79,29 @@ fn test_init_dry_run() {
     assert!(!temp.path().join(".github/agents").exists());
 }
 
+#[test]
+fn test_init_list_profiles() {
+    let temp = TempDir::new().unwrap();
+    fs::create_dir_all(temp.path().join("ralph")).unwrap();
+    fs::write(
+        temp.path().join("ralph/validation.json"),
+        r#"{"schemaVersion":"1.0","profiles":{"go":{"extends":"go","commands":{"test":["go test -race ./..."]}}}}"#,
+    )
+    .unwrap();
+
+    let output = ralph_binary()
+        .args(["init", "--list-profiles"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("python-uv"));
+    assert!(stdout.contains("(overridden in ralph/validation.json)"));
+    assert!(!temp.path().join("ralph/tasks").exists());
+}
+
 #[test]
 fn test_status_no_features() {
     let temp = TempDir::new().unwrap();
@@ -143,6 +167,362 @@ fn test_status_with_feature() {
     assert!(stdout.contains("Test Feature"));
 }
 
+#[test]
+fn test_lint_reports_warnings() {
+    let temp = TempDir::new().unwrap();
+    let task_dir = temp.path().join("ralph/tasks/lint-feature");
+    fs::create_dir_all(&task_dir).unwrap();
+
+    let prd = r#"{
+        "schemaVersion": "1.0",
+        "slug": "lint-feature",
+        "title": "Lint Feature",
+        "activeRunId": "lint-20260119",
+        "validationProfiles": ["rust-cargo"],
+        "requirements": [
+            {"id": "REQ-01", "title": "Done early", "status": "done", "acceptanceCriteria": ["A"]},
+            {"id": "REQ-03", "title": "Gap", "status": "todo", "acceptanceCriteria": []}
+        ]
+    }"#;
+    fs::write(task_dir.join("prd.json"), prd).unwrap();
+
+    let output = ralph_binary()
+        .args(["lint", "lint-feature"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("REQ-01: marked done but has no ledger events"));
+    assert!(stdout.contains("REQ-03: no acceptance criteria"));
+    assert!(stdout.contains("REQ-03: non-sequential ID (expected REQ-02)"));
+}
+
+#[test]
+fn test_split_moves_requirements() {
+    let temp = TempDir::new().unwrap();
+    let task_dir = temp.path().join("ralph/tasks/big-feature");
+    fs::create_dir_all(&task_dir).unwrap();
+
+    let prd = r#"{
+        "schemaVersion": "1.0",
+        "slug": "big-feature",
+        "title": "Big Feature",
+        "activeRunId": "big-20260119",
+        "validationProfiles": ["rust-cargo"],
+        "requirements": [
+            {"id": "REQ-01", "title": "Keep", "status": "todo", "acceptanceCriteria": ["A"]},
+            {"id": "REQ-02", "title": "Move", "status": "todo", "acceptanceCriteria": ["B"]}
+        ]
+    }"#;
+    fs::write(task_dir.join("prd.json"), prd).unwrap();
+    fs::write(temp.path().join("ralph/ledger.jsonl"), "").unwrap();
+
+    let output = ralph_binary()
+        .args(["split", "big-feature", "small-feature", "--req", "REQ-02"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let old = fs::read_to_string(task_dir.join("prd.json")).unwrap();
+    assert!(old.contains("REQ-01"));
+    assert!(!old.contains("REQ-02"));
+
+    let new_dir = temp.path().join("ralph/tasks/small-feature");
+    let new = fs::read_to_string(new_dir.join("prd.json")).unwrap();
+    assert!(new.contains("REQ-02"));
+    assert!(fs::read_to_string(task_dir.join("ledger.jsonl"))
+        .unwrap()
+        .contains("small-feature"));
+    assert!(fs::read_to_string(new_dir.join("ledger.jsonl"))
+        .unwrap()
+        .contains("big-feature"));
+    assert!(temp.path().join("docs/ralph/small-feature/prd.md").exists());
+
+    let workspace = fs::read_to_string(temp.path().join("ralph/ledger.jsonl")).unwrap();
+    assert!(workspace.contains(r#""feature":"small-feature","type":"feature_split""#));
+    let output = ralph_binary()
+        .arg("status")
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("small-feature: split from 'big-feature'")
+    );
+}
+
+#[test]
+fn test_note_records_human_intervention() {
+    let temp = TempDir::new().unwrap();
+    let task_dir = temp.path().join("ralph/tasks/note-feature");
+    fs::create_dir_all(&task_dir).unwrap();
+    let prd = r#"{
+        "schemaVersion": "1.0",
+        "slug": "note-feature",
+        "title": "Note Feature",
+        "activeRunId": "note-20260119",
+        "validationProfiles": ["rust-cargo"],
+        "requirements": [
+            {"id": "REQ-02", "title": "Migrate", "status": "todo", "acceptanceCriteria": ["A"]}
+        ]
+    }"#;
+    fs::write(task_dir.join("prd.json"), prd).unwrap();
+
+    let note = |req: &str| {
+        ralph_binary()
+            .args([
+                "note",
+                "note-feature",
+                "--req",
+                req,
+                "manually fixed the migration",
+            ])
+            .current_dir(temp.path())
+            .output()
+            .unwrap()
+    };
+    assert!(note("REQ-02").status.success());
+    assert!(!note("REQ-09").status.success());
+
+    let ledger = fs::read_to_string(task_dir.join("ledger.jsonl")).unwrap();
+    assert_eq!(ledger.lines().count(), 2);
+    assert!(ledger.starts_with(r#"{"ledgerVersion":1}"#));
+    assert!(ledger.contains(r#""kind":"note""#));
+    assert!(ledger.contains("human_intervention"));
+    assert!(ledger.contains("manually fixed the migration"));
+}
+
+#[test]
+fn test_log_filters_events() {
+    let temp = TempDir::new().unwrap();
+    let task_dir = temp.path().join("ralph/tasks/log-feature");
+    fs::create_dir_all(&task_dir).unwrap();
+    fs::write(
+        task_dir.join("ledger.jsonl"),
+        r#"{"timestamp":"2026-01-19T10:00:00Z","iteration":1,"requirement":"REQ-01","status":"failed"}
+{"timestamp":"2026-01-19T11:00:00Z","iteration":2,"requirement":"REQ-01","status":"done"}
+{"timestamp":"2026-01-20T09:00:00Z","iteration":3,"requirement":"REQ-02","status":"failed"}
+"#,
+    )
+    .unwrap();
+
+    let log = |args: &[&str]| {
+        let output = ralph_binary()
+            .args(["log", "log-feature"])
+            .args(args)
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let failed = log(&["--status", "failed", "--format", "jsonl"]);
+    assert_eq!(failed.lines().count(), 2);
+    assert!(failed
+        .lines()
+        .all(|line| line.contains(r#""status":"failed""#)));
+
+    let recent = log(&["--req", "REQ-01", "--limit", "1"]);
+    assert!(recent.contains("2 REQ-01 Done"));
+    assert!(!recent.contains("1 REQ-01"));
+
+    let since = log(&["--since", "2026-01-20", "--format", "json"]);
+    let events: serde_json::Value = serde_json::from_str(&since).unwrap();
+    assert_eq!(events.as_array().unwrap().len(), 1);
+    assert_eq!(events[0]["requirement"], "REQ-02");
+}
+
+#[test]
+fn test_annotate_links_past_iteration() {
+    let temp = TempDir::new().unwrap();
+    let task_dir = temp.path().join("ralph/tasks/annotate-feature");
+    fs::create_dir_all(&task_dir).unwrap();
+    let original = r#"{"timestamp":"2026-01-19T10:00:00Z","iteration":7,"requirement":"REQ-02","status":"failed"}
+{"timestamp":"2026-01-19T11:00:00Z","iteration":8,"requirement":"REQ-02","status":"done"}
+"#;
+    fs::write(task_dir.join("ledger.jsonl"), original).unwrap();
+
+    let annotate = |iteration: &str| {
+        ralph_binary()
+            .args(["annotate", "annotate-feature", "--iteration", iteration])
+            .arg("this failure was a flaky test")
+            .current_dir(temp.path())
+            .output()
+            .unwrap()
+    };
+    assert!(annotate("7").status.success());
+    assert!(!annotate("42").status.success());
+
+    // Past events are untouched; the annotation is a new event linked by iteration
+    let ledger = fs::read_to_string(task_dir.join("ledger.jsonl")).unwrap();
+    assert!(ledger.starts_with(original));
+    let annotation = ledger.lines().last().unwrap();
+    assert!(annotation.contains(r#""iteration":8"#));
+    assert!(annotation.contains(r#""kind":"annotation""#));
+    assert!(annotation.contains(r#""type":"annotation","iteration":7"#));
+}
+
+#[test]
+fn test_export_ledger_csv() {
+    let temp = TempDir::new().unwrap();
+    let task_dir = temp.path().join("ralph/tasks/csv-feature");
+    fs::create_dir_all(&task_dir).unwrap();
+    fs::write(
+        task_dir.join("ledger.jsonl"),
+        r#"{"timestamp":"2026-01-19T10:00:00Z","iteration":1,"requirement":"REQ-01","status":"done","validationPassed":true,"message":"ok, done"}
+"#,
+    )
+    .unwrap();
+
+    let output = ralph_binary()
+        .args(["export", "csv-feature", "--format", "csv"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    assert!(lines.next().unwrap().starts_with("timestamp,"));
+    assert_eq!(
+        lines.next().unwrap(),
+        "2026-01-19T10:00:00+00:00,1,REQ-01,done,true,\"ok, done\",,,,,"
+    );
+}
+
+#[test]
+fn test_export_ledger_otlp() {
+    let temp = TempDir::new().unwrap();
+    let task_dir = temp.path().join("ralph/tasks/otlp-feature");
+    fs::create_dir_all(&task_dir).unwrap();
+    fs::write(
+        task_dir.join("ledger.jsonl"),
+        r#"{"timestamp":"2026-01-19T10:00:00Z","iteration":1,"requirement":"REQ-01","status":"started"}
+{"timestamp":"2026-01-19T10:05:00Z","iteration":1,"requirement":"REQ-01","status":"done"}
+"#,
+    )
+    .unwrap();
+
+    let output = ralph_binary()
+        .args(["export", "otlp-feature", "--format", "otlp"])
+        .env_remove("OTEL_EXPORTER_OTLP_ENDPOINT")
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let traces: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let spans = traces["resourceSpans"][0]["scopeSpans"][0]["spans"]
+        .as_array()
+        .unwrap();
+    let names: Vec<&str> = spans.iter().map(|s| s["name"].as_str().unwrap()).collect();
+    assert_eq!(
+        names,
+        ["ralph run otlp-feature", "REQ-01", "REQ-01 iteration 1"]
+    );
+}
+
+#[test]
+fn test_verify_ledger_detects_tampering() {
+    use ralph_lib::{EventStatus, Ledger, LedgerEvent};
+
+    let temp = TempDir::new().unwrap();
+    let ledger_path = temp.path().join("ralph/tasks/chain-feature/ledger.jsonl");
+    let mut ledger = Ledger::create(&ledger_path).unwrap();
+    ledger.enable_hash_chain();
+    for (iteration, status) in [(1, EventStatus::Failed), (2, EventStatus::Done)] {
+        ledger
+            .append(LedgerEvent::new(iteration, "REQ-01", status))
+            .unwrap();
+    }
+
+    let verify = || {
+        ralph_binary()
+            .args(["verify-ledger", "chain-feature"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap()
+    };
+    let output = verify();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("chain intact"));
+
+    let content = fs::read_to_string(&ledger_path).unwrap();
+    fs::write(&ledger_path, content.replacen("failed", "done", 1)).unwrap();
+    let output = verify();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("iteration 2"));
+}
+
+#[test]
+fn test_ledger_merge() {
+    let temp = TempDir::new().unwrap();
+    let task_dir = temp.path().join("ralph/tasks/merge-feature");
+    fs::create_dir_all(&task_dir).unwrap();
+    fs::write(
+        task_dir.join("ledger.jsonl"),
+        r#"{"timestamp":"2026-01-19T10:00:00Z","iteration":1,"requirement":"REQ-01","status":"done"}
+{"timestamp":"2026-01-19T12:00:00Z","iteration":2,"requirement":"REQ-02","status":"done"}
+"#,
+    )
+    .unwrap();
+    let other = temp.path().join("other.jsonl");
+    fs::write(
+        &other,
+        r#"{"timestamp":"2026-01-19T10:00:00Z","iteration":1,"requirement":"REQ-01","status":"done"}
+{"timestamp":"2026-01-19T11:00:00Z","iteration":2,"requirement":"REQ-03","status":"failed"}
+"#,
+    )
+    .unwrap();
+
+    let output = ralph_binary()
+        .args(["ledger", "merge", "merge-feature"])
+        .arg(&other)
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Added 1 event(s)"));
+    let merged = fs::read_to_string(task_dir.join("ledger.jsonl")).unwrap();
+    // Rewritten ledgers gain the format version header
+    assert!(merged.starts_with(r#"{"ledgerVersion":1}"#));
+    let reqs: Vec<&str> = merged
+        .lines()
+        .skip(1)
+        .map(|line| &line[line.find("REQ-").unwrap()..][..6])
+        .collect();
+    assert_eq!(reqs, vec!["REQ-01", "REQ-03", "REQ-02"]);
+}
+
+#[test]
+fn test_export_prd_html() {
+    let temp = TempDir::new().unwrap();
+    let task_dir = temp.path().join("ralph/tasks/html-feature");
+    fs::create_dir_all(&task_dir).unwrap();
+    fs::write(
+        task_dir.join("prd.json"),
+        r#"{"schemaVersion":"1.0","slug":"html-feature","title":"HTML Feature","activeRunId":"html-1","validationProfiles":[],"requirements":[
+            {"id":"REQ-01","title":"Render","status":"done","acceptanceCriteria":["Shows a table"]}
+        ]}"#,
+    )
+    .unwrap();
+
+    let output = ralph_binary()
+        .args(["export", "prd", "html-feature", "--format", "html"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("<h1>HTML Feature</h1>"));
+    assert!(stdout.contains("<li>Shows a table</li>"));
+}
+
 #[test]
 fn test_hook_commit_msg_valid() {
     let temp = TempDir::new().unwrap();
@@ -158,6 +538,8 @@ fn test_hook_commit_msg_valid() {
         .unwrap();
 
     assert!(output.status.success());
+    let stamped = fs::read_to_string(&msg_file).unwrap();
+    assert!(stamped.ends_with("REQ-01: Add feature\n\nRalph-Hook: verified\n"));
 }
 
 #[test]
@@ -1,7 +1,7 @@
 // ABOUTME: Criterion benchmarks for Ralph core library
 // ABOUTME: Measures performance of PRD parsing, ledger operations, and validation
 
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use ralph_lib::ledger::{EventStatus, Ledger, LedgerEvent};
 use ralph_lib::prd::{Prd, Requirement, RequirementStatus};
 
@@ -21,8 +21,15 @@ fn sample_prd() -> Prd {
                     format!("Given X{i}, when Y{i}, then Z{i}"),
                     format!("Given A{i}, when B{i}, then C{i}"),
                 ],
+                risk: None,
+                prompt_hints: None,
+                paths: Vec::new(),
+                priority: None,
+                extra: serde_json::Map::new(),
             })
             .collect(),
+        skip: Vec::new(),
+        extra: serde_json::Map::new(),
     }
 }
 
@@ -87,11 +94,32 @@ fn bench_ledger_query(c: &mut Criterion) {
     });
 }
 
+fn bench_ledger_query_scaling(c: &mut Criterion) {
+    // Indexed lookups should stay flat as the ledger grows
+    let mut group = c.benchmark_group("ledger_requirement_lookup");
+    for size in [1_000u32, 10_000, 100_000] {
+        let mut ledger = Ledger::new();
+        for i in 1..=size {
+            let req = format!("REQ-{:02}", (i % 50) + 1);
+            ledger
+                .append(LedgerEvent::new(i, &req, EventStatus::Started))
+                .unwrap();
+        }
+        group.bench_with_input(
+            BenchmarkId::new("is_requirement_failed", size),
+            &ledger,
+            |b, ledger| b.iter(|| black_box(ledger.is_requirement_failed("REQ-05"))),
+        );
+    }
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_prd_json_roundtrip,
     bench_prd_markdown,
     bench_ledger_append,
-    bench_ledger_query
+    bench_ledger_query,
+    bench_ledger_query_scaling
 );
 criterion_main!(benches);
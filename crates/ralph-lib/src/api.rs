@@ -0,0 +1,243 @@
+// ABOUTME: Public API surface extraction from rustdoc JSON output
+// ABOUTME: Diffs the surface before and after a run so reviewers can check it against the PRD
+
+use crate::{RalphError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Public items of one or more crates, as "kind path" entries (e.g., "function ralph_lib::prd_path")
+///
+/// Only item paths are tracked; signature changes of an existing item are not detected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiSurface {
+    items: BTreeSet<String>,
+}
+
+impl ApiSurface {
+    /// Extract the public items of the crate documented by a rustdoc JSON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid or lacks the `index` and `paths` tables.
+    pub fn from_rustdoc_json(json: &str) -> Result<Self> {
+        let doc: Value = serde_json::from_str(json)?;
+        let (Some(index), Some(paths)) = (
+            doc.get("index").and_then(Value::as_object),
+            doc.get("paths").and_then(Value::as_object),
+        ) else {
+            return Err(RalphError::Command(
+                "rustdoc JSON is missing 'index' or 'paths'".to_string(),
+            ));
+        };
+
+        let is_public = |id: &str| {
+            index.get(id).is_some_and(|item| {
+                item.get("visibility").and_then(Value::as_str) == Some("public")
+            })
+        };
+        let local_path = |id: &str| {
+            paths
+                .get(id)
+                .filter(|summary| summary.get("crate_id").and_then(Value::as_u64) == Some(0))
+                .map(|summary| {
+                    let kind = summary
+                        .get("kind")
+                        .and_then(Value::as_str)
+                        .unwrap_or("item");
+                    let path: Vec<&str> = summary
+                        .get("path")
+                        .and_then(Value::as_array)
+                        .map(|segments| segments.iter().filter_map(Value::as_str).collect())
+                        .unwrap_or_default();
+                    (kind, path.join("::"))
+                })
+        };
+
+        let mut items = BTreeSet::new();
+        for id in paths.keys() {
+            if let (true, Some((kind, path))) = (is_public(id), local_path(id)) {
+                items.insert(format!("{kind} {path}"));
+            }
+        }
+
+        // Methods and associated items of inherent impls have no entry in `paths`
+        for item in index.values() {
+            let Some(imp) = item.get("inner").and_then(|inner| inner.get("impl")) else {
+                continue;
+            };
+            if imp.get("trait").is_some_and(|t| !t.is_null()) {
+                continue;
+            }
+            let owner = imp
+                .get("for")
+                .and_then(|ty| ty.get("resolved_path"))
+                .and_then(|ty| ty.get("id"))
+                .map(id_key);
+            let Some((_, owner_path)) = owner.as_deref().and_then(&local_path) else {
+                continue;
+            };
+            for member in imp
+                .get("items")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let member = id_key(member);
+                let Some(member_item) = index.get(&member).filter(|_| is_public(&member)) else {
+                    continue;
+                };
+                let name = member_item
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or("_");
+                let kind = member_item
+                    .get("inner")
+                    .and_then(Value::as_object)
+                    .and_then(|inner| inner.keys().next())
+                    .map_or("item", String::as_str);
+                items.insert(format!("{kind} {owner_path}::{name}"));
+            }
+        }
+
+        Ok(Self { items })
+    }
+
+    /// Extract and combine the surfaces of every rustdoc JSON file in a directory
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or a JSON file cannot be read or parsed.
+    pub fn from_rustdoc_dir(dir: impl AsRef<Path>) -> Result<Self> {
+        let mut surface = Self::default();
+        for entry in std::fs::read_dir(dir.as_ref())?.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let json = std::fs::read_to_string(&path)?;
+                surface.items.extend(Self::from_rustdoc_json(&json)?.items);
+            }
+        }
+        Ok(surface)
+    }
+
+    /// Public items, sorted
+    pub fn items(&self) -> impl Iterator<Item = &str> {
+        self.items.iter().map(String::as_str)
+    }
+
+    /// Items added and removed going from this surface to `after`
+    #[must_use]
+    pub fn diff(&self, after: &Self) -> ApiDiff {
+        ApiDiff {
+            added: after.items.difference(&self.items).cloned().collect(),
+            removed: self.items.difference(&after.items).cloned().collect(),
+        }
+    }
+}
+
+/// Public items added and removed by a run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDiff {
+    /// Items present only after the run
+    pub added: Vec<String>,
+    /// Items present only before the run
+    pub removed: Vec<String>,
+}
+
+impl ApiDiff {
+    /// Whether the public surface is unchanged
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Render the diff as a markdown document for reviewers
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut doc = String::from("# Public API diff\n");
+        if self.is_empty() {
+            doc.push_str("\nNo public API changes.\n");
+            return doc;
+        }
+        for (heading, items) in [("Added", &self.added), ("Removed", &self.removed)] {
+            if items.is_empty() {
+                continue;
+            }
+            let _ = writeln!(doc, "\n## {heading} ({})\n", items.len());
+            for item in items {
+                let _ = writeln!(doc, "- `{item}`");
+            }
+        }
+        doc
+    }
+}
+
+/// Normalize an item ID, which rustdoc emits as a string or an integer depending on format version
+fn id_key(id: &Value) -> String {
+    match id {
+        Value::String(id) => id.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rustdoc_json(extra_method: bool) -> String {
+        let methods = if extra_method { "[3, 4]" } else { "[3]" };
+        format!(
+            r#"{{
+                "index": {{
+                    "1": {{"name": "Ledger", "visibility": "public", "inner": {{"struct": {{}}}}}},
+                    "2": {{"name": null, "visibility": "default", "inner": {{"impl": {{"trait": null, "for": {{"resolved_path": {{"name": "Ledger", "id": 1}}}}, "items": {methods}}}}}}},
+                    "3": {{"name": "new", "visibility": "public", "inner": {{"function": {{}}}}}},
+                    "4": {{"name": "merge", "visibility": "public", "inner": {{"function": {{}}}}}},
+                    "5": {{"name": "helper", "visibility": "crate", "inner": {{"function": {{}}}}}}
+                }},
+                "paths": {{
+                    "1": {{"crate_id": 0, "path": ["ralph_lib", "ledger", "Ledger"], "kind": "struct"}},
+                    "5": {{"crate_id": 0, "path": ["ralph_lib", "helper"], "kind": "function"}},
+                    "9": {{"crate_id": 1, "path": ["std", "string", "String"], "kind": "struct"}}
+                }}
+            }}"#
+        )
+    }
+
+    #[test]
+    fn test_from_rustdoc_json() {
+        let surface = ApiSurface::from_rustdoc_json(&rustdoc_json(false)).unwrap();
+        let items: Vec<&str> = surface.items().collect();
+        assert_eq!(
+            items,
+            vec![
+                "function ralph_lib::ledger::Ledger::new",
+                "struct ralph_lib::ledger::Ledger"
+            ]
+        );
+        assert!(ApiSurface::from_rustdoc_json("{}").is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = ApiSurface::from_rustdoc_json(&rustdoc_json(false)).unwrap();
+        let after = ApiSurface::from_rustdoc_json(&rustdoc_json(true)).unwrap();
+
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff.added,
+            vec!["function ralph_lib::ledger::Ledger::merge"]
+        );
+        assert!(diff.removed.is_empty());
+        assert!(diff
+            .to_markdown()
+            .contains("- `function ralph_lib::ledger::Ledger::merge`"));
+
+        let diff = after.diff(&after);
+        assert!(diff.is_empty());
+        assert!(diff.to_markdown().contains("No public API changes"));
+    }
+}
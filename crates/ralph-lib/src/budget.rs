@@ -0,0 +1,264 @@
+// ABOUTME: Wall-clock and cost budgets that stop 'ralph implement' between iterations
+// ABOUTME: Parses limits like "2h" and "$5" and measures a run's time and spend from the ledger
+
+use crate::{Ledger, RalphError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A length of time written as `2h`, `90m`, `1h30m`, `45s`, or plain seconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HumanDuration(pub Duration);
+
+impl FromStr for HumanDuration {
+    type Err = RalphError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let invalid = || {
+            RalphError::Config(format!(
+                "invalid duration '{text}' (expected e.g. 2h, 90m, 1h30m, or 45s)"
+            ))
+        };
+        let trimmed = text.trim();
+        if let Ok(secs) = trimmed.parse::<u64>() {
+            return Ok(Self(Duration::from_secs(secs)));
+        }
+
+        let mut secs = 0u64;
+        let mut digits = String::new();
+        for c in trimmed.chars() {
+            if c.is_ascii_digit() {
+                digits.push(c);
+                continue;
+            }
+            let unit = match c {
+                'd' => 86_400,
+                'h' => 3_600,
+                'm' => 60,
+                's' => 1,
+                _ => return Err(invalid()),
+            };
+            let count: u64 = digits.parse().map_err(|_| invalid())?;
+            secs = secs.saturating_add(count.saturating_mul(unit));
+            digits.clear();
+        }
+        if trimmed.is_empty() || !digits.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self(Duration::from_secs(secs)))
+    }
+}
+
+impl fmt::Display for HumanDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_duration(self.0))
+    }
+}
+
+impl Serialize for HumanDuration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for HumanDuration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match TextOrNumber::deserialize(deserializer)? {
+            TextOrNumber::Text(text) => text.parse().map_err(serde::de::Error::custom),
+            TextOrNumber::Number(secs) => Duration::try_from_secs_f64(secs)
+                .map(Self)
+                .map_err(|_| serde::de::Error::custom(format!("invalid duration {secs}"))),
+        }
+    }
+}
+
+/// An amount in USD written as `$5`, `5`, or `2.50`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dollars(pub f64);
+
+impl FromStr for Dollars {
+    type Err = RalphError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let trimmed = text.trim();
+        let amount = trimmed.strip_prefix('$').unwrap_or(trimmed);
+        amount.parse().ok().and_then(Self::checked).ok_or_else(|| {
+            RalphError::Config(format!(
+                "invalid amount '{text}' (expected USD, e.g. $5 or 2.50)"
+            ))
+        })
+    }
+}
+
+impl Dollars {
+    /// `usd` as an amount, unless it is negative or not finite
+    fn checked(usd: f64) -> Option<Self> {
+        (usd.is_finite() && usd >= 0.0).then_some(Self(usd))
+    }
+}
+
+impl fmt::Display for Dollars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "${:.2}", self.0)
+    }
+}
+
+impl Serialize for Dollars {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Dollars {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match TextOrNumber::deserialize(deserializer)? {
+            TextOrNumber::Text(text) => text.parse().map_err(serde::de::Error::custom),
+            TextOrNumber::Number(usd) => Self::checked(usd).ok_or_else(|| {
+                serde::de::Error::custom(format!("amount must not be negative, got {usd}"))
+            }),
+        }
+    }
+}
+
+/// Config values that may be written as a string (`"2h"`, `"$5"`) or a bare number
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TextOrNumber {
+    Number(f64),
+    Text(String),
+}
+
+/// Limits on the wall-clock time and estimated cost of one implementation run
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunBudget {
+    /// Longest the run may go on, measured from its start (resumes included)
+    pub max_duration: Option<Duration>,
+    /// Most the run's agent calls may cost in USD, by the ledger's estimates
+    pub max_cost: Option<f64>,
+}
+
+/// Time and estimated cost a run has used so far
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunSpend {
+    /// Wall-clock time since the run started
+    pub elapsed: Duration,
+    /// Estimated USD cost of the ledger events recorded since the run started
+    pub cost: f64,
+}
+
+impl RunSpend {
+    /// What the run that started at `started_at` has used by `now`
+    #[must_use]
+    pub fn since(ledger: &Ledger, started_at: DateTime<Utc>, now: DateTime<Utc>) -> Self {
+        Self {
+            elapsed: (now - started_at).to_std().unwrap_or_default(),
+            cost: ledger.usage_since(started_at).estimated_cost,
+        }
+    }
+}
+
+impl RunBudget {
+    /// Why the run must stop, if `spend` has reached a limit
+    #[must_use]
+    pub fn exceeded(&self, spend: &RunSpend) -> Option<String> {
+        if let Some(max) = self.max_duration.filter(|&max| spend.elapsed >= max) {
+            return Some(format!(
+                "time budget of {} reached ({} elapsed)",
+                format_duration(max),
+                format_duration(spend.elapsed)
+            ));
+        }
+        if let Some(max) = self.max_cost.filter(|&max| spend.cost >= max) {
+            return Some(format!(
+                "cost budget of {} reached ({} spent)",
+                Dollars(max),
+                Dollars(spend.cost)
+            ));
+        }
+        None
+    }
+}
+
+/// Compact rendering like `1h05m`, `12m30s`, or `45s`
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3_600, secs % 3_600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}h{minutes:02}m")
+    } else if minutes > 0 {
+        format!("{minutes}m{seconds:02}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventStatus, LedgerEvent, TokenUsage};
+
+    #[test]
+    fn test_parse_limits() {
+        let secs = |text: &str| text.parse::<HumanDuration>().unwrap().0.as_secs();
+        assert_eq!(secs("2h"), 7_200);
+        assert_eq!(secs("90m"), 5_400);
+        assert_eq!(secs("1h30m"), 5_400);
+        assert_eq!(secs(" 45s "), 45);
+        assert_eq!(secs("600"), 600);
+        for bad in ["", "2x", "h", "1h30", "-5m"] {
+            assert!(bad.parse::<HumanDuration>().is_err(), "{bad}");
+        }
+
+        assert_eq!("$5".parse::<Dollars>().unwrap(), Dollars(5.0));
+        assert_eq!("2.50".parse::<Dollars>().unwrap(), Dollars(2.5));
+        assert!("five".parse::<Dollars>().is_err());
+        assert!("-1".parse::<Dollars>().is_err());
+        assert_eq!(Dollars(5.0).to_string(), "$5.00");
+    }
+
+    #[test]
+    fn test_budget_exceeded() {
+        let start = Utc::now();
+        let mut ledger = Ledger::new();
+        let mut before =
+            LedgerEvent::new(1, "REQ-01", EventStatus::Failed).with_usage(&TokenUsage {
+                model: Some("claude-opus-4.5".to_string()),
+                prompt_tokens: 1_000_000,
+                completion_tokens: 0,
+            });
+        before.timestamp = start - chrono::Duration::hours(1);
+        ledger.append(before).unwrap();
+        let mut during = LedgerEvent::new(2, "REQ-01", EventStatus::Done).with_usage(&TokenUsage {
+            model: Some("claude-haiku-4.5".to_string()),
+            prompt_tokens: 2_000_000,
+            completion_tokens: 0,
+        });
+        during.timestamp = start + chrono::Duration::minutes(5);
+        ledger.append(during).unwrap();
+
+        let spend = RunSpend::since(&ledger, start, start + chrono::Duration::minutes(90));
+        assert_eq!(spend.elapsed, Duration::from_secs(5_400));
+        assert!((spend.cost - 2.0).abs() < 1e-9);
+
+        assert_eq!(RunBudget::default().exceeded(&spend), None);
+        let budget = RunBudget {
+            max_duration: Some(Duration::from_secs(7_200)),
+            max_cost: Some(2.0),
+        };
+        assert_eq!(
+            budget.exceeded(&spend).as_deref(),
+            Some("cost budget of $2.00 reached ($2.00 spent)")
+        );
+        let budget = RunBudget {
+            max_duration: Some(Duration::from_secs(3_600)),
+            max_cost: Some(5.0),
+        };
+        assert_eq!(
+            budget.exceeded(&spend).as_deref(),
+            Some("time budget of 1h00m reached (1h30m elapsed)")
+        );
+    }
+}
@@ -0,0 +1,181 @@
+// ABOUTME: Run checkpoints that let 'ralph implement --resume' continue an interrupted run
+// ABOUTME: Records the branch, iterations used, and the in-flight iteration's requirement, phase, and snapshot
+
+use crate::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Checkpoint file name inside a feature's task directory
+pub const CHECKPOINT_FILE: &str = "checkpoint.json";
+
+/// Loop state of a run, rewritten at every step so a killed run can pick up where it stopped
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Checkpoint {
+    /// Where the checkpoint is stored
+    #[serde(skip)]
+    path: PathBuf,
+    /// Run the checkpoint belongs to; a PRD with a different active run can't resume it
+    pub run_id: String,
+    /// Branch the run works on
+    pub branch: String,
+    /// When the run started, the reference for its time and cost budgets on resume
+    #[serde(default = "Utc::now")]
+    pub started_at: DateTime<Utc>,
+    /// Loop iterations used so far, counted against the iteration limit on resume
+    pub iterations_used: u32,
+    /// Iteration that was under way, if the run stopped in the middle of one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current: Option<InFlight>,
+    /// When the checkpoint was last written
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An iteration that had started but not been recorded as finished
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InFlight {
+    /// Requirement being implemented
+    pub requirement: String,
+    /// Ledger iteration number (its start event is already in the ledger)
+    pub iteration: u32,
+    /// Whether this iteration runs the full test sweep
+    pub run_full_tests: bool,
+    /// HEAD before the agent ran, for scoping validation to the iteration's changes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_sha: Option<String>,
+    /// Tree state before the agent ran, when failed iterations are rolled back
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<Snapshot>,
+    /// Step the iteration had reached
+    pub phase: Phase,
+}
+
+/// Working tree state captured before the agent runs, for rolling a failed iteration back
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    /// HEAD when the snapshot was taken
+    pub head: String,
+    /// `git stash create` commit holding uncommitted changes to tracked files, if there were any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stash: Option<String>,
+    /// Untracked files that already existed, which a rollback leaves alone
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub untracked: Vec<String>,
+}
+
+impl Snapshot {
+    /// Commit whose tree holds the snapshotted tracked files
+    #[must_use]
+    pub fn source(&self) -> &str {
+        self.stash.as_deref().unwrap_or(&self.head)
+    }
+}
+
+/// Steps of an iteration that a resume can restart from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    /// The agent was running; resuming relaunches it on the same requirement
+    Agent,
+    /// The agent finished; resuming goes straight to validation
+    Validation {
+        /// Whether the agent reported success
+        agent_succeeded: bool,
+    },
+}
+
+impl Checkpoint {
+    /// Fresh checkpoint for a run in the task directory `task_dir`
+    #[must_use]
+    pub fn new(task_dir: impl AsRef<Path>, run_id: &str, branch: &str) -> Self {
+        Self {
+            path: task_dir.as_ref().join(CHECKPOINT_FILE),
+            run_id: run_id.to_string(),
+            branch: branch.to_string(),
+            started_at: Utc::now(),
+            iterations_used: 0,
+            current: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Load the checkpoint left in `task_dir`, if any
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(task_dir: impl AsRef<Path>) -> Result<Option<Self>> {
+        let path = task_dir.as_ref().join(CHECKPOINT_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let mut checkpoint: Self = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+        checkpoint.path = path;
+        Ok(Some(checkpoint))
+    }
+
+    /// Write the checkpoint, replacing the previous one atomically
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&mut self) -> Result<()> {
+        self.updated_at = Utc::now();
+        let temp = self.path.with_extension("json.tmp");
+        std::fs::write(&temp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&temp, &self.path)?;
+        Ok(())
+    }
+
+    /// Delete the checkpoint once the run has ended normally
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be removed.
+    pub fn remove(&self) -> Result<()> {
+        match std::fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let dir = tempdir().unwrap();
+        assert_eq!(Checkpoint::load(dir.path()).unwrap(), None);
+
+        let mut checkpoint = Checkpoint::new(dir.path(), "run-1", "ralph/auth/run-1");
+        checkpoint.iterations_used = 3;
+        checkpoint.current = Some(InFlight {
+            requirement: "REQ-02".to_string(),
+            iteration: 7,
+            run_full_tests: false,
+            base_sha: Some("abc123".to_string()),
+            snapshot: Some(Snapshot {
+                head: "abc123".to_string(),
+                stash: None,
+                untracked: vec!["notes.txt".to_string()],
+            }),
+            phase: Phase::Validation {
+                agent_succeeded: true,
+            },
+        });
+        checkpoint.save().unwrap();
+
+        let loaded = Checkpoint::load(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded, checkpoint);
+
+        loaded.remove().unwrap();
+        assert_eq!(Checkpoint::load(dir.path()).unwrap(), None);
+        loaded.remove().unwrap();
+    }
+}
@@ -0,0 +1,849 @@
+// ABOUTME: Layered Ralph configuration: user config.toml, repository ralph.toml, then RALPH_* env vars
+// ABOUTME: Covers models, iteration and budget limits, branch naming, failure summarization, hook and project paths; CLI flags override all
+
+use crate::budget::{Dollars, HumanDuration, RunBudget};
+use crate::ledger::workspace::WORKSPACE_LEDGER;
+use crate::notify::NOTIFY_CONFIG_FILE;
+use crate::selection::Selection;
+use crate::{RalphError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Project configuration file, relative to the repository root
+pub const CONFIG_FILE: &str = "ralph.toml";
+
+/// User configuration file, relative to the user config directory (e.g., `~/.config`)
+pub const USER_CONFIG_FILE: &str = "ralph/config.toml";
+
+/// Model the implementer agent runs on unless configured
+pub const DEFAULT_IMPLEMENTER_MODEL: &str = "claude-haiku-4.5";
+
+/// Model planning sessions run on unless configured
+pub const DEFAULT_PLANNER_MODEL: &str = "claude-opus-4.5";
+
+/// Model that summarizes validation failures and compacts the scratchpad unless configured
+pub const DEFAULT_SUMMARIZER_MODEL: &str = "gpt-5-mini";
+
+/// Iterations `ralph implement` runs before stopping unless configured
+pub const DEFAULT_MAX_ITERATIONS: u32 = 10;
+
+/// Requirements `ralph implement` works on at once unless configured (1 is sequential)
+pub const DEFAULT_PARALLEL: usize = 1;
+
+/// Branch each implementation run works on; `{slug}` and `{run_id}` are substituted
+pub const DEFAULT_BRANCH_TEMPLATE: &str = "ralph/{slug}/{run_id}";
+
+/// Directory holding one subdirectory of PRD, ledger, and scratchpad per feature
+pub const DEFAULT_TASKS_DIR: &str = "ralph/tasks";
+
+/// Directory holding the rendered Markdown PRD of each feature
+pub const DEFAULT_DOCS_DIR: &str = "docs/ralph";
+
+/// Validation profiles file
+pub const DEFAULT_VALIDATION_FILE: &str = "ralph/validation.json";
+
+/// Remote ledger sync settings file
+pub const DEFAULT_SYNC_FILE: &str = "ralph/sync.json";
+
+/// Directory of prompt templates that replace the built-in agent prompts
+pub const DEFAULT_PROMPTS_DIR: &str = ".ralph/prompts";
+
+/// Default estimated tokens an implementer prompt may use
+pub const DEFAULT_PROMPT_TOKENS: usize = 8_000;
+
+/// Directory `ralph init` installs git hooks into
+pub const DEFAULT_HOOKS_DIR: &str = ".githooks";
+
+/// Resolved settings; unset values fall back to the defaults above
+///
+/// Each layer overrides the one before it: `~/.config/ralph/config.toml`, the repository's
+/// `ralph.toml`, `RALPH_*` environment variables, and finally command-line flags.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Models used for each kind of agent call
+    #[serde(default)]
+    pub models: ModelConfig,
+    /// Implementation loop limits and branch naming
+    #[serde(default)]
+    pub implement: LoopConfig,
+    /// Where Ralph keeps its files, relative to the repository root
+    #[serde(default)]
+    pub paths: PathConfig,
+    /// Git hook installation
+    #[serde(default)]
+    pub hooks: HookConfig,
+    /// How failed validation output is summarized for the ledger
+    #[serde(default)]
+    pub summarizer: SummarizerConfig,
+}
+
+impl Config {
+    /// Resolve the user config, `ralph.toml` in `root`, and the environment, in that order
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a config file exists but cannot be read or is not valid config,
+    /// or an environment variable has an invalid value.
+    pub fn load(root: impl AsRef<Path>) -> Result<Self> {
+        Self::resolve(root.as_ref(), |var| std::env::var(var).ok())
+    }
+
+    /// [`Config::load`] with environment lookups going through `lookup`
+    fn resolve(root: &Path, lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let user = match user_config_path(&lookup) {
+            Some(path) => Self::from_file(&path)?,
+            None => Self::default(),
+        };
+        let project = Self::from_file(&root.join(CONFIG_FILE))?;
+        Ok(Self::from_env(lookup)?.or(project).or(user))
+    }
+
+    /// Load a config file, or the defaults if it doesn't exist
+    fn from_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| RalphError::Toml(format!("{}: {e}", path.display())))
+    }
+
+    /// Parse config from a TOML string
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML is invalid or has unknown keys.
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        toml::from_str(toml).map_err(|e| RalphError::Toml(e.to_string()))
+    }
+
+    /// Settings from `RALPH_*` variables, e.g. `RALPH_MAX_ITERATIONS` or `RALPH_TASKS_DIR`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a numeric variable such as `RALPH_MAX_ITERATIONS` is not a whole
+    /// number, a flag such as `RALPH_AUTO_COMMIT` is not `true` or `false`, `RALPH_SELECTION`
+    /// names no known strategy, or `RALPH_MAX_DURATION` or `RALPH_MAX_COST` is not a valid limit.
+    pub fn from_env(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let max_iterations = env_number(&lookup, "RALPH_MAX_ITERATIONS")?;
+        let parallel = env_number(&lookup, "RALPH_PARALLEL")?;
+        let agent_timeout = env_number(&lookup, "RALPH_AGENT_TIMEOUT")?;
+        let agent_timeout_retries = env_number(&lookup, "RALPH_AGENT_TIMEOUT_RETRIES")?;
+        let selection = lookup("RALPH_SELECTION")
+            .map(|name| name.trim().parse())
+            .transpose()?;
+        let max_duration = lookup("RALPH_MAX_DURATION")
+            .map(|value| value.parse())
+            .transpose()?;
+        let max_cost = lookup("RALPH_MAX_COST")
+            .map(|value| value.parse())
+            .transpose()?;
+        let auto_commit = env_bool(&lookup, "RALPH_AUTO_COMMIT")?;
+        let stuck_after = env_number(&lookup, "RALPH_STUCK_AFTER")?;
+        let rollback = env_bool(&lookup, "RALPH_ROLLBACK")?;
+        let review = env_bool(&lookup, "RALPH_REVIEW")?;
+        let review_auto_accept = env_number(&lookup, "RALPH_REVIEW_AUTO_ACCEPT")?;
+        let draft_pr = env_bool(&lookup, "RALPH_DRAFT_PR")?;
+        let repo_map = env_bool(&lookup, "RALPH_REPO_MAP")?;
+        let prompt_tokens = env_number(&lookup, "RALPH_PROMPT_TOKENS")?;
+        let desktop_notify = env_bool(&lookup, "RALPH_DESKTOP_NOTIFY")?;
+        let agent_sessions = env_bool(&lookup, "RALPH_AGENT_SESSIONS")?;
+        let summarizer_mode = lookup("RALPH_SUMMARIZER")
+            .map(|name| name.trim().parse())
+            .transpose()?;
+        let path = |var| lookup(var).map(PathBuf::from);
+        Ok(Self {
+            models: ModelConfig {
+                implementer: lookup("RALPH_IMPLEMENTER_MODEL"),
+                planner: lookup("RALPH_PLANNER_MODEL"),
+                summarizer: lookup("RALPH_SUMMARIZER_MODEL"),
+            },
+            implement: LoopConfig {
+                max_iterations,
+                parallel,
+                selection,
+                agent_timeout,
+                agent_timeout_retries,
+                max_duration,
+                max_cost,
+                auto_commit,
+                stuck_after,
+                rollback,
+                review,
+                review_auto_accept,
+                draft_pr,
+                repo_map,
+                prompt_tokens,
+                desktop_notify,
+                agent_sessions,
+                branch_template: lookup("RALPH_BRANCH_TEMPLATE"),
+            },
+            paths: PathConfig {
+                tasks: path("RALPH_TASKS_DIR"),
+                docs: path("RALPH_DOCS_DIR"),
+                validation: path("RALPH_VALIDATION_FILE"),
+                prompts: path("RALPH_PROMPTS_DIR"),
+                sync: path("RALPH_SYNC_FILE"),
+                notify: path("RALPH_NOTIFY_FILE"),
+                workspace_ledger: path("RALPH_WORKSPACE_LEDGER"),
+            },
+            hooks: HookConfig {
+                dir: path("RALPH_HOOKS_DIR"),
+            },
+            summarizer: SummarizerConfig {
+                mode: summarizer_mode,
+                command: lookup("RALPH_SUMMARIZER_COMMAND"),
+            },
+        })
+    }
+
+    /// This config with unset values taken from `fallback` (e.g., flags over the files)
+    #[must_use]
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            models: self.models.or(fallback.models),
+            implement: self.implement.or(fallback.implement),
+            paths: self.paths.or(fallback.paths),
+            hooks: self.hooks.or(fallback.hooks),
+            summarizer: self.summarizer.or(fallback.summarizer),
+        }
+    }
+}
+
+/// Whole number in environment variable `var`, if set
+fn env_number<T: FromStr>(lookup: impl Fn(&str) -> Option<String>, var: &str) -> Result<Option<T>> {
+    lookup(var)
+        .map(|value| {
+            value.trim().parse().map_err(|_| {
+                RalphError::Config(format!("{var} must be a whole number, got '{value}'"))
+            })
+        })
+        .transpose()
+}
+
+/// `true`/`false` (or `1`/`0`) in environment variable `var`, if set
+fn env_bool(lookup: impl Fn(&str) -> Option<String>, var: &str) -> Result<Option<bool>> {
+    lookup(var)
+        .map(|value| match value.trim() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(RalphError::Config(format!(
+                "{var} must be true or false, got '{value}'"
+            ))),
+        })
+        .transpose()
+}
+
+/// `$XDG_CONFIG_HOME/ralph/config.toml`, else under `$HOME/.config` (`%APPDATA%` on Windows)
+fn user_config_path(lookup: impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    let non_empty = |var| lookup(var).filter(|value| !value.is_empty());
+    let dir = non_empty("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| Path::new(&home).join(".config")))
+        .or_else(|| non_empty("APPDATA").map(PathBuf::from))?;
+    Some(dir.join(USER_CONFIG_FILE))
+}
+
+/// Models for implementing, planning, and summarizing; unset ones use the defaults
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelConfig {
+    /// Runs each implementation iteration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub implementer: Option<String>,
+    /// Runs planning sessions and clarifying questions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub planner: Option<String>,
+    /// Summarizes validation failures and compacts the scratchpad
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summarizer: Option<String>,
+}
+
+impl ModelConfig {
+    /// Implementer model, defaulting to [`DEFAULT_IMPLEMENTER_MODEL`]
+    #[must_use]
+    pub fn implementer(&self) -> &str {
+        self.implementer
+            .as_deref()
+            .unwrap_or(DEFAULT_IMPLEMENTER_MODEL)
+    }
+
+    /// Planner model, defaulting to [`DEFAULT_PLANNER_MODEL`]
+    #[must_use]
+    pub fn planner(&self) -> &str {
+        self.planner.as_deref().unwrap_or(DEFAULT_PLANNER_MODEL)
+    }
+
+    /// Summarizer model, defaulting to [`DEFAULT_SUMMARIZER_MODEL`]
+    #[must_use]
+    pub fn summarizer(&self) -> &str {
+        self.summarizer
+            .as_deref()
+            .unwrap_or(DEFAULT_SUMMARIZER_MODEL)
+    }
+
+    /// These models with unset ones taken from `fallback` (e.g., flags over the file)
+    #[must_use]
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            implementer: self.implementer.or(fallback.implementer),
+            planner: self.planner.or(fallback.planner),
+            summarizer: self.summarizer.or(fallback.summarizer),
+        }
+    }
+}
+
+/// `[implement]` settings for the implementation loop
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct LoopConfig {
+    /// Iterations to run before stopping
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<u32>,
+    /// Requirements with disjoint paths to implement concurrently in git worktrees
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parallel: Option<usize>,
+    /// Strategy choosing the next requirement
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection: Option<Selection>,
+    /// Seconds an implementer call may run before it is killed (0 or unset: no limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_timeout: Option<u64>,
+    /// Times a timed-out implementer call is retried before the iteration fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_timeout_retries: Option<u32>,
+    /// Wall-clock time a run may take, e.g. `"2h"` (0 or unset: no limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_duration: Option<HumanDuration>,
+    /// Estimated USD a run may spend on agent calls, e.g. `5` (0 or unset: no limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cost: Option<Dollars>,
+    /// Commit each iteration's changes as `REQ-xx: <title>` once validation passes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_commit: Option<bool>,
+    /// Consecutive validation failures after which a requirement is blocked (0 or unset: never)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stuck_after: Option<u32>,
+    /// Roll the working tree back to its pre-agent state when an iteration fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollback: Option<bool>,
+    /// Show each passing iteration's diff and ask before marking it done
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review: Option<bool>,
+    /// Changed lines (added plus removed) a reviewed diff may have and still pass unasked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_auto_accept: Option<u64>,
+    /// Push the branch and open a draft pull request via `gh` when the run ends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft_pr: Option<bool>,
+    /// Include a map of likely-relevant files and their symbols in implementer prompts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_map: Option<bool>,
+    /// Estimated tokens an implementer prompt may use; failure output, the repository
+    /// map, and team notes are trimmed to fit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens: Option<usize>,
+    /// Show a desktop notification when the run ends or a requirement keeps failing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desktop_notify: Option<bool>,
+    /// Resume the agent's session from a requirement's previous attempt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_sessions: Option<bool>,
+    /// Branch name with `{slug}` and `{run_id}` placeholders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_template: Option<String>,
+}
+
+impl LoopConfig {
+    /// Iteration limit, defaulting to [`DEFAULT_MAX_ITERATIONS`]
+    #[must_use]
+    pub fn max_iterations(&self) -> u32 {
+        self.max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS)
+    }
+
+    /// Concurrent requirements, defaulting to [`DEFAULT_PARALLEL`]
+    #[must_use]
+    pub fn parallel(&self) -> usize {
+        self.parallel.unwrap_or(DEFAULT_PARALLEL).max(1)
+    }
+
+    /// Selection strategy, defaulting to [`Selection::Fifo`]
+    #[must_use]
+    pub fn selection(&self) -> Selection {
+        self.selection.unwrap_or_default()
+    }
+
+    /// Longest an implementer call may run, if limited
+    #[must_use]
+    pub fn agent_timeout(&self) -> Option<Duration> {
+        self.agent_timeout
+            .filter(|&secs| secs > 0)
+            .map(Duration::from_secs)
+    }
+
+    /// Retries after an implementer call times out, defaulting to none
+    #[must_use]
+    pub fn agent_timeout_retries(&self) -> u32 {
+        self.agent_timeout_retries.unwrap_or(0)
+    }
+
+    /// Time and cost limits checked between iterations
+    #[must_use]
+    pub fn budget(&self) -> RunBudget {
+        RunBudget {
+            max_duration: self
+                .max_duration
+                .map(|HumanDuration(limit)| limit)
+                .filter(|limit| !limit.is_zero()),
+            max_cost: self
+                .max_cost
+                .map(|Dollars(limit)| limit)
+                .filter(|&limit| limit > 0.0),
+        }
+    }
+
+    /// Whether passing iterations are committed, defaulting to off
+    #[must_use]
+    pub fn auto_commit(&self) -> bool {
+        self.auto_commit.unwrap_or(false)
+    }
+
+    /// Validation failure streak that blocks a requirement; `None` (the default) when disabled
+    #[must_use]
+    pub fn stuck_after(&self) -> Option<u32> {
+        self.stuck_after.filter(|&n| n > 0)
+    }
+
+    /// Whether failed iterations are rolled back, defaulting to off
+    #[must_use]
+    pub fn rollback(&self) -> bool {
+        self.rollback.unwrap_or(false)
+    }
+
+    /// Whether passing diffs are reviewed before acceptance, defaulting to off
+    #[must_use]
+    pub fn review(&self) -> bool {
+        self.review.unwrap_or(false)
+    }
+
+    /// Largest diff, in changed lines, accepted without asking; defaults to 0 (ask for any)
+    #[must_use]
+    pub fn review_auto_accept(&self) -> u64 {
+        self.review_auto_accept.unwrap_or(0)
+    }
+
+    /// Whether a draft pull request is opened when the run ends, defaulting to off
+    #[must_use]
+    pub fn draft_pr(&self) -> bool {
+        self.draft_pr.unwrap_or(false)
+    }
+
+    /// Whether implementer prompts include a repository map, defaulting to on
+    #[must_use]
+    pub fn repo_map(&self) -> bool {
+        self.repo_map.unwrap_or(true)
+    }
+
+    /// Implementer prompt budget in estimated tokens, defaulting to [`DEFAULT_PROMPT_TOKENS`]
+    #[must_use]
+    pub fn prompt_tokens(&self) -> usize {
+        self.prompt_tokens.unwrap_or(DEFAULT_PROMPT_TOKENS)
+    }
+
+    /// Whether desktop notifications are shown, defaulting to off
+    #[must_use]
+    pub fn desktop_notify(&self) -> bool {
+        self.desktop_notify.unwrap_or(false)
+    }
+
+    /// Whether a requirement's retries resume the agent's previous session, defaulting to on
+    #[must_use]
+    pub fn agent_sessions(&self) -> bool {
+        self.agent_sessions.unwrap_or(true)
+    }
+
+    /// Branch for `run_id` of feature `slug`, from the template or [`DEFAULT_BRANCH_TEMPLATE`]
+    #[must_use]
+    pub fn branch_name(&self, slug: &str, run_id: &str) -> String {
+        self.branch_template
+            .as_deref()
+            .unwrap_or(DEFAULT_BRANCH_TEMPLATE)
+            .replace("{slug}", slug)
+            .replace("{run_id}", run_id)
+    }
+
+    /// These settings with unset ones taken from `fallback`
+    #[must_use]
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            max_iterations: self.max_iterations.or(fallback.max_iterations),
+            parallel: self.parallel.or(fallback.parallel),
+            selection: self.selection.or(fallback.selection),
+            agent_timeout: self.agent_timeout.or(fallback.agent_timeout),
+            agent_timeout_retries: self
+                .agent_timeout_retries
+                .or(fallback.agent_timeout_retries),
+            max_duration: self.max_duration.or(fallback.max_duration),
+            max_cost: self.max_cost.or(fallback.max_cost),
+            auto_commit: self.auto_commit.or(fallback.auto_commit),
+            stuck_after: self.stuck_after.or(fallback.stuck_after),
+            rollback: self.rollback.or(fallback.rollback),
+            review: self.review.or(fallback.review),
+            review_auto_accept: self.review_auto_accept.or(fallback.review_auto_accept),
+            draft_pr: self.draft_pr.or(fallback.draft_pr),
+            repo_map: self.repo_map.or(fallback.repo_map),
+            prompt_tokens: self.prompt_tokens.or(fallback.prompt_tokens),
+            desktop_notify: self.desktop_notify.or(fallback.desktop_notify),
+            agent_sessions: self.agent_sessions.or(fallback.agent_sessions),
+            branch_template: self.branch_template.or(fallback.branch_template),
+        }
+    }
+}
+
+/// `[paths]` locations of Ralph's files, relative to the repository root
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PathConfig {
+    /// Per-feature task directories
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tasks: Option<PathBuf>,
+    /// Rendered Markdown PRDs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<PathBuf>,
+    /// Validation profiles file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation: Option<PathBuf>,
+    /// Agent prompt templates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompts: Option<PathBuf>,
+    /// Remote ledger sync settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync: Option<PathBuf>,
+    /// Notification settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<PathBuf>,
+    /// Ledger of cross-feature activity
+    #[serde(
+        default,
+        rename = "workspace-ledger",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub workspace_ledger: Option<PathBuf>,
+}
+
+impl PathConfig {
+    /// Tasks directory, defaulting to [`DEFAULT_TASKS_DIR`]
+    #[must_use]
+    pub fn tasks(&self) -> &Path {
+        self.tasks
+            .as_deref()
+            .unwrap_or(Path::new(DEFAULT_TASKS_DIR))
+    }
+
+    /// Docs directory, defaulting to [`DEFAULT_DOCS_DIR`]
+    #[must_use]
+    pub fn docs(&self) -> &Path {
+        self.docs.as_deref().unwrap_or(Path::new(DEFAULT_DOCS_DIR))
+    }
+
+    /// Validation file, defaulting to [`DEFAULT_VALIDATION_FILE`]
+    #[must_use]
+    pub fn validation(&self) -> &Path {
+        self.validation
+            .as_deref()
+            .unwrap_or(Path::new(DEFAULT_VALIDATION_FILE))
+    }
+
+    /// Prompt templates directory, defaulting to [`DEFAULT_PROMPTS_DIR`]
+    #[must_use]
+    pub fn prompts(&self) -> &Path {
+        self.prompts
+            .as_deref()
+            .unwrap_or(Path::new(DEFAULT_PROMPTS_DIR))
+    }
+
+    /// Sync settings file, defaulting to [`DEFAULT_SYNC_FILE`]
+    #[must_use]
+    pub fn sync(&self) -> &Path {
+        self.sync.as_deref().unwrap_or(Path::new(DEFAULT_SYNC_FILE))
+    }
+
+    /// Notification settings file, defaulting to [`NOTIFY_CONFIG_FILE`]
+    #[must_use]
+    pub fn notify(&self) -> &Path {
+        self.notify
+            .as_deref()
+            .unwrap_or(Path::new(NOTIFY_CONFIG_FILE))
+    }
+
+    /// Workspace ledger, defaulting to [`WORKSPACE_LEDGER`]
+    #[must_use]
+    pub fn workspace_ledger(&self) -> &Path {
+        self.workspace_ledger
+            .as_deref()
+            .unwrap_or(Path::new(WORKSPACE_LEDGER))
+    }
+
+    /// These paths with unset ones taken from `fallback`
+    #[must_use]
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            tasks: self.tasks.or(fallback.tasks),
+            docs: self.docs.or(fallback.docs),
+            validation: self.validation.or(fallback.validation),
+            prompts: self.prompts.or(fallback.prompts),
+            sync: self.sync.or(fallback.sync),
+            notify: self.notify.or(fallback.notify),
+            workspace_ledger: self.workspace_ledger.or(fallback.workspace_ledger),
+        }
+    }
+}
+
+/// How failed validation output is summarized before it is recorded in the ledger
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SummarizerMode {
+    /// Ask the summarizer model (or the configured command) for a few bullet points, falling
+    /// back to the heuristic summary if the call fails
+    #[default]
+    Agent,
+    /// Extract errors and failing tests locally, with no model or network calls
+    Heuristic,
+    /// Never summarize; long output is truncated to its first and last lines
+    Off,
+}
+
+impl SummarizerMode {
+    /// Names accepted in config, the environment, and on the command line
+    pub const NAMES: [&'static str; 3] = ["agent", "heuristic", "off"];
+}
+
+impl FromStr for SummarizerMode {
+    type Err = RalphError;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match name {
+            "agent" => Ok(Self::Agent),
+            "heuristic" => Ok(Self::Heuristic),
+            "off" => Ok(Self::Off),
+            other => Err(RalphError::Config(format!(
+                "unknown summarizer '{other}' (expected one of: {})",
+                Self::NAMES.join(", ")
+            ))),
+        }
+    }
+}
+
+/// `[summarizer]` failure summarization
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SummarizerConfig {
+    /// Whether and how failures are summarized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<SummarizerMode>,
+    /// Shell command run instead of copilot: it reads the summarization prompt on stdin and
+    /// prints the summary, with `{model}` replaced by the summarizer model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+impl SummarizerConfig {
+    /// Summarizer mode, defaulting to [`SummarizerMode::Agent`]
+    #[must_use]
+    pub fn mode(&self) -> SummarizerMode {
+        self.mode.unwrap_or_default()
+    }
+
+    /// These settings with unset ones taken from `fallback`
+    #[must_use]
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            mode: self.mode.or(fallback.mode),
+            command: self.command.or(fallback.command),
+        }
+    }
+}
+
+/// `[hooks]` git hook installation
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HookConfig {
+    /// Directory the commit-msg hook is installed into, for `core.hooksPath`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<PathBuf>,
+}
+
+impl HookConfig {
+    /// Hooks directory, defaulting to [`DEFAULT_HOOKS_DIR`]
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        self.dir.as_deref().unwrap_or(Path::new(DEFAULT_HOOKS_DIR))
+    }
+
+    /// These settings with unset ones taken from `fallback`
+    #[must_use]
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            dir: self.dir.or(fallback.dir),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_models_default_and_override() {
+        let dir = tempdir().unwrap();
+        let config = Config::resolve(dir.path(), |_| None).unwrap();
+        assert_eq!(config.models.implementer(), DEFAULT_IMPLEMENTER_MODEL);
+        assert_eq!(config.models.planner(), DEFAULT_PLANNER_MODEL);
+
+        std::fs::write(
+            dir.path().join(CONFIG_FILE),
+            "[models]\nimplementer = \"gpt-5\"\nsummarizer = \"claude-haiku-4.5\"\n",
+        )
+        .unwrap();
+        let file = Config::resolve(dir.path(), |_| None).unwrap().models;
+        let flags = ModelConfig {
+            implementer: Some("claude-sonnet-4.5".to_string()),
+            ..ModelConfig::default()
+        };
+        let models = flags.or(file);
+        assert_eq!(models.implementer(), "claude-sonnet-4.5");
+        assert_eq!(models.summarizer(), "claude-haiku-4.5");
+        assert_eq!(models.planner(), DEFAULT_PLANNER_MODEL);
+    }
+
+    #[test]
+    fn test_unknown_keys_rejected() {
+        let err = Config::from_toml("[models]\nimplementor = \"gpt-5\"\n").unwrap_err();
+        assert!(err.to_string().contains("implementor"));
+    }
+
+    #[test]
+    fn test_layers_override_in_order() {
+        let home = tempdir().unwrap();
+        let repo = tempdir().unwrap();
+        let user_file = home.path().join(".config").join(USER_CONFIG_FILE);
+        std::fs::create_dir_all(user_file.parent().unwrap()).unwrap();
+        std::fs::write(
+            &user_file,
+            "[models]\nplanner = \"gpt-5\"\nimplementer = \"gpt-5\"\n\n\
+             [implement]\nmax-iterations = 3\nbranch-template = \"me/{slug}\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            repo.path().join(CONFIG_FILE),
+            "[implement]\nmax-iterations = 5\nselection = \"round-robin\"\n\
+             max-duration = \"90m\"\nmax-cost = 5\nreview = true\nreview-auto-accept = 40\n\n\
+             [paths]\ntasks = \"work/tasks\"\n\n\
+             [summarizer]\ncommand = \"llm -m {model}\"\n",
+        )
+        .unwrap();
+
+        let home_dir = home.path().display().to_string();
+        let lookup = |var: &str| match var {
+            "HOME" => Some(home_dir.clone()),
+            "RALPH_IMPLEMENTER_MODEL" => Some("claude-sonnet-4.5".to_string()),
+            _ => None,
+        };
+        let config = Config::resolve(repo.path(), lookup).unwrap();
+        assert_eq!(config.models.implementer(), "claude-sonnet-4.5");
+        assert_eq!(config.models.planner(), "gpt-5");
+        assert_eq!(config.implement.max_iterations(), 5);
+        assert_eq!(config.implement.selection(), Selection::RoundRobin);
+        assert_eq!(config.implement.branch_name("auth", "run-1"), "me/auth");
+        assert_eq!(
+            config.implement.budget(),
+            RunBudget {
+                max_duration: Some(Duration::from_secs(5_400)),
+                max_cost: Some(5.0),
+            }
+        );
+        assert!(config.implement.review());
+        assert_eq!(config.implement.review_auto_accept(), 40);
+        assert_eq!(config.paths.tasks(), Path::new("work/tasks"));
+        assert_eq!(config.paths.docs(), Path::new(DEFAULT_DOCS_DIR));
+        assert_eq!(config.paths.sync(), Path::new(DEFAULT_SYNC_FILE));
+        assert_eq!(config.paths.workspace_ledger(), Path::new(WORKSPACE_LEDGER));
+        assert_eq!(config.hooks.dir(), Path::new(DEFAULT_HOOKS_DIR));
+        assert_eq!(config.summarizer.mode(), SummarizerMode::Agent);
+        assert_eq!(config.summarizer.command.as_deref(), Some("llm -m {model}"));
+    }
+
+    #[test]
+    fn test_from_env() {
+        let lookup = |var: &str| match var {
+            "RALPH_MAX_ITERATIONS" => Some("25".to_string()),
+            "RALPH_DOCS_DIR" => Some("site/prds".to_string()),
+            "RALPH_PARALLEL" => Some("4".to_string()),
+            "RALPH_AGENT_TIMEOUT" => Some("900".to_string()),
+            "RALPH_MAX_COST" => Some("$2.50".to_string()),
+            "RALPH_AUTO_COMMIT" => Some("1".to_string()),
+            "RALPH_ROLLBACK" => Some("true".to_string()),
+            "RALPH_PROMPTS_DIR" => Some("agents/prompts".to_string()),
+            "RALPH_REPO_MAP" => Some("0".to_string()),
+            "RALPH_PROMPT_TOKENS" => Some("12000".to_string()),
+            "RALPH_DESKTOP_NOTIFY" => Some("true".to_string()),
+            "RALPH_AGENT_SESSIONS" => Some("false".to_string()),
+            "RALPH_SUMMARIZER" => Some("off".to_string()),
+            _ => None,
+        };
+        let config = Config::from_env(lookup).unwrap();
+        assert_eq!(config.implement.max_iterations, Some(25));
+        assert_eq!(config.implement.parallel(), 4);
+        assert_eq!(
+            config.implement.agent_timeout(),
+            Some(Duration::from_secs(900))
+        );
+        assert_eq!(config.implement.agent_timeout_retries(), 0);
+        assert_eq!(config.implement.budget().max_cost, Some(2.5));
+        assert_eq!(config.implement.budget().max_duration, None);
+        assert!(config.implement.auto_commit());
+        assert!(config.implement.rollback());
+        assert_eq!(config.implement.stuck_after(), None);
+        assert!(!config.implement.draft_pr());
+        assert!(!config.implement.repo_map());
+        assert_eq!(config.implement.prompt_tokens(), 12_000);
+        assert!(config.implement.desktop_notify());
+        assert!(!config.implement.agent_sessions());
+        assert_eq!(config.summarizer.mode(), SummarizerMode::Off);
+        assert_eq!(config.summarizer.command, None);
+        assert_eq!(config.paths.docs(), Path::new("site/prds"));
+        assert_eq!(config.paths.prompts(), Path::new("agents/prompts"));
+        assert_eq!(config.models, ModelConfig::default());
+
+        let err = Config::from_env(|var| (var == "RALPH_MAX_ITERATIONS").then(|| "ten".into()))
+            .unwrap_err();
+        assert!(err.to_string().contains("ten"));
+        let err =
+            Config::from_env(|var| (var == "RALPH_AUTO_COMMIT").then(|| "yes".into())).unwrap_err();
+        assert!(err.to_string().contains("yes"));
+        let err =
+            Config::from_env(|var| (var == "RALPH_SUMMARIZER").then(|| "llm".into())).unwrap_err();
+        assert!(err.to_string().contains("unknown summarizer 'llm'"));
+        assert_eq!(
+            "heuristic".parse::<SummarizerMode>().unwrap(),
+            SummarizerMode::Heuristic
+        );
+    }
+
+    #[test]
+    fn test_branch_name() {
+        let config = LoopConfig::default();
+        assert_eq!(
+            config.branch_name("auth", "20250101-abc"),
+            "ralph/auth/20250101-abc"
+        );
+        let custom = LoopConfig {
+            branch_template: Some("feature/{slug}-{run_id}".to_string()),
+            ..LoopConfig::default()
+        };
+        assert_eq!(custom.branch_name("auth", "7"), "feature/auth-7");
+    }
+}
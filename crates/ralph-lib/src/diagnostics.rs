@@ -0,0 +1,252 @@
+// ABOUTME: Extracts file/line diagnostics from machine-readable tool output
+// ABOUTME: Understands cargo --message-format json, eslint --format json, and tsc's error lines
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Most diagnostics kept from one command's output
+pub const MAX_DIAGNOSTICS: usize = 200;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    /// Lowercase name as printed by compilers
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+
+/// A problem a tool reported at a location in the source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// File the problem is in, as the tool reported it
+    pub file: String,
+    /// 1-based line, if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    /// 1-based column, if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    pub severity: Severity,
+    /// Tool-specific code or rule (e.g., E0308, TS2322, no-unused-vars)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file)?;
+        if let Some(line) = self.line {
+            write!(f, ":{line}")?;
+            if let Some(column) = self.column {
+                write!(f, ":{column}")?;
+            }
+        }
+        write!(f, ": {}", self.severity.as_str())?;
+        if let Some(code) = &self.code {
+            write!(f, "[{code}]")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Extract diagnostics from a tool's combined output
+#[must_use]
+pub fn parse(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for line in output.lines() {
+        parse_line(line, &mut diagnostics);
+        if diagnostics.len() >= MAX_DIAGNOSTICS {
+            break;
+        }
+    }
+    diagnostics.truncate(MAX_DIAGNOSTICS);
+    diagnostics
+}
+
+/// Extract diagnostics from output spooled to a file, one line at a time
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read.
+pub fn parse_file(path: &Path) -> std::io::Result<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+    for line in BufReader::new(File::open(path)?).split(b'\n') {
+        parse_line(&String::from_utf8_lossy(&line?), &mut diagnostics);
+        if diagnostics.len() >= MAX_DIAGNOSTICS {
+            break;
+        }
+    }
+    diagnostics.truncate(MAX_DIAGNOSTICS);
+    Ok(diagnostics)
+}
+
+fn parse_line(line: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let line = line.trim();
+    if line.starts_with('{') || line.starts_with('[') {
+        if let Ok(value) = serde_json::from_str::<Value>(line) {
+            match value {
+                Value::Object(_) => diagnostics.extend(cargo_diagnostic(&value)),
+                Value::Array(files) => diagnostics.extend(eslint_diagnostics(&files)),
+                _ => {}
+            }
+        }
+    } else if let Some(diagnostic) = tsc_diagnostic(line) {
+        diagnostics.push(diagnostic);
+    }
+}
+
+/// A `compiler-message` line from `cargo --message-format json`
+fn cargo_diagnostic(value: &Value) -> Option<Diagnostic> {
+    if value.get("reason")?.as_str()? != "compiler-message" {
+        return None;
+    }
+    let message = value.get("message")?;
+    let severity = match message.get("level")?.as_str()? {
+        "error" => Severity::Error,
+        "warning" => Severity::Warning,
+        _ => return None,
+    };
+    let spans = message.get("spans")?.as_array()?;
+    let span = spans
+        .iter()
+        .find(|span| span.get("is_primary").and_then(Value::as_bool) == Some(true))
+        .or_else(|| spans.first())?;
+    Some(Diagnostic {
+        file: span.get("file_name")?.as_str()?.to_string(),
+        line: json_u32(span.get("line_start")),
+        column: json_u32(span.get("column_start")),
+        severity,
+        code: message
+            .get("code")
+            .and_then(|code| code.get("code"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        message: message.get("message")?.as_str()?.to_string(),
+    })
+}
+
+/// The file results array from `eslint --format json`
+fn eslint_diagnostics(files: &[Value]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for file in files {
+        let Some(path) = file.get("filePath").and_then(Value::as_str) else {
+            continue;
+        };
+        let messages = file.get("messages").and_then(Value::as_array);
+        for message in messages.into_iter().flatten() {
+            let severity = match message.get("severity").and_then(Value::as_u64) {
+                Some(2) => Severity::Error,
+                Some(1) => Severity::Warning,
+                _ => continue,
+            };
+            let Some(text) = message.get("message").and_then(Value::as_str) else {
+                continue;
+            };
+            diagnostics.push(Diagnostic {
+                file: path.to_string(),
+                line: json_u32(message.get("line")),
+                column: json_u32(message.get("column")),
+                severity,
+                code: message
+                    .get("ruleId")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                message: text.to_string(),
+            });
+        }
+    }
+    diagnostics
+}
+
+/// A tsc error line: `src/a.ts(3,7): error TS2322: Type 'string' is not assignable ...`
+fn tsc_diagnostic(line: &str) -> Option<Diagnostic> {
+    let (location, rest) = line.split_once("): ")?;
+    let (file, position) = location.rsplit_once('(')?;
+    let (line_number, column) = position.split_once(',')?;
+    let (category, rest) = rest.split_once(' ')?;
+    let severity = match category {
+        "error" => Severity::Error,
+        "warning" => Severity::Warning,
+        _ => return None,
+    };
+    let (code, message) = rest.split_once(": ")?;
+    if !code.starts_with("TS") {
+        return None;
+    }
+    Some(Diagnostic {
+        file: file.to_string(),
+        line: Some(line_number.trim().parse().ok()?),
+        column: Some(column.trim().parse().ok()?),
+        severity,
+        code: Some(code.to_string()),
+        message: message.to_string(),
+    })
+}
+
+fn json_u32(value: Option<&Value>) -> Option<u32> {
+    value?.as_u64()?.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cargo_json() {
+        let output = r#"{"reason":"compiler-artifact","target":{"name":"dep"}}
+{"reason":"compiler-message","message":{"message":"mismatched types","code":{"code":"E0308"},"level":"error","spans":[{"file_name":"src/other.rs","line_start":1,"column_start":1,"is_primary":false},{"file_name":"src/lib.rs","line_start":10,"column_start":5,"is_primary":true}]}}
+{"reason":"compiler-message","message":{"message":"aborting due to 1 previous error","code":null,"level":"error","spans":[]}}"#;
+        let diagnostics = parse(output);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "src/lib.rs:10:5: error[E0308]: mismatched types"
+        );
+    }
+
+    #[test]
+    fn test_parse_eslint_json() {
+        let output = r#"[{"filePath":"/repo/src/app.js","messages":[{"ruleId":"no-unused-vars","severity":2,"message":"'x' is defined but never used.","line":1,"column":7},{"ruleId":"eqeqeq","severity":1,"message":"Expected '==='.","line":3,"column":9}]}]"#;
+        let diagnostics = parse(output);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("no-unused-vars"));
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert_eq!(diagnostics[1].line, Some(3));
+    }
+
+    #[test]
+    fn test_parse_tsc() {
+        let output =
+            "src/a.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                      Found 1 error in src/a.ts:3";
+        let diagnostics = parse(output);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].file, "src/a.ts");
+        assert_eq!(diagnostics[0].column, Some(7));
+        assert_eq!(diagnostics[0].code.as_deref(), Some("TS2322"));
+    }
+
+    #[test]
+    fn test_parse_ignores_plain_output() {
+        assert!(
+            parse("error: could not compile `foo` (lib)\nnote: run with RUST_BACKTRACE=1")
+                .is_empty()
+        );
+    }
+}
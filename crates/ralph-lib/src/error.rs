@@ -14,6 +14,10 @@ pub enum RalphError {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// TOML parsing or serialization error
+    #[error("TOML error: {0}")]
+    Toml(String),
+
     /// PRD schema validation failed
     #[error("PRD validation error: {0}")]
     PrdValidation(String),
@@ -37,4 +41,12 @@ pub enum RalphError {
     /// Copilot CLI error
     #[error("Copilot error: {0}")]
     Copilot(String),
+
+    /// Configuration value is invalid
+    #[error("Config error: {0}")]
+    Config(String),
+
+    /// Secret could not be resolved
+    #[error("Secret error: {0}")]
+    Secret(String),
 }
@@ -0,0 +1,154 @@
+// ABOUTME: Renders PRDs for stakeholders who don't read markdown in the repo
+// ABOUTME: Supports standalone styled HTML and Confluence storage format
+
+use crate::{Prd, RequirementStatus};
+use std::fmt::Write;
+
+const HTML_STYLE: &str = "body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;\
+max-width:860px;margin:2em auto;padding:0 1em;color:#24292f;line-height:1.5}\
+table{border-collapse:collapse;width:100%}th,td{border:1px solid #d0d7de;padding:6px 10px;\
+text-align:left;vertical-align:top}th{background:#f6f8fa}.status{border-radius:1em;\
+padding:2px 8px;font-size:.85em;font-weight:600;white-space:nowrap}\
+.todo{background:#eaeef2}.in_progress{background:#ddf4ff}.done{background:#dafbe1}\
+.blocked{background:#ffebe9}pre{background:#f6f8fa;padding:1em;white-space:pre-wrap}";
+
+/// Render a PRD as a standalone styled HTML page
+#[must_use]
+pub fn prd_to_html(prd: &Prd, planning_log: Option<&str>) -> String {
+    let title = escape(&prd.title);
+    let (done, total) = progress(prd);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n<p>Progress: {done}/{total} requirements done</p>\n\
+         <h2>Requirements</h2>\n<table>\n\
+         <tr><th>ID</th><th>Requirement</th><th>Status</th><th>Acceptance criteria</th></tr>\n"
+    );
+    for req in &prd.requirements {
+        let (class, label) = status_label(&req.status);
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td><span class=\"status {class}\">{label}</span></td><td>{}</td></tr>",
+            escape(&req.id),
+            escape(&req.title),
+            criteria_list(&req.acceptance_criteria)
+        );
+    }
+    html.push_str("</table>\n");
+    if let Some(log) = planning_log.filter(|log| !log.trim().is_empty()) {
+        let _ = writeln!(html, "<h2>Planning Log</h2>\n<pre>{}</pre>", escape(log));
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Render a PRD in Confluence storage format (paste via the page's source editor or REST API)
+#[must_use]
+pub fn prd_to_confluence(prd: &Prd, planning_log: Option<&str>) -> String {
+    let (done, total) = progress(prd);
+    let mut page = format!(
+        "<p>Progress: {done}/{total} requirements done</p>\n<h2>Requirements</h2>\n<table>\n<tbody>\n\
+         <tr><th>ID</th><th>Requirement</th><th>Status</th><th>Acceptance criteria</th></tr>\n"
+    );
+    for req in &prd.requirements {
+        let (_, label) = status_label(&req.status);
+        let colour = match req.status {
+            RequirementStatus::Todo => "Grey",
+            RequirementStatus::InProgress => "Blue",
+            RequirementStatus::Done => "Green",
+            RequirementStatus::Blocked => "Red",
+        };
+        let _ = writeln!(
+            page,
+            "<tr><td>{}</td><td>{}</td><td><ac:structured-macro ac:name=\"status\">\
+             <ac:parameter ac:name=\"colour\">{colour}</ac:parameter>\
+             <ac:parameter ac:name=\"title\">{label}</ac:parameter>\
+             </ac:structured-macro></td><td>{}</td></tr>",
+            escape(&req.id),
+            escape(&req.title),
+            criteria_list(&req.acceptance_criteria)
+        );
+    }
+    page.push_str("</tbody>\n</table>\n");
+    if let Some(log) = planning_log.filter(|log| !log.trim().is_empty()) {
+        let _ = writeln!(
+            page,
+            "<h2>Planning Log</h2>\n<ac:structured-macro ac:name=\"code\">\
+             <ac:plain-text-body><![CDATA[{}]]></ac:plain-text-body></ac:structured-macro>",
+            log.replace("]]>", "]]]]><![CDATA[>")
+        );
+    }
+    page
+}
+
+fn progress(prd: &Prd) -> (usize, usize) {
+    let done = prd
+        .requirements
+        .iter()
+        .filter(|r| r.status == RequirementStatus::Done)
+        .count();
+    (done, prd.requirements.len())
+}
+
+fn status_label(status: &RequirementStatus) -> (&'static str, &'static str) {
+    match status {
+        RequirementStatus::Todo => ("todo", "TODO"),
+        RequirementStatus::InProgress => ("in_progress", "IN PROGRESS"),
+        RequirementStatus::Done => ("done", "DONE"),
+        RequirementStatus::Blocked => ("blocked", "BLOCKED"),
+    }
+}
+
+fn criteria_list(criteria: &[String]) -> String {
+    if criteria.is_empty() {
+        return String::new();
+    }
+    let items: String = criteria
+        .iter()
+        .map(|ac| format!("<li>{}</li>", escape(ac)))
+        .collect();
+    format!("<ul>{items}</ul>")
+}
+
+/// Escape text for inclusion in HTML/XHTML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_prd() -> Prd {
+        Prd::from_json(
+            r#"{"schemaVersion":"1.0","slug":"f","title":"Search <beta>","activeRunId":"f-1","validationProfiles":[],"requirements":[
+                {"id":"REQ-01","title":"Index","status":"done","acceptanceCriteria":["Given A & B"]},
+                {"id":"REQ-02","title":"Query","status":"todo","acceptanceCriteria":[]}
+            ]}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_prd_to_html() {
+        let html = prd_to_html(&sample_prd(), Some("Q1: why? A: because"));
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Search &lt;beta&gt;</title>"));
+        assert!(html.contains("Progress: 1/2"));
+        assert!(html.contains("<span class=\"status done\">DONE</span>"));
+        assert!(html.contains("<li>Given A &amp; B</li>"));
+        assert!(html.contains("<pre>Q1: why? A: because</pre>"));
+    }
+
+    #[test]
+    fn test_prd_to_confluence() {
+        let page = prd_to_confluence(&sample_prd(), Some("log ]]> end"));
+        assert!(page.contains("<ac:parameter ac:name=\"colour\">Green</ac:parameter>"));
+        assert!(page.contains("<ac:parameter ac:name=\"title\">TODO</ac:parameter>"));
+        assert!(page.contains("<![CDATA[log ]]]]><![CDATA[> end]]>"));
+        assert!(!prd_to_confluence(&sample_prd(), None).contains("Planning Log"));
+    }
+}
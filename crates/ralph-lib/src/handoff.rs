@@ -0,0 +1,306 @@
+// ABOUTME: Hand-off documents for requirements the implement loop gave up on
+// ABOUTME: Summarizes context, attempts, failures, and the last diff for a human to pick up
+
+use crate::ledger::NO_OP_MESSAGE;
+use crate::{EventStatus, Ledger, Prd, Requirement};
+use std::fmt::Write;
+
+/// Diff size above which the hand-off only includes the beginning of the diff
+const MAX_DIFF_CHARS: usize = 20_000;
+
+/// Message prefix of the ledger event recorded when a requirement is escalated
+pub const ESCALATED_MESSAGE: &str = "escalated to blocked";
+
+/// Count failed iterations for a requirement since it was last escalated
+#[must_use]
+pub fn failed_attempts(ledger: &Ledger, req_id: &str) -> usize {
+    ledger
+        .events_for_requirement(req_id)
+        .iter()
+        .rev()
+        .take_while(|e| {
+            !e.message
+                .as_deref()
+                .is_some_and(|m| m.starts_with(ESCALATED_MESSAGE))
+        })
+        .filter(|e| e.status == EventStatus::Failed)
+        .count()
+}
+
+/// Count the requirement's most recent iterations that failed validation back to back
+///
+/// Iterations that failed for another reason (or passed) end the streak, as does escalation.
+#[must_use]
+pub fn consecutive_validation_failures(ledger: &Ledger, req_id: &str) -> usize {
+    ledger
+        .events_for_requirement(req_id)
+        .iter()
+        .rev()
+        .take_while(|e| {
+            !e.message
+                .as_deref()
+                .is_some_and(|m| m.starts_with(ESCALATED_MESSAGE))
+        })
+        .filter(|e| matches!(e.status, EventStatus::Done | EventStatus::Failed))
+        .take_while(|e| e.status == EventStatus::Failed && e.validation_passed == Some(false))
+        .count()
+}
+
+/// One-line summary of the requirement's last `count` validation failures, grouped by stage
+///
+/// E.g. `Test failed 3x (last: parse_bad_input panicked); Clippy failed 1x`.
+#[must_use]
+pub fn failure_summary(ledger: &Ledger, req_id: &str, count: usize) -> String {
+    // (stage, failures, first detail line of the latest failure)
+    let mut stages: Vec<(String, usize, Option<String>)> = Vec::new();
+    let failures = ledger
+        .events_for_requirement(req_id)
+        .into_iter()
+        .rev()
+        .filter(|e| e.validation_passed == Some(false))
+        .take(count);
+    for event in failures {
+        let output = event.validation_output.as_deref().unwrap_or_default();
+        let mut lines = output.lines().map(str::trim).filter(|l| !l.is_empty());
+        let stage = lines
+            .next()
+            .and_then(|line| line.strip_prefix("Stage: "))
+            .unwrap_or("validation")
+            .to_string();
+        let detail = lines
+            .next()
+            .map(|line| line.trim_start_matches("- ").to_string());
+        match stages.iter_mut().find(|(name, _, _)| *name == stage) {
+            Some((_, failures, _)) => *failures += 1,
+            None => stages.push((stage, 1, detail)),
+        }
+    }
+    stages
+        .iter()
+        .map(|(stage, failures, detail)| match detail {
+            Some(detail) => format!("{stage} failed {failures}x (last: {detail})"),
+            None => format!("{stage} failed {failures}x"),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Render a ready-to-file markdown hand-off for a blocked requirement
+#[must_use]
+pub fn render_handoff(prd: &Prd, req: &Requirement, ledger: &Ledger, diff: &str) -> String {
+    let mut doc = format!("# Hand-off: {} {} ({})\n\n", req.id, req.title, prd.slug);
+    let _ = writeln!(
+        doc,
+        "The implement loop escalated this requirement to **blocked** after {} failed attempt(s).\n",
+        failed_attempts(ledger, &req.id)
+    );
+
+    doc.push_str("## Context\n\n");
+    let _ = writeln!(doc, "- Feature: {} (`{}`)", prd.title, prd.slug);
+    let _ = writeln!(doc, "- Run: `{}`", prd.active_run_id);
+    if !req.paths.is_empty() {
+        let _ = writeln!(doc, "- Paths: {}", req.paths.join(", "));
+    }
+    doc.push_str("\nAcceptance criteria:\n\n");
+    for ac in &req.acceptance_criteria {
+        let _ = writeln!(doc, "- [ ] {ac}");
+    }
+
+    let events = ledger.events_for_requirement(&req.id);
+    doc.push_str("\n## Attempts\n\n| Iteration | Time | Status | Notes |\n|---|---|---|---|\n");
+    for event in events.iter().filter(|e| e.status != EventStatus::Started) {
+        let notes = match (event.validation_passed, event.message.as_deref()) {
+            (_, Some(message)) => message.replace('|', "\\|"),
+            (Some(false), None) => "validation failed".to_string(),
+            _ => String::new(),
+        };
+        let _ = writeln!(
+            doc,
+            "| {} | {} | {:?} | {notes} |",
+            event.iteration,
+            event.timestamp.format("%Y-%m-%d %H:%M"),
+            event.status
+        );
+    }
+
+    doc.push_str("\n## Failures\n\n");
+    match ledger.get_last_validation_failure(&req.id) {
+        Some(output) => {
+            let _ = writeln!(
+                doc,
+                "Last validation failure:\n\n```\n{}\n```",
+                output.trim()
+            );
+        }
+        None => doc.push_str("No validation output was recorded.\n"),
+    }
+
+    doc.push_str("\n## Last diff\n\n");
+    if diff.trim().is_empty() {
+        doc.push_str("No uncommitted changes when the loop gave up.\n");
+    } else {
+        let mut end = diff.len().min(MAX_DIFF_CHARS);
+        while !diff.is_char_boundary(end) {
+            end -= 1;
+        }
+        let _ = writeln!(doc, "```diff\n{}\n```", diff[..end].trim_end());
+        if end < diff.len() {
+            let _ = writeln!(doc, "\n_(diff truncated, {} more chars)_", diff.len() - end);
+        }
+    }
+
+    doc.push_str("\n## Suggested next steps\n\n");
+    for step in suggested_steps(ledger, &req.id) {
+        let _ = writeln!(doc, "- {step}");
+    }
+    let _ = writeln!(
+        doc,
+        "- Once resolved, set {} back to `todo` (or `done`) in the PRD and re-run `ralph implement {}`",
+        req.id, prd.slug
+    );
+    doc
+}
+
+/// Suggest next steps based on how the attempts failed
+fn suggested_steps(ledger: &Ledger, req_id: &str) -> Vec<String> {
+    let events = ledger.events_for_requirement(req_id);
+    let mut steps = Vec::new();
+
+    if events
+        .iter()
+        .any(|e| e.message.as_deref() == Some(NO_OP_MESSAGE))
+    {
+        steps.push(
+            "The agent finished without changing anything at least once: check whether the \
+             requirement is already satisfied or the acceptance criteria are ambiguous"
+                .to_string(),
+        );
+    }
+    if let Some(stage) = ledger
+        .get_last_validation_failure(req_id)
+        .and_then(|output| output.lines().next().map(str::to_string))
+        .and_then(|line| line.strip_prefix("Stage: ").map(str::to_lowercase))
+    {
+        steps.push(format!(
+            "Reproduce the failing {stage} stage locally and fix the root cause the agent kept missing"
+        ));
+    }
+    if events.iter().any(|e| {
+        e.message
+            .as_deref()
+            .is_some_and(|m| m.starts_with("merge failed"))
+    }) {
+        steps.push(
+            "A parallel merge failed: check the requirement's declared paths for overlap"
+                .to_string(),
+        );
+    }
+    if steps.is_empty() {
+        steps.push(
+            "Review the attempts above and consider splitting the requirement into smaller ones \
+             (`ralph split`)"
+                .to_string(),
+        );
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LedgerEvent;
+
+    fn sample_prd() -> Prd {
+        Prd::from_json(
+            r#"{"schemaVersion":"1.0","slug":"f","title":"Feature","activeRunId":"f-1","validationProfiles":[],"requirements":[
+                {"id":"REQ-01","title":"Parse input","status":"blocked","acceptanceCriteria":["Given bad input, then an error"],"paths":["src/parse.rs"]}
+            ]}"#,
+        )
+        .unwrap()
+    }
+
+    fn sample_ledger() -> Ledger {
+        let mut ledger = Ledger::new();
+        ledger
+            .append(LedgerEvent::new(1, "REQ-01", EventStatus::Started))
+            .unwrap();
+        ledger
+            .append(
+                LedgerEvent::new(1, "REQ-01", EventStatus::Failed)
+                    .with_validation(false)
+                    .with_validation_output("Stage: Test\n\n- parse_bad_input panicked"),
+            )
+            .unwrap();
+        ledger
+            .append(LedgerEvent::new(2, "REQ-01", EventStatus::Failed).with_message(NO_OP_MESSAGE))
+            .unwrap();
+        ledger
+    }
+
+    #[test]
+    fn test_failed_attempts() {
+        let mut ledger = sample_ledger();
+        assert_eq!(failed_attempts(&ledger, "REQ-01"), 2);
+        assert_eq!(failed_attempts(&ledger, "REQ-02"), 0);
+
+        // Escalation resets the budget for when a human sets it back to todo
+        ledger
+            .append(
+                LedgerEvent::new(2, "REQ-01", EventStatus::InProgress)
+                    .with_message(format!("{ESCALATED_MESSAGE}: see hand-off")),
+            )
+            .unwrap();
+        assert_eq!(failed_attempts(&ledger, "REQ-01"), 0);
+    }
+
+    #[test]
+    fn test_consecutive_validation_failures() {
+        let mut ledger = sample_ledger();
+        // The no-op failure at the end breaks the validation streak
+        assert_eq!(consecutive_validation_failures(&ledger, "REQ-01"), 0);
+
+        for iteration in 3..=4 {
+            ledger
+                .append(
+                    LedgerEvent::new(iteration, "REQ-01", EventStatus::Failed)
+                        .with_validation(false)
+                        .with_validation_output("Stage: Clippy\n\n- needless_return"),
+                )
+                .unwrap();
+        }
+        assert_eq!(consecutive_validation_failures(&ledger, "REQ-01"), 2);
+        assert_eq!(
+            failure_summary(&ledger, "REQ-01", 3),
+            "Clippy failed 2x (last: needless_return); Test failed 1x (last: parse_bad_input panicked)"
+        );
+    }
+
+    #[test]
+    fn test_render_handoff() {
+        let prd = sample_prd();
+        let doc = render_handoff(
+            &prd,
+            &prd.requirements[0],
+            &sample_ledger(),
+            "+fn parse() {}\n",
+        );
+        assert!(doc.starts_with("# Hand-off: REQ-01 Parse input (f)"));
+        assert!(doc.contains("after 2 failed attempt(s)"));
+        assert!(doc.contains("- Paths: src/parse.rs"));
+        assert!(doc.contains("- [ ] Given bad input, then an error"));
+        assert!(doc.contains("| 1 | "));
+        assert!(doc.contains("parse_bad_input panicked"));
+        assert!(doc.contains("```diff\n+fn parse() {}\n```"));
+        assert!(doc.contains("failing test stage"));
+        assert!(doc.contains("already satisfied"));
+    }
+
+    #[test]
+    fn test_render_handoff_without_diff() {
+        let prd = sample_prd();
+        let doc = render_handoff(&prd, &prd.requirements[0], &Ledger::new(), "");
+        assert!(doc.contains("No uncommitted changes"));
+        assert!(doc.contains("No validation output was recorded."));
+        assert!(doc.contains("ralph split"));
+    }
+}
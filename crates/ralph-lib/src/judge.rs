@@ -0,0 +1,189 @@
+// ABOUTME: LLM judge evaluation of a finished feature against its acceptance criteria
+// ABOUTME: Builds the judge prompt, parses per-criterion scores, and stores the report
+
+use crate::api::ApiDiff;
+use crate::{Prd, RalphError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Highest score a judge can award a criterion
+pub const MAX_SCORE: u8 = 5;
+
+/// Diff size above which the judge only sees a truncated diff
+const MAX_DIFF_CHARS: usize = 100_000;
+
+/// Judge's assessment of a single acceptance criterion
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriterionScore {
+    /// Requirement ID
+    pub requirement: String,
+    /// 1-based index of the criterion within the requirement
+    pub criterion: usize,
+    /// Satisfaction score from 0 to [`MAX_SCORE`]
+    pub score: u8,
+    /// Short justification
+    #[serde(default)]
+    pub rationale: String,
+}
+
+/// Judge scores for a run, stored alongside the PRD
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JudgeReport {
+    /// Run the report belongs to
+    pub run_id: String,
+    /// Model that produced the scores
+    pub model: String,
+    /// When the evaluation happened
+    pub generated_at: DateTime<Utc>,
+    /// Per-criterion scores
+    pub scores: Vec<CriterionScore>,
+    /// Public API added and removed by the run (Rust projects, when requested)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_diff: Option<ApiDiff>,
+}
+
+impl JudgeReport {
+    /// Average score across all criteria (None if nothing was scored)
+    #[must_use]
+    pub fn average(&self) -> Option<f64> {
+        if self.scores.is_empty() {
+            return None;
+        }
+        let total: u32 = self.scores.iter().map(|s| u32::from(s.score)).sum();
+        Some(f64::from(total) / self.scores.len() as f64)
+    }
+
+    /// Save the report as pretty-printed JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails or the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path.as_ref(), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Build the prompt asking a judge model to score each criterion against the diff
+#[must_use]
+pub fn build_judge_prompt(prd: &Prd, diff: &str) -> String {
+    let mut prompt = format!(
+        "You are reviewing the implementation of feature '{}'. For every acceptance \
+         criterion below, score from 0 (not satisfied) to {MAX_SCORE} (fully satisfied) \
+         how well the diff satisfies it.\n\n\
+         Respond with ONLY a JSON array, one object per criterion: \
+         [{{\"requirement\": \"REQ-01\", \"criterion\": 1, \"score\": 4, \"rationale\": \"...\"}}]\n\n\
+         Requirements:\n",
+        prd.slug
+    );
+    for req in &prd.requirements {
+        let _ = writeln!(prompt, "\n{} - {}", req.id, req.title);
+        for (i, ac) in req.acceptance_criteria.iter().enumerate() {
+            let _ = writeln!(prompt, "  {}. {ac}", i + 1);
+        }
+    }
+
+    prompt.push_str("\nDiff:\n");
+    if diff.len() > MAX_DIFF_CHARS {
+        let mut end = MAX_DIFF_CHARS;
+        while !diff.is_char_boundary(end) {
+            end -= 1;
+        }
+        prompt.push_str(&diff[..end]);
+        let _ = write!(
+            prompt,
+            "\n... (diff truncated, {} more chars) ...\n",
+            diff.len() - end
+        );
+    } else {
+        prompt.push_str(diff);
+    }
+    prompt
+}
+
+/// Parse the judge's response, tolerating prose around the JSON array
+///
+/// # Errors
+///
+/// Returns an error if no JSON array of scores can be found.
+pub fn parse_judge_output(output: &str) -> Result<Vec<CriterionScore>> {
+    let start = output.find('[');
+    let end = output.rfind(']');
+    let (Some(start), Some(end)) = (start, end) else {
+        return Err(RalphError::Copilot(
+            "Judge response did not contain a JSON array".to_string(),
+        ));
+    };
+    if end < start {
+        return Err(RalphError::Copilot(
+            "Judge response did not contain a JSON array".to_string(),
+        ));
+    }
+
+    let mut scores: Vec<CriterionScore> = serde_json::from_str(&output[start..=end])?;
+    for score in &mut scores {
+        score.score = score.score.min(MAX_SCORE);
+    }
+    Ok(scores)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_prd() -> Prd {
+        Prd::from_json(
+            r#"{"schemaVersion":"1.0","slug":"f","title":"F","activeRunId":"f-1","validationProfiles":[],"requirements":[
+                {"id":"REQ-01","title":"Add endpoint","status":"done","acceptanceCriteria":["Given A, then B","Given C, then D"]}
+            ]}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_build_judge_prompt() {
+        let prompt = build_judge_prompt(&sample_prd(), "+fn endpoint() {}");
+        assert!(prompt.contains("REQ-01 - Add endpoint"));
+        assert!(prompt.contains("  2. Given C, then D"));
+        assert!(prompt.ends_with("+fn endpoint() {}"));
+    }
+
+    #[test]
+    fn test_build_judge_prompt_truncates_diff() {
+        let diff = "x".repeat(MAX_DIFF_CHARS + 10);
+        let prompt = build_judge_prompt(&sample_prd(), &diff);
+        assert!(prompt.contains("diff truncated, 10 more chars"));
+    }
+
+    #[test]
+    fn test_parse_judge_output() {
+        let output = "Here are the scores:\n[{\"requirement\":\"REQ-01\",\"criterion\":1,\"score\":4,\"rationale\":\"ok\"},{\"requirement\":\"REQ-01\",\"criterion\":2,\"score\":9}]\nDone.";
+        let scores = parse_judge_output(output).unwrap();
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[0].score, 4);
+        assert_eq!(scores[1].score, MAX_SCORE);
+        assert!(scores[1].rationale.is_empty());
+        assert!(parse_judge_output("no json here").is_err());
+    }
+
+    #[test]
+    fn test_report_average() {
+        let mut report = JudgeReport {
+            run_id: "f-1".to_string(),
+            model: "judge".to_string(),
+            generated_at: Utc::now(),
+            scores: Vec::new(),
+            api_diff: None,
+        };
+        assert!(report.average().is_none());
+        report.scores = parse_judge_output(
+            r#"[{"requirement":"REQ-01","criterion":1,"score":4},{"requirement":"REQ-01","criterion":2,"score":3}]"#,
+        )
+        .unwrap();
+        assert!((report.average().unwrap() - 3.5).abs() < f64::EPSILON);
+    }
+}
@@ -1,13 +1,37 @@
 // ABOUTME: Append-only ledger for tracking implementation events
-// ABOUTME: Supports JSONL format with optional AVRO serialization
+// ABOUTME: Supports JSONL format with AVRO export and import
 
+pub mod analytics;
+pub mod workspace;
+
+use crate::notify::Notifier;
+use crate::sync::LedgerSync;
+use crate::usage::TokenUsage;
 use crate::{RalphError, Result};
 use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{File, OpenOptions};
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
 use std::path::Path;
 
+/// Requirement ID used for audit findings not tied to a single requirement
+pub const AUDIT_REQUIREMENT: &str = "AUDIT";
+
+/// Requirement ID recorded on chore events, which are not tied to a requirement
+pub const CHORE_REQUIREMENT: &str = "CHORE";
+
+/// Requirement ID recorded on run-level events (e.g., an aborted run)
+pub const RUN_REQUIREMENT: &str = "RUN";
+
+/// Message recorded when the agent succeeded but changed nothing
+pub const NO_OP_MESSAGE: &str = "no-op iteration: agent made no changes";
+
+/// Previous-event hash recorded by the first event of a hash chain
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
 /// Status of a ledger event
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -18,8 +42,115 @@ pub enum EventStatus {
     Failed,
 }
 
-/// A single event in the ledger
+/// What kind of work a ledger event records
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// Work on a PRD requirement
+    #[default]
+    Requirement,
+    /// Housekeeping not tied to a requirement (e.g., "update deps")
+    Chore,
+    /// Context recorded by a human between agent iterations (e.g., a manual fix)
+    Note,
+    /// Human correction attached to an earlier iteration (e.g., "flaky test")
+    Annotation,
+}
+
+impl EventKind {
+    /// Whether the event records requirement work (as opposed to chores, notes, ...)
+    #[must_use]
+    pub fn is_requirement(&self) -> bool {
+        *self == Self::Requirement
+    }
+}
+
+/// Kind-specific details of a ledger event
+///
+/// Older ledgers carry no payload; [`LedgerEvent::payload_or_inferred`] recovers
+/// one from their status and validation fields.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventPayload {
+    /// An iteration began working on the requirement
+    IterationStarted {
+        /// Whether the iteration runs the full test stage (None if unknown)
+        #[serde(default, rename = "fullTests", skip_serializing_if = "Option::is_none")]
+        full_tests: Option<bool>,
+    },
+    /// An iteration finished, successfully or not
+    IterationFinished { success: bool },
+    /// The validation profile ran against the agent's changes
+    ValidationRun {
+        passed: bool,
+        /// First stage that failed (e.g., "Test")
+        #[serde(
+            default,
+            rename = "failedStage",
+            skip_serializing_if = "Option::is_none"
+        )]
+        failed_stage: Option<String>,
+    },
+    /// A commit landed during the iteration
+    CommitCreated { sha: String, summary: String },
+    /// Someone changed the branch outside the loop
+    HumanIntervention { description: String },
+    /// The implementer agent was killed for running past its timeout
+    AgentTimedOut { seconds: u64 },
+    /// The run stopped before finishing its requirements
+    RunAborted { reason: String },
+    /// The PRD's requirements changed (added, split out, ...)
+    PlanUpdated { description: String },
+    /// The requirement was split out of feature `from` into feature `to`, keeping its ID
+    RequirementMoved { from: String, to: String },
+    /// A human annotated an earlier iteration's events
+    Annotation { iteration: u32, text: String },
+    /// A human gave guidance for the requirement's next attempt (`ralph implement --hint`)
+    Hint { text: String },
+}
+
+impl EventPayload {
+    /// One-line human-readable summary
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self {
+            Self::IterationStarted { full_tests } => match full_tests {
+                Some(true) => "iteration started (full tests)".to_string(),
+                _ => "iteration started".to_string(),
+            },
+            Self::IterationFinished { success } => format!(
+                "iteration {}",
+                if *success { "succeeded" } else { "failed" }
+            ),
+            Self::ValidationRun {
+                passed: true,
+                failed_stage: _,
+            } => "validation passed".to_string(),
+            Self::ValidationRun {
+                passed: false,
+                failed_stage,
+            } => format!(
+                "validation failed at {}",
+                failed_stage.as_deref().unwrap_or("unknown stage")
+            ),
+            Self::CommitCreated { sha, summary } => {
+                format!("commit {} {summary}", &sha[..sha.len().min(7)])
+            }
+            Self::HumanIntervention { description } => format!("human intervention: {description}"),
+            Self::AgentTimedOut { seconds } => format!("agent timed out after {seconds}s"),
+            Self::RunAborted { reason } => format!("run aborted: {reason}"),
+            Self::PlanUpdated { description } => format!("plan updated: {description}"),
+            Self::RequirementMoved { from, to } => format!("moved from '{from}' to '{to}'"),
+            Self::Annotation { iteration, text } => {
+                format!("annotation on iteration {iteration}: {text}")
+            }
+            Self::Hint { text } => format!("hint: {text}"),
+        }
+    }
+}
+
+/// A single event in the ledger
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LedgerEvent {
     /// When the event occurred
@@ -30,15 +161,90 @@ pub struct LedgerEvent {
     pub requirement: String,
     /// Status of the event
     pub status: EventStatus,
+    /// Whether the event records requirement work or a chore
+    #[serde(default, skip_serializing_if = "EventKind::is_requirement")]
+    pub kind: EventKind,
     /// Whether validation passed (if applicable)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub validation_passed: Option<bool>,
     /// Validation output (error messages from failed validation stages)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub validation_output: Option<String>,
+    /// Full validation output file, relative to the ledger's directory (see [`Ledger::full_validation_output`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation_log: Option<String>,
+    /// Implementer agent's output, a file relative to the ledger's directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<String>,
+    /// Implementer agent's session, for resuming it on the requirement's next iteration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_session: Option<String>,
     /// Optional message or details
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+    /// Run labels for experiment comparison (e.g., "prompt-v2")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Input tokens consumed by the agent (if reported)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens: Option<u64>,
+    /// Output tokens produced by the agent (if reported)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_tokens: Option<u64>,
+    /// Model that handled the iteration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Estimated cost in USD (if the model's pricing is known)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_cost: Option<f64>,
+    /// Nondeterministic inputs needed to reproduce the iteration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reproducibility: Option<Reproducibility>,
+    /// SHA-256 of the previous event's JSON line (set when the ledger is hash-chained)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_hash: Option<String>,
+    /// Git HEAD after the iteration, i.e. the code state it produced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_sha: Option<String>,
+    /// Commits the iteration added, as a `base..head` range (None if HEAD didn't move)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff_range: Option<String>,
+    /// Kind-specific details (absent on events written before payloads existed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<EventPayload>,
+}
+
+/// Everything that varies between runs of an otherwise identical iteration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reproducibility {
+    /// Random seed exported to the agent as `RALPH_SEED`
+    pub seed: u64,
+    /// Model that handled the iteration
+    pub model: String,
+    /// Sampling temperature (None when the agent doesn't expose it)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// SHA-256 of the prompt sent to the agent
+    pub prompt_hash: String,
+    /// Git tree hash of HEAD when the iteration started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tree_hash: Option<String>,
+    /// SHA-256 of uncommitted changes when the iteration started (None if clean)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dirty_hash: Option<String>,
+    /// Versions of the tools involved (e.g., "copilot" -> "0.0.350")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_versions: BTreeMap<String, String>,
+}
+
+impl Reproducibility {
+    /// Hex-encoded SHA-256 of arbitrary content (prompts, diffs)
+    #[must_use]
+    pub fn sha256(content: &str) -> String {
+        use sha2::{Digest, Sha256};
+        format!("{:x}", Sha256::digest(content.as_bytes()))
+    }
 }
 
 impl LedgerEvent {
@@ -50,10 +256,105 @@ impl LedgerEvent {
             iteration,
             requirement: requirement.into(),
             status,
+            kind: EventKind::Requirement,
             validation_passed: None,
             validation_output: None,
+            validation_log: None,
+            transcript: None,
+            agent_session: None,
             message: None,
+            labels: Vec::new(),
+            prompt_tokens: None,
+            completion_tokens: None,
+            model: None,
+            estimated_cost: None,
+            reproducibility: None,
+            prev_hash: None,
+            commit_sha: None,
+            diff_range: None,
+            payload: None,
+        }
+    }
+
+    /// Create a chore event with current timestamp
+    #[must_use]
+    pub fn chore(iteration: u32, status: EventStatus) -> Self {
+        Self {
+            kind: EventKind::Chore,
+            ..Self::new(iteration, CHORE_REQUIREMENT, status)
+        }
+    }
+
+    /// Create a human note on a requirement (or on the whole run, with [`RUN_REQUIREMENT`])
+    #[must_use]
+    pub fn note(iteration: u32, requirement: impl Into<String>, description: &str) -> Self {
+        Self {
+            kind: EventKind::Note,
+            ..Self::new(iteration, requirement, EventStatus::InProgress)
+        }
+        .with_message(description)
+        .with_payload(EventPayload::HumanIntervention {
+            description: description.to_string(),
+        })
+    }
+
+    /// Create a human hint for a requirement's next attempt
+    ///
+    /// Hints are notes that only reach the prompt of the attempt after them (see
+    /// [`Ledger::pending_hints`]), not every later one.
+    #[must_use]
+    pub fn hint(iteration: u32, requirement: impl Into<String>, text: &str) -> Self {
+        Self {
+            kind: EventKind::Note,
+            ..Self::new(iteration, requirement, EventStatus::InProgress)
         }
+        .with_message(text)
+        .with_payload(EventPayload::Hint {
+            text: text.to_string(),
+        })
+    }
+
+    /// Create a note that `requirement` was split out of feature `from` into feature `to`
+    ///
+    /// Recorded in both features' ledgers; it's bookkeeping, not work on the requirement.
+    #[must_use]
+    pub fn moved(
+        iteration: u32,
+        requirement: impl Into<String>,
+        from: &str,
+        to: &str,
+        message: &str,
+    ) -> Self {
+        Self {
+            kind: EventKind::Note,
+            ..Self::new(iteration, requirement, EventStatus::InProgress)
+        }
+        .with_message(message)
+        .with_payload(EventPayload::RequirementMoved {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+
+    /// Create an annotation on an earlier iteration, recorded at the current `iteration`
+    ///
+    /// The event keeps the current iteration so iterations only grow through the ledger;
+    /// the annotated one is linked through the payload.
+    #[must_use]
+    pub fn annotation(
+        iteration: u32,
+        requirement: impl Into<String>,
+        annotated_iteration: u32,
+        text: &str,
+    ) -> Self {
+        Self {
+            kind: EventKind::Annotation,
+            ..Self::new(iteration, requirement, EventStatus::InProgress)
+        }
+        .with_payload(EventPayload::Annotation {
+            iteration: annotated_iteration,
+            text: text.to_string(),
+        })
     }
 
     /// Set validation result
@@ -70,12 +371,255 @@ impl LedgerEvent {
         self
     }
 
+    /// Set the sidecar file holding the full validation output
+    #[must_use]
+    pub fn with_validation_log(mut self, path: impl Into<String>) -> Self {
+        self.validation_log = Some(path.into());
+        self
+    }
+
+    /// Set the file holding the implementer agent's output
+    #[must_use]
+    pub fn with_transcript(mut self, path: impl Into<String>) -> Self {
+        self.transcript = Some(path.into());
+        self
+    }
+
+    /// Set the implementer agent's session ID
+    #[must_use]
+    pub fn with_agent_session(mut self, session: impl Into<String>) -> Self {
+        self.agent_session = Some(session.into());
+        self
+    }
+
     /// Set message
     #[must_use]
     pub fn with_message(mut self, message: impl Into<String>) -> Self {
         self.message = Some(message.into());
         self
     }
+
+    /// Set run labels
+    #[must_use]
+    pub fn with_labels<I, S>(mut self, labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.labels = labels.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the reproducibility block
+    #[must_use]
+    pub fn with_reproducibility(mut self, reproducibility: Reproducibility) -> Self {
+        self.reproducibility = Some(reproducibility);
+        self
+    }
+
+    /// Set the commit the iteration produced, and its range from `base` if HEAD moved
+    #[must_use]
+    pub fn with_commit(mut self, head: impl Into<String>, base: Option<&str>) -> Self {
+        let head = head.into();
+        self.diff_range = base
+            .filter(|base| *base != head)
+            .map(|base| format!("{base}..{head}"));
+        self.commit_sha = Some(head);
+        self
+    }
+
+    /// Set the kind-specific payload
+    #[must_use]
+    pub fn with_payload(mut self, payload: EventPayload) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+
+    /// The event's payload, inferred from its status for events that predate payloads
+    ///
+    /// Returns None for legacy in-progress events, whose details live only in `message`.
+    #[must_use]
+    pub fn payload_or_inferred(&self) -> Option<EventPayload> {
+        if let Some(payload) = &self.payload {
+            return Some(payload.clone());
+        }
+        match self.status {
+            EventStatus::Started => Some(EventPayload::IterationStarted { full_tests: None }),
+            EventStatus::Done => Some(EventPayload::IterationFinished { success: true }),
+            EventStatus::Failed => Some(EventPayload::IterationFinished { success: false }),
+            EventStatus::InProgress => None,
+        }
+    }
+
+    /// Set token usage, model, and estimated cost
+    #[must_use]
+    pub fn with_usage(mut self, usage: &TokenUsage) -> Self {
+        self.prompt_tokens = Some(usage.prompt_tokens);
+        self.completion_tokens = Some(usage.completion_tokens);
+        self.model.clone_from(&usage.model);
+        self.estimated_cost = usage.estimated_cost();
+        self
+    }
+}
+
+/// Aggregate token usage and cost across events
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageTotals {
+    /// Total input tokens
+    pub prompt_tokens: u64,
+    /// Total output tokens
+    pub completion_tokens: u64,
+    /// Total estimated cost in USD (events with unknown pricing contribute nothing)
+    pub estimated_cost: f64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, event: &LedgerEvent) {
+        self.prompt_tokens += event.prompt_tokens.unwrap_or(0);
+        self.completion_tokens += event.completion_tokens.unwrap_or(0);
+        self.estimated_cost += event.estimated_cost.unwrap_or(0.0);
+    }
+}
+
+/// Aggregate metrics for events sharing a run label
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelMetrics {
+    /// Distinct iterations recorded under the label
+    pub iterations: usize,
+    /// Iterations that completed a requirement
+    pub done: usize,
+    /// Iterations that failed
+    pub failed: usize,
+}
+
+impl LabelMetrics {
+    /// Fraction of finished iterations that completed a requirement (0.0 if none finished)
+    #[must_use]
+    pub fn success_rate(&self) -> f64 {
+        let finished = self.done + self.failed;
+        if finished == 0 {
+            0.0
+        } else {
+            self.done as f64 / finished as f64
+        }
+    }
+}
+
+/// Point-in-time marker of a ledger's contents, used with [`Ledger::diff`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LedgerSnapshot {
+    len: usize,
+}
+
+impl LedgerSnapshot {
+    /// Number of events the ledger held when the snapshot was taken
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the ledger was empty when the snapshot was taken
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// A point where a hash-chained ledger fails verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainBreak {
+    /// Position of the offending event (0-based)
+    pub index: usize,
+    /// Iteration of the offending event
+    pub iteration: u32,
+    /// Requirement of the offending event
+    pub requirement: String,
+    /// What is wrong with the link
+    pub reason: String,
+}
+
+/// Current ledger file format, recorded in the header line of ledger files
+///
+/// Bump this when `LedgerEvent` changes incompatibly and teach [`migrate_event`] the step.
+pub const LEDGER_VERSION: u32 = 1;
+
+/// Header field marking the first line of a versioned ledger file
+const VERSION_FIELD: &str = "ledgerVersion";
+
+/// Header flag on ledgers rewritten by [`Ledger::save`], whose events are ordered by
+/// timestamp rather than appended with growing iterations
+const REWRITTEN_FIELD: &str = "rewritten";
+
+/// Bytes read per step when scanning a ledger file backwards
+const TAIL_CHUNK: u64 = 8 * 1024;
+
+/// Streaming reader that yields ledger events one JSONL line at a time
+#[derive(Debug)]
+pub struct LedgerReader<R, T = LedgerEvent> {
+    lines: std::io::Lines<R>,
+    line_num: usize,
+    /// Format version from the header line (0 for legacy files); None until the first line
+    version: Option<u32>,
+    event: PhantomData<T>,
+}
+
+impl<R: BufRead, T> LedgerReader<R, T> {
+    /// Read events from any buffered JSONL source
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line_num: 0,
+            version: None,
+            event: PhantomData,
+        }
+    }
+
+    /// Format version of the source, known once the first event has been read
+    #[must_use]
+    pub fn version(&self) -> Option<u32> {
+        self.version
+    }
+}
+
+impl<R: BufRead, T: DeserializeOwned> Iterator for LedgerReader<R, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e.into())),
+            };
+            self.line_num += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let parse_error =
+                |e| RalphError::Ledger(format!("Failed to parse line {}: {}", self.line_num, e));
+            let value: serde_json::Value = match serde_json::from_str(&line) {
+                Ok(value) => value,
+                Err(e) => return Some(Err(parse_error(e))),
+            };
+            let version = match self.version {
+                Some(version) => version,
+                None => {
+                    let header = header_version(&value);
+                    self.version = Some(header.unwrap_or(0));
+                    if let Some(version) = header {
+                        if let Err(e) = check_version(version) {
+                            return Some(Err(e));
+                        }
+                        continue;
+                    }
+                    0
+                }
+            };
+            return Some(
+                serde_json::from_value(migrate_event(value, version)).map_err(parse_error),
+            );
+        }
+    }
 }
 
 /// Append-only ledger for implementation events
@@ -83,6 +627,11 @@ impl LedgerEvent {
 pub struct Ledger {
     path: Option<std::path::PathBuf>,
     events: Vec<LedgerEvent>,
+    /// Positions in `events` per requirement, kept in step with every mutation
+    by_requirement: HashMap<String, Vec<usize>>,
+    hash_chain: bool,
+    sync: Option<LedgerSync>,
+    notifier: Option<Notifier>,
 }
 
 impl Ledger {
@@ -92,6 +641,10 @@ impl Ledger {
         Self {
             path: None,
             events: Vec::new(),
+            by_requirement: HashMap::new(),
+            hash_chain: false,
+            sync: None,
+            notifier: None,
         }
     }
 
@@ -102,27 +655,115 @@ impl Ledger {
     /// Returns an error if the file cannot be read or contains invalid JSON.
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref();
-        let mut events = Vec::new();
+        let events = if path.exists() {
+            parse_jsonl(BufReader::new(File::open(path)?))?
+        } else {
+            Vec::new()
+        };
 
-        if path.exists() {
-            let file = File::open(path)?;
-            let reader = BufReader::new(file);
+        // Keep extending a chain once one has been started
+        let hash_chain = events.last().is_some_and(|e| e.prev_hash.is_some());
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            by_requirement: index_by_requirement(&events),
+            events,
+            hash_chain,
+            sync: None,
+            notifier: None,
+        })
+    }
 
-            for (line_num, line) in reader.lines().enumerate() {
-                let line = line?;
-                if line.trim().is_empty() {
-                    continue;
-                }
-                let event: LedgerEvent = serde_json::from_str(&line).map_err(|e| {
-                    RalphError::Ledger(format!("Failed to parse line {}: {}", line_num + 1, e))
-                })?;
-                events.push(event);
+    /// Stream events from a JSONL ledger file without loading them all into memory
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened.
+    pub fn stream(path: impl AsRef<Path>) -> Result<LedgerReader<BufReader<File>>> {
+        Ok(LedgerReader::new(BufReader::new(File::open(path)?)))
+    }
+
+    /// Latest iteration in a JSONL ledger, reading only the end of the file when it can
+    ///
+    /// Returns 0 if the file is missing or holds no events. Iterations only grow as events
+    /// are appended, so the last event has the latest one. A ledger rewritten by a merge is
+    /// ordered by timestamp instead, so it is scanned in full.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or its last line is invalid JSON.
+    pub fn latest_iteration_fast(path: impl AsRef<Path>) -> Result<u32> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(0);
+        }
+
+        // Migrations depend on the header at the top of the file
+        let mut first_line = String::new();
+        BufReader::new(File::open(path)?).read_line(&mut first_line)?;
+        let header: Option<serde_json::Value> = serde_json::from_str(&first_line).ok();
+        let version = header.as_ref().and_then(header_version).unwrap_or(0);
+        check_version(version)?;
+        let rewritten = header
+            .as_ref()
+            .and_then(|h| h.get(REWRITTEN_FIELD))
+            .and_then(serde_json::Value::as_bool);
+        if version > 0 && rewritten == Some(true) {
+            return Self::stream(path)?
+                .try_fold(0, |latest, event| Ok(latest.max(event?.iteration)));
+        }
+
+        let mut file = File::open(path)?;
+        let mut end = file.metadata()?.len();
+        let mut tail: Vec<u8> = Vec::new();
+        while end > 0 {
+            let start = end.saturating_sub(TAIL_CHUNK);
+            let mut chunk = vec![0; usize::try_from(end - start).unwrap_or_default()];
+            file.seek(SeekFrom::Start(start))?;
+            file.read_exact(&mut chunk)?;
+            chunk.extend_from_slice(&tail);
+            tail = chunk;
+            end = start;
+
+            // Done once the last non-blank line is known to be complete
+            let content_end = tail
+                .iter()
+                .rposition(|b| !b.is_ascii_whitespace())
+                .map_or(0, |i| i + 1);
+            if content_end > 0 && (end == 0 || tail[..content_end].contains(&b'\n')) {
+                break;
             }
         }
 
+        let content = String::from_utf8_lossy(&tail);
+        let Some(line) = content.lines().rev().find(|line| !line.trim().is_empty()) else {
+            return Ok(0);
+        };
+        let parse_error =
+            |e| RalphError::Ledger(format!("Failed to parse last line of ledger: {e}"));
+        let value: serde_json::Value = serde_json::from_str(line).map_err(parse_error)?;
+        if header_version(&value).is_some() {
+            return Ok(0);
+        }
+        let event: LedgerEvent =
+            serde_json::from_value(migrate_event(value, version)).map_err(parse_error)?;
+        Ok(event.iteration)
+    }
+
+    /// Parse JSONL ledger content into an in-memory ledger (e.g., from `git show`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a line contains invalid JSON.
+    pub fn from_jsonl(content: &str) -> Result<Self> {
+        let events = parse_jsonl(content.as_bytes())?;
+        let hash_chain = events.last().is_some_and(|e| e.prev_hash.is_some());
         Ok(Self {
-            path: Some(path.to_path_buf()),
+            path: None,
+            by_requirement: index_by_requirement(&events),
             events,
+            hash_chain,
+            sync: None,
+            notifier: None,
         })
     }
 
@@ -142,32 +783,243 @@ impl Ledger {
         Ok(Self {
             path: Some(path.to_path_buf()),
             events: Vec::new(),
+            by_requirement: HashMap::new(),
+            hash_chain: false,
+            sync: None,
+            notifier: None,
         })
     }
 
+    /// Link every subsequently appended event to its predecessor by hash
+    ///
+    /// Earlier events are left as they are; the chain starts at the next append.
+    pub fn enable_hash_chain(&mut self) {
+        self.hash_chain = true;
+    }
+
+    /// Whether appended events are hash-chained
+    #[must_use]
+    pub fn is_hash_chained(&self) -> bool {
+        self.hash_chain
+    }
+
+    /// Push every subsequently appended event to remote targets
+    pub fn enable_sync(&mut self, sync: LedgerSync) {
+        self.sync = Some(sync);
+    }
+
+    /// Remote sync state, if enabled
+    #[must_use]
+    pub fn sync(&self) -> Option<&LedgerSync> {
+        self.sync.as_ref()
+    }
+
+    /// Send notifications for subsequently appended events (failed iterations, finished
+    /// requirements)
+    pub fn enable_notify(&mut self, notifier: Notifier) {
+        self.notifier = Some(notifier);
+    }
+
+    /// Notification state, if enabled
+    #[must_use]
+    pub fn notifier_mut(&mut self) -> Option<&mut Notifier> {
+        self.notifier.as_mut()
+    }
+
+    /// Retry delivery of events queued for remote sync, returning how many remain queued
+    pub fn flush_sync(&mut self) -> usize {
+        self.sync.as_mut().map_or(0, LedgerSync::flush)
+    }
+
+    /// Hash of the last event, which the next chained event will record
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the last event cannot be serialized.
+    pub fn head_hash(&self) -> Result<String> {
+        match self.events.last() {
+            Some(event) => event_hash(event),
+            None => Ok(GENESIS_HASH.to_string()),
+        }
+    }
+
+    /// Check every link of the hash chain, returning the breaks found
+    ///
+    /// Events written before the chain was enabled are accepted; from the first
+    /// chained event on, every event must carry its predecessor's hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an event cannot be serialized.
+    pub fn verify_chain(&self) -> Result<Vec<ChainBreak>> {
+        let mut breaks = Vec::new();
+        let Some(start) = self.events.iter().position(|e| e.prev_hash.is_some()) else {
+            return Ok(breaks);
+        };
+
+        for index in start..self.events.len() {
+            let event = &self.events[index];
+            let expected = match index {
+                0 => GENESIS_HASH.to_string(),
+                _ => event_hash(&self.events[index - 1])?,
+            };
+            let reason = match &event.prev_hash {
+                None => Some("missing previous-event hash".to_string()),
+                Some(actual) if *actual != expected => Some(format!(
+                    "previous-event hash {actual} does not match {expected}"
+                )),
+                Some(_) => None,
+            };
+            if let Some(reason) = reason {
+                breaks.push(ChainBreak {
+                    index,
+                    iteration: event.iteration,
+                    requirement: event.requirement.clone(),
+                    reason,
+                });
+            }
+        }
+        Ok(breaks)
+    }
+
     /// Get all events
     #[must_use]
     pub fn events(&self) -> &[LedgerEvent] {
         &self.events
     }
 
+    /// Mark the current end of the ledger
+    #[must_use]
+    pub fn snapshot(&self) -> LedgerSnapshot {
+        LedgerSnapshot {
+            len: self.events.len(),
+        }
+    }
+
+    /// Get events appended since the snapshot was taken
+    #[must_use]
+    pub fn diff(&self, snapshot: &LedgerSnapshot) -> &[LedgerEvent] {
+        &self.events[snapshot.len.min(self.events.len())..]
+    }
+
     /// Append a new event to the ledger
     ///
     /// # Errors
     ///
     /// Returns an error if the event cannot be serialized or written to the file.
     pub fn append(&mut self, event: LedgerEvent) -> Result<()> {
-        // First, append to file atomically if we have a path
+        self.append_batch(std::slice::from_ref(&event))
+    }
+
+    /// Append several events with a single write, all or nothing
+    ///
+    /// If the write fails partway, the file is truncated back to its previous length and the
+    /// in-memory ledger is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an event cannot be serialized or the file cannot be written.
+    pub fn append_batch(&mut self, events: &[LedgerEvent]) -> Result<()> {
+        let mut prev_hash = if self.hash_chain {
+            Some(self.head_hash()?)
+        } else {
+            None
+        };
+        let mut batch = Vec::with_capacity(events.len());
+        let mut lines = String::new();
+        for event in events {
+            let mut event = event.clone();
+            if let Some(hash) = prev_hash.take() {
+                event.prev_hash = Some(hash);
+            }
+            let json = serde_json::to_string(&event)?;
+            if self.hash_chain {
+                prev_hash = Some(Reproducibility::sha256(&json));
+            }
+            lines.push_str(&json);
+            lines.push('\n');
+            batch.push(event);
+        }
+
+        // First, write every line to the file in one go if we have a path
         if let Some(ref path) = self.path {
             let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            let original_len = file.metadata()?.len();
+            if original_len == 0 {
+                lines.insert_str(0, &format!("{}\n", version_header()));
+            }
+            if let Err(e) = file.write_all(lines.as_bytes()).and_then(|()| file.flush()) {
+                // Drop a partial write so the file still matches the in-memory ledger
+                let _ = file.set_len(original_len);
+                return Err(e.into());
+            }
+        }
 
-            let json = serde_json::to_string(&event)?;
-            writeln!(file, "{json}")?;
-            file.flush()?;
+        for event in batch {
+            // Remote sync is best-effort: failures queue the event instead of failing the append
+            if let Some(sync) = &mut self.sync {
+                sync.push(&event);
+            }
+
+            // Then add to in-memory list
+            self.by_requirement
+                .entry(event.requirement.clone())
+                .or_default()
+                .push(self.events.len());
+            self.events.push(event);
+        }
+
+        // Notifications are best-effort too, and see the ledger including the new events
+        if let Some(mut notifier) = self.notifier.take() {
+            let start = self.events.len() - events.len();
+            for index in start..self.events.len() {
+                notifier.observe(&self.events[index], self);
+            }
+            self.notifier = Some(notifier);
+        }
+        Ok(())
+    }
+
+    /// Union another ledger's events into this one, e.g. after a rebase or work on two machines
+    ///
+    /// Events are deduplicated by (timestamp, iteration, requirement) and ordered by that
+    /// key, so merging in either direction yields the same ledger. The merge is in-memory;
+    /// call [`Ledger::save`] to persist it. Returns the number of events added.
+    pub fn merge(&mut self, other: &Ledger) -> usize {
+        let before = self.events.len();
+        let mut seen: HashSet<(DateTime<Utc>, u32, String)> =
+            self.events.iter().map(merge_key).collect();
+        for event in &other.events {
+            if seen.insert(merge_key(event)) {
+                self.events.push(event.clone());
+            }
         }
+        self.events.sort_by_key(merge_key);
+        self.by_requirement = index_by_requirement(&self.events);
+        self.events.len() - before
+    }
 
-        // Then add to in-memory list
-        self.events.push(event);
+    /// Rewrite the whole ledger to a JSONL file, replacing it atomically
+    ///
+    /// Unlike [`Ledger::append`] this rewrites history; use it only to persist a merge.
+    /// The header marks the file as rewritten, since its events may no longer be in
+    /// iteration order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an event cannot be serialized or the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let mut content =
+            serde_json::json!({ VERSION_FIELD: LEDGER_VERSION, REWRITTEN_FIELD: true }).to_string();
+        content.push('\n');
+        for event in &self.events {
+            content.push_str(&serde_json::to_string(event)?);
+            content.push('\n');
+        }
+        let tmp = path.with_extension("jsonl.tmp");
+        std::fs::write(&tmp, content)?;
+        std::fs::rename(&tmp, path)?;
         Ok(())
     }
 
@@ -180,18 +1032,135 @@ impl Ledger {
     /// Get events for a specific requirement
     #[must_use]
     pub fn events_for_requirement(&self, req_id: &str) -> Vec<&LedgerEvent> {
-        self.events
-            .iter()
-            .filter(|e| e.requirement == req_id)
-            .collect()
+        self.by_requirement
+            .get(req_id)
+            .map(|indexes| indexes.iter().map(|&i| &self.events[i]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get the agent session of a requirement's latest finished iteration, to resume it
+    ///
+    /// Returns None if that iteration recorded no session, so a session is only carried
+    /// over from the attempt immediately before.
+    #[must_use]
+    pub fn last_agent_session(&self, req_id: &str) -> Option<String> {
+        self.events_for_requirement(req_id)
+            .into_iter()
+            .rev()
+            .find(|e| {
+                matches!(
+                    e.payload_or_inferred(),
+                    Some(EventPayload::IterationFinished { .. })
+                )
+            })
+            .and_then(|e| e.agent_session.clone())
+    }
+
+    /// Get commits produced by iterations on a requirement, oldest first
+    ///
+    /// Only events whose iteration moved HEAD are included, each commit once.
+    #[must_use]
+    pub fn commits_for_requirement(&self, req_id: &str) -> Vec<&str> {
+        let mut commits: Vec<&str> = Vec::new();
+        for event in self.events_for_requirement(req_id) {
+            if let (Some(sha), Some(_)) = (&event.commit_sha, &event.diff_range) {
+                if !commits.contains(&sha.as_str()) {
+                    commits.push(sha);
+                }
+            }
+        }
+        commits
+    }
+
+    /// Get the event that produced a commit (matching a full SHA or an abbreviated prefix)
+    #[must_use]
+    pub fn event_for_commit(&self, sha: &str) -> Option<&LedgerEvent> {
+        if sha.is_empty() {
+            return None;
+        }
+        self.events.iter().find(|e| {
+            e.diff_range.is_some()
+                && e.commit_sha
+                    .as_deref()
+                    .is_some_and(|commit| commit.starts_with(sha))
+        })
     }
 
     /// Check if the last event for a requirement was a failure
     #[must_use]
     pub fn is_requirement_failed(&self, req_id: &str) -> bool {
+        // Notes and annotations describe the record; they don't change its outcome
+        self.by_requirement
+            .get(req_id)
+            .and_then(|indexes| {
+                indexes
+                    .iter()
+                    .rev()
+                    .find(|&&i| self.events[i].kind.is_requirement())
+            })
+            .is_some_and(|&i| self.events[i].status == EventStatus::Failed)
+    }
+
+    /// Check if the last finished iteration for a requirement was a no-op
+    #[must_use]
+    pub fn is_last_iteration_noop(&self, req_id: &str) -> bool {
         self.events_for_requirement(req_id)
-            .last()
-            .is_some_and(|e| e.status == EventStatus::Failed)
+            .iter()
+            .rev()
+            .find(|e| e.kind.is_requirement() && e.status != EventStatus::Started)
+            .is_some_and(|e| e.message.as_deref() == Some(NO_OP_MESSAGE))
+    }
+
+    /// Write an iteration's full validation output to `artifacts/<iteration>-validation.log`
+    /// next to the ledger, returning its path relative to the ledger's directory
+    ///
+    /// Returns `None` for in-memory ledgers, which have nowhere to put the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the artifacts directory or the file cannot be written.
+    pub fn save_validation_log(&self, iteration: u32, output: &str) -> Result<Option<String>> {
+        self.save_artifact(&format!("{iteration}-validation.log"), output)
+    }
+
+    /// Write an iteration's implementer agent output to `artifacts/<iteration>-agent.log`
+    /// next to the ledger, returning its path relative to the ledger's directory
+    ///
+    /// Returns `None` for in-memory ledgers, which have nowhere to put the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the artifacts directory or the file cannot be written.
+    pub fn save_transcript(&self, iteration: u32, transcript: &str) -> Result<Option<String>> {
+        self.save_artifact(&format!("{iteration}-agent.log"), transcript)
+    }
+
+    fn save_artifact(&self, name: &str, content: &str) -> Result<Option<String>> {
+        let Some(dir) = self.path.as_deref().and_then(Path::parent) else {
+            return Ok(None);
+        };
+        let relative = format!("artifacts/{name}");
+        let path = dir.join(&relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, content)?;
+        Ok(Some(relative))
+    }
+
+    /// Full validation output of an event, read from its sidecar log when it has one
+    ///
+    /// Falls back to the inline (summarized) output if there is no sidecar log.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event's sidecar log cannot be read.
+    pub fn full_validation_output(&self, event: &LedgerEvent) -> Result<Option<String>> {
+        let dir = self.path.as_deref().and_then(Path::parent);
+        match (dir, &event.validation_log) {
+            (Some(dir), Some(log)) => Ok(Some(std::fs::read_to_string(dir.join(log))?)),
+            _ => Ok(event.validation_output.clone()),
+        }
     }
 
     /// Get validation output from the most recent failed iteration for a requirement
@@ -206,6 +1175,77 @@ impl Ledger {
             .and_then(|e| e.validation_output.clone())
     }
 
+    /// Get audit findings (e.g., commits that bypassed the commit-msg hook)
+    #[must_use]
+    pub fn audit_events(&self) -> Vec<&LedgerEvent> {
+        self.events_for_requirement(AUDIT_REQUIREMENT)
+    }
+
+    /// Get chore events (iterations not tied to a requirement)
+    #[must_use]
+    pub fn chore_events(&self) -> Vec<&LedgerEvent> {
+        self.events
+            .iter()
+            .filter(|e| e.kind == EventKind::Chore)
+            .collect()
+    }
+
+    /// Get human notes that apply to a requirement, including run-wide notes, oldest first
+    ///
+    /// Hints are left out; see [`Ledger::pending_hints`].
+    #[must_use]
+    pub fn notes_for_requirement(&self, req_id: &str) -> Vec<&LedgerEvent> {
+        self.events
+            .iter()
+            .filter(|e| e.kind == EventKind::Note)
+            .filter(|e| {
+                !matches!(
+                    e.payload,
+                    Some(EventPayload::Hint { .. } | EventPayload::RequirementMoved { .. })
+                )
+            })
+            .filter(|e| e.requirement == req_id || e.requirement == RUN_REQUIREMENT)
+            .collect()
+    }
+
+    /// Get hints given for a requirement since its latest finished iteration, oldest first
+    ///
+    /// These are for the attempt in progress or about to start; once it finishes they no
+    /// longer apply.
+    #[must_use]
+    pub fn pending_hints(&self, req_id: &str) -> Vec<&LedgerEvent> {
+        let events = self.events_for_requirement(req_id);
+        let since = events
+            .iter()
+            .rposition(|e| {
+                e.kind.is_requirement()
+                    && matches!(
+                        e.payload_or_inferred(),
+                        Some(EventPayload::IterationFinished { .. })
+                    )
+            })
+            .map_or(0, |i| i + 1);
+        events[since..]
+            .iter()
+            .copied()
+            .filter(|e| matches!(e.payload, Some(EventPayload::Hint { .. })))
+            .collect()
+    }
+
+    /// Get annotations attached to an iteration, oldest first
+    #[must_use]
+    pub fn annotations_for_iteration(&self, iteration: u32) -> Vec<&LedgerEvent> {
+        self.events
+            .iter()
+            .filter(|e| {
+                matches!(
+                    e.payload,
+                    Some(EventPayload::Annotation { iteration: annotated, .. }) if annotated == iteration
+                )
+            })
+            .collect()
+    }
+
     /// Get the count of iterations where full tests were run
     #[must_use]
     pub fn full_test_count(&self) -> usize {
@@ -216,6 +1256,102 @@ impl Ledger {
             .count()
     }
 
+    /// Aggregate iteration outcomes per run label, for comparing experiments
+    #[must_use]
+    pub fn metrics_by_label(&self) -> BTreeMap<String, LabelMetrics> {
+        let mut metrics: BTreeMap<String, LabelMetrics> = BTreeMap::new();
+        let mut seen: HashSet<(&str, u32)> = HashSet::new();
+
+        for event in &self.events {
+            for label in &event.labels {
+                let entry = metrics.entry(label.clone()).or_default();
+                if seen.insert((label.as_str(), event.iteration)) {
+                    entry.iterations += 1;
+                }
+                match event.status {
+                    EventStatus::Done => entry.done += 1,
+                    EventStatus::Failed => entry.failed += 1,
+                    EventStatus::Started | EventStatus::InProgress => {}
+                }
+            }
+        }
+        metrics
+    }
+
+    /// Total token usage and estimated cost across all events
+    #[must_use]
+    pub fn usage_totals(&self) -> UsageTotals {
+        let mut totals = UsageTotals::default();
+        for event in &self.events {
+            totals.add(event);
+        }
+        totals
+    }
+
+    /// Token usage and estimated cost of the events recorded at or after `since`
+    #[must_use]
+    pub fn usage_since(&self, since: DateTime<Utc>) -> UsageTotals {
+        let mut totals = UsageTotals::default();
+        for event in self.events.iter().filter(|e| e.timestamp >= since) {
+            totals.add(event);
+        }
+        totals
+    }
+
+    /// Token usage and estimated cost grouped by model
+    #[must_use]
+    pub fn usage_by_model(&self) -> BTreeMap<String, UsageTotals> {
+        let mut by_model: BTreeMap<String, UsageTotals> = BTreeMap::new();
+        for event in &self.events {
+            if let Some(model) = &event.model {
+                by_model.entry(model.clone()).or_default().add(event);
+            }
+        }
+        by_model
+    }
+
+    /// Export events as a flat CSV table (header row included) for spreadsheet analysis
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "timestamp,iteration,requirement,status,validation_passed,message,labels,\
+             prompt_tokens,completion_tokens,model,estimated_cost\n",
+        );
+        for event in &self.events {
+            let status = match event.status {
+                EventStatus::Started => "started",
+                EventStatus::InProgress => "in_progress",
+                EventStatus::Done => "done",
+                EventStatus::Failed => "failed",
+            };
+            let row = [
+                event.timestamp.to_rfc3339(),
+                event.iteration.to_string(),
+                event.requirement.clone(),
+                status.to_string(),
+                event
+                    .validation_passed
+                    .map_or_else(String::new, |v| v.to_string()),
+                event.message.clone().unwrap_or_default(),
+                event.labels.join(";"),
+                event
+                    .prompt_tokens
+                    .map_or_else(String::new, |t| t.to_string()),
+                event
+                    .completion_tokens
+                    .map_or_else(String::new, |t| t.to_string()),
+                event.model.clone().unwrap_or_default(),
+                event
+                    .estimated_cost
+                    .map_or_else(String::new, |c| format!("{c:.6}")),
+            ];
+            let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
     /// Export ledger to AVRO format for schema evolution
     ///
     /// # Errors
@@ -245,6 +1381,59 @@ impl Ledger {
                     EventStatus::Failed => "failed",
                 },
             );
+            record.put(
+                "commitSha",
+                event
+                    .commit_sha
+                    .clone()
+                    .map(apache_avro::types::Value::String),
+            );
+            record.put(
+                "validationLog",
+                event
+                    .validation_log
+                    .clone()
+                    .map(apache_avro::types::Value::String),
+            );
+            record.put(
+                "transcript",
+                event
+                    .transcript
+                    .clone()
+                    .map(apache_avro::types::Value::String),
+            );
+            record.put(
+                "agentSession",
+                event
+                    .agent_session
+                    .clone()
+                    .map(apache_avro::types::Value::String),
+            );
+            record.put(
+                "diffRange",
+                event
+                    .diff_range
+                    .clone()
+                    .map(apache_avro::types::Value::String),
+            );
+            record.put(
+                "payload",
+                event
+                    .payload
+                    .as_ref()
+                    .map(serde_json::to_string)
+                    .transpose()?
+                    .map(apache_avro::types::Value::String),
+            );
+            record.put(
+                "kind",
+                match event.kind {
+                    EventKind::Requirement => "requirement",
+                    EventKind::Chore => "chore",
+                    EventKind::Note => "note",
+                    EventKind::Annotation => "annotation",
+                },
+            );
             record.put(
                 "validationPassed",
                 event
@@ -262,6 +1451,42 @@ impl Ledger {
                 "message",
                 event.message.clone().map(apache_avro::types::Value::String),
             );
+            record.put(
+                "labels",
+                apache_avro::types::Value::Array(
+                    event
+                        .labels
+                        .iter()
+                        .cloned()
+                        .map(apache_avro::types::Value::String)
+                        .collect(),
+                ),
+            );
+            let token_count = |tokens: Option<u64>| {
+                tokens
+                    .map(|t| apache_avro::types::Value::Long(i64::try_from(t).unwrap_or(i64::MAX)))
+            };
+            record.put("promptTokens", token_count(event.prompt_tokens));
+            record.put("completionTokens", token_count(event.completion_tokens));
+            record.put(
+                "model",
+                event.model.clone().map(apache_avro::types::Value::String),
+            );
+            record.put(
+                "estimatedCost",
+                event.estimated_cost.map(apache_avro::types::Value::Double),
+            );
+            record.put(
+                "reproducibility",
+                event.reproducibility.as_ref().map(reproducibility_to_avro),
+            );
+            record.put(
+                "prevHash",
+                event
+                    .prev_hash
+                    .clone()
+                    .map(apache_avro::types::Value::String),
+            );
 
             writer
                 .append(record)
@@ -283,6 +1508,261 @@ impl Ledger {
         std::fs::write(path, data)?;
         Ok(())
     }
+
+    /// Load an archived AVRO ledger, resolving its writer schema against the current one
+    ///
+    /// Fields added since the archive was written take their schema defaults. The
+    /// returned ledger is in-memory: appends are not written back to the archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, its schema is incompatible, or a
+    /// record cannot be converted to an event.
+    pub fn from_avro(path: impl AsRef<Path>) -> Result<Self> {
+        use apache_avro::{Reader, Schema};
+
+        let schema = Schema::parse_str(LEDGER_AVRO_SCHEMA)
+            .map_err(|e| RalphError::Ledger(format!("Invalid AVRO schema: {e}")))?;
+        let file = File::open(path.as_ref())?;
+        let reader = Reader::with_schema(&schema, BufReader::new(file))
+            .map_err(|e| RalphError::Ledger(format!("Failed to read AVRO ledger: {e}")))?;
+
+        let mut events = Vec::new();
+        for (index, value) in reader.enumerate() {
+            let value = value.map_err(|e| {
+                RalphError::Ledger(format!("Failed to read AVRO record {}: {e}", index + 1))
+            })?;
+            events.push(event_from_avro(value).map_err(|e| {
+                RalphError::Ledger(format!("Invalid AVRO record {}: {e}", index + 1))
+            })?);
+        }
+
+        let hash_chain = events.last().is_some_and(|e| e.prev_hash.is_some());
+        Ok(Self {
+            path: None,
+            by_requirement: index_by_requirement(&events),
+            events,
+            hash_chain,
+            sync: None,
+            notifier: None,
+        })
+    }
+}
+
+/// Convert a reproducibility block into its AVRO record value
+fn reproducibility_to_avro(repro: &Reproducibility) -> apache_avro::types::Value {
+    use apache_avro::types::Value;
+
+    let optional_string = |value: &Option<String>| Value::from(value.clone().map(Value::String));
+    Value::Record(vec![
+        (
+            "seed".to_string(),
+            Value::Long(i64::try_from(repro.seed).unwrap_or(i64::MAX)),
+        ),
+        ("model".to_string(), Value::String(repro.model.clone())),
+        (
+            "temperature".to_string(),
+            Value::from(repro.temperature.map(Value::Double)),
+        ),
+        (
+            "promptHash".to_string(),
+            Value::String(repro.prompt_hash.clone()),
+        ),
+        ("treeHash".to_string(), optional_string(&repro.tree_hash)),
+        ("dirtyHash".to_string(), optional_string(&repro.dirty_hash)),
+        (
+            "toolVersions".to_string(),
+            Value::Map(
+                repro
+                    .tool_versions
+                    .iter()
+                    .map(|(tool, version)| (tool.clone(), Value::String(version.clone())))
+                    .collect(),
+            ),
+        ),
+    ])
+}
+
+/// Convert a schema-resolved AVRO record into a reproducibility block
+fn reproducibility_from_avro(fields: Vec<(String, apache_avro::types::Value)>) -> Reproducibility {
+    use apache_avro::types::Value;
+
+    let mut repro = Reproducibility::default();
+    for (name, value) in fields {
+        let value = match value {
+            Value::Union(_, inner) => *inner,
+            other => other,
+        };
+        match (name.as_str(), value) {
+            ("seed", Value::Long(seed)) => repro.seed = u64::try_from(seed).unwrap_or_default(),
+            ("model", Value::String(model)) => repro.model = model,
+            ("temperature", Value::Double(t)) => repro.temperature = Some(t),
+            ("promptHash", Value::String(hash)) => repro.prompt_hash = hash,
+            ("treeHash", Value::String(hash)) => repro.tree_hash = Some(hash),
+            ("dirtyHash", Value::String(hash)) => repro.dirty_hash = Some(hash),
+            ("toolVersions", Value::Map(versions)) => {
+                repro.tool_versions = versions
+                    .into_iter()
+                    .filter_map(|(tool, version)| match version {
+                        Value::String(version) => Some((tool, version)),
+                        _ => None,
+                    })
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+    repro
+}
+
+/// Convert a schema-resolved AVRO record into a ledger event
+fn event_from_avro(value: apache_avro::types::Value) -> Result<LedgerEvent> {
+    use apache_avro::types::Value;
+
+    let Value::Record(fields) = value else {
+        return Err(RalphError::Ledger("expected a record".to_string()));
+    };
+
+    let mut event = LedgerEvent::new(0, "", EventStatus::Started);
+    for (name, value) in fields {
+        let value = match value {
+            Value::Union(_, inner) => *inner,
+            other => other,
+        };
+        match (name.as_str(), value) {
+            ("timestamp", Value::String(ts)) => {
+                event.timestamp = DateTime::parse_from_rfc3339(&ts)
+                    .map_err(|e| RalphError::Ledger(format!("bad timestamp '{ts}': {e}")))?
+                    .with_timezone(&Utc);
+            }
+            ("iteration", Value::Long(n)) => {
+                event.iteration = u32::try_from(n)
+                    .map_err(|_| RalphError::Ledger(format!("bad iteration {n}")))?;
+            }
+            ("requirement", Value::String(req)) => event.requirement = req,
+            ("status", Value::Enum(_, status)) => {
+                event.status = match status.as_str() {
+                    "started" => EventStatus::Started,
+                    "in_progress" => EventStatus::InProgress,
+                    "done" => EventStatus::Done,
+                    "failed" => EventStatus::Failed,
+                    other => return Err(RalphError::Ledger(format!("bad status '{other}'"))),
+                };
+            }
+            ("kind", Value::Enum(_, kind)) => {
+                event.kind = match kind.as_str() {
+                    "requirement" => EventKind::Requirement,
+                    "chore" => EventKind::Chore,
+                    "note" => EventKind::Note,
+                    "annotation" => EventKind::Annotation,
+                    other => return Err(RalphError::Ledger(format!("bad kind '{other}'"))),
+                };
+            }
+            ("validationPassed", Value::Boolean(passed)) => event.validation_passed = Some(passed),
+            ("validationOutput", Value::String(output)) => event.validation_output = Some(output),
+            ("message", Value::String(message)) => event.message = Some(message),
+            ("labels", Value::Array(labels)) => {
+                event.labels = labels
+                    .into_iter()
+                    .filter_map(|label| match label {
+                        Value::String(label) => Some(label),
+                        _ => None,
+                    })
+                    .collect();
+            }
+            ("promptTokens", Value::Long(n)) => event.prompt_tokens = u64::try_from(n).ok(),
+            ("completionTokens", Value::Long(n)) => {
+                event.completion_tokens = u64::try_from(n).ok();
+            }
+            ("model", Value::String(model)) => event.model = Some(model),
+            ("estimatedCost", Value::Double(cost)) => event.estimated_cost = Some(cost),
+            ("reproducibility", Value::Record(fields)) => {
+                event.reproducibility = Some(reproducibility_from_avro(fields));
+            }
+            ("prevHash", Value::String(hash)) => event.prev_hash = Some(hash),
+            ("payload", Value::String(payload)) => {
+                event.payload = Some(serde_json::from_str(&payload)?);
+            }
+            ("commitSha", Value::String(sha)) => event.commit_sha = Some(sha),
+            ("diffRange", Value::String(range)) => event.diff_range = Some(range),
+            ("validationLog", Value::String(log)) => event.validation_log = Some(log),
+            ("transcript", Value::String(path)) => event.transcript = Some(path),
+            ("agentSession", Value::String(session)) => event.agent_session = Some(session),
+            // Nulls, and fields from newer writers that this reader doesn't know
+            _ => {}
+        }
+    }
+    Ok(event)
+}
+
+/// Parse JSONL ledger lines, skipping blank ones
+fn parse_jsonl(reader: impl BufRead) -> Result<Vec<LedgerEvent>> {
+    LedgerReader::new(reader).collect()
+}
+
+/// Header line written at the top of new ledger files
+fn version_header() -> String {
+    serde_json::json!({ VERSION_FIELD: LEDGER_VERSION }).to_string()
+}
+
+/// Version recorded by a header line, or None if the line is an event
+fn header_version(value: &serde_json::Value) -> Option<u32> {
+    let object = value.as_object()?;
+    if object.contains_key("timestamp") {
+        return None;
+    }
+    object
+        .get(VERSION_FIELD)?
+        .as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+}
+
+/// Refuse ledgers written by a newer Ralph instead of misreading them
+fn check_version(version: u32) -> Result<()> {
+    if version > LEDGER_VERSION {
+        return Err(RalphError::Ledger(format!(
+            "Ledger format version {version} is newer than supported version {LEDGER_VERSION}; upgrade ralph"
+        )));
+    }
+    Ok(())
+}
+
+/// Upgrade an event written in format `version` to the current format, one step at a time
+fn migrate_event(value: serde_json::Value, version: u32) -> serde_json::Value {
+    if version >= LEDGER_VERSION {
+        return value;
+    }
+    // 0 -> 1 only added the header line, so events need no rewriting yet. Later steps go
+    // here (e.g., `if version < 2 { rename a field }`), oldest first.
+    value
+}
+
+/// Map each requirement to the positions of its events, in ledger order
+fn index_by_requirement(events: &[LedgerEvent]) -> HashMap<String, Vec<usize>> {
+    let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, event) in events.iter().enumerate() {
+        index.entry(event.requirement.clone()).or_default().push(i);
+    }
+    index
+}
+
+/// Identity of an event when merging ledgers
+fn merge_key(event: &LedgerEvent) -> (DateTime<Utc>, u32, String) {
+    (event.timestamp, event.iteration, event.requirement.clone())
+}
+
+/// Hash an event exactly as it is written to the JSONL ledger
+fn event_hash(event: &LedgerEvent) -> Result<String> {
+    Ok(Reproducibility::sha256(&serde_json::to_string(event)?))
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
 }
 
 /// AVRO schema for ledger events
@@ -297,7 +1777,33 @@ pub const LEDGER_AVRO_SCHEMA: &str = r#"{
         {"name": "status", "type": {"type": "enum", "name": "EventStatus", "symbols": ["started", "in_progress", "done", "failed"]}},
         {"name": "validationPassed", "type": ["null", "boolean"], "default": null},
         {"name": "validationOutput", "type": ["null", "string"], "default": null},
-        {"name": "message", "type": ["null", "string"], "default": null}
+        {"name": "message", "type": ["null", "string"], "default": null},
+        {"name": "labels", "type": {"type": "array", "items": "string"}, "default": []},
+        {"name": "promptTokens", "type": ["null", "long"], "default": null},
+        {"name": "completionTokens", "type": ["null", "long"], "default": null},
+        {"name": "model", "type": ["null", "string"], "default": null},
+        {"name": "estimatedCost", "type": ["null", "double"], "default": null},
+        {"name": "reproducibility", "type": ["null", {
+            "type": "record",
+            "name": "Reproducibility",
+            "fields": [
+                {"name": "seed", "type": "long"},
+                {"name": "model", "type": "string"},
+                {"name": "temperature", "type": ["null", "double"], "default": null},
+                {"name": "promptHash", "type": "string"},
+                {"name": "treeHash", "type": ["null", "string"], "default": null},
+                {"name": "dirtyHash", "type": ["null", "string"], "default": null},
+                {"name": "toolVersions", "type": {"type": "map", "values": "string"}, "default": {}}
+            ]
+        }], "default": null},
+        {"name": "prevHash", "type": ["null", "string"], "default": null},
+        {"name": "kind", "type": {"type": "enum", "name": "EventKind", "symbols": ["requirement", "chore", "note", "annotation"]}, "default": "requirement"},
+        {"name": "commitSha", "type": ["null", "string"], "default": null},
+        {"name": "diffRange", "type": ["null", "string"], "default": null},
+        {"name": "validationLog", "type": ["null", "string"], "default": null},
+        {"name": "transcript", "type": ["null", "string"], "default": null},
+        {"name": "agentSession", "type": ["null", "string"], "default": null},
+        {"name": "payload", "type": ["null", "string"], "default": null, "doc": "JSON-encoded EventPayload"}
     ]
 }"#;
 
@@ -374,34 +1880,463 @@ mod tests {
     }
 
     #[test]
-    fn test_events_for_requirement() {
+    fn test_stream_reader() {
+        let content = format!(
+            "{}\n\n{}\n",
+            serde_json::to_string(&sample_event()).unwrap(),
+            serde_json::to_string(&LedgerEvent::new(2, "REQ-02", EventStatus::Done)).unwrap()
+        );
+        let events: Vec<LedgerEvent> = LedgerReader::new(content.as_bytes())
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].requirement, "REQ-02");
+
+        let err = LedgerReader::<_, LedgerEvent>::new(format!("{content}not json\n").as_bytes())
+            .find_map(std::result::Result::err)
+            .unwrap();
+        assert!(err.to_string().contains("line 4"));
+    }
+
+    #[test]
+    fn test_latest_iteration_fast() {
+        let file = NamedTempFile::new().unwrap();
+        assert_eq!(Ledger::latest_iteration_fast(file.path()).unwrap(), 0);
+        assert_eq!(
+            Ledger::latest_iteration_fast(file.path().with_extension("missing")).unwrap(),
+            0
+        );
+
+        // Span several chunks so the backwards scan has to stitch lines together
+        let mut ledger = Ledger::create(file.path()).unwrap();
+        for iteration in 1..=200 {
+            ledger
+                .append(
+                    LedgerEvent::new(iteration, "REQ-01", EventStatus::Done)
+                        .with_message("x".repeat(100)),
+                )
+                .unwrap();
+        }
+        let mut handle = OpenOptions::new().append(true).open(file.path()).unwrap();
+        writeln!(handle).unwrap();
+
+        assert_eq!(Ledger::latest_iteration_fast(file.path()).unwrap(), 200);
+        assert_eq!(
+            Ledger::latest_iteration_fast(file.path()).unwrap(),
+            Ledger::from_file(file.path()).unwrap().latest_iteration()
+        );
+        assert_eq!(Ledger::stream(file.path()).unwrap().count(), 200);
+    }
+
+    #[test]
+    fn test_notes_for_requirement() {
         let mut ledger = Ledger::new();
-        ledger.append(sample_event()).unwrap();
         ledger
-            .append(LedgerEvent::new(2, "REQ-02", EventStatus::Started))
+            .append(LedgerEvent::new(1, "REQ-02", EventStatus::Failed))
             .unwrap();
         ledger
-            .append(LedgerEvent::new(3, "REQ-01", EventStatus::Done))
+            .append(LedgerEvent::note(
+                1,
+                "REQ-02",
+                "fixed the migration by hand",
+            ))
+            .unwrap();
+        ledger
+            .append(LedgerEvent::note(
+                1,
+                RUN_REQUIREMENT,
+                "bumped the toolchain",
+            ))
+            .unwrap();
+        ledger
+            .append(LedgerEvent::note(1, "REQ-03", "unrelated"))
             .unwrap();
 
-        let req1_events = ledger.events_for_requirement("REQ-01");
-        assert_eq!(req1_events.len(), 2);
+        let notes: Vec<&str> = ledger
+            .notes_for_requirement("REQ-02")
+            .iter()
+            .filter_map(|e| e.message.as_deref())
+            .collect();
+        assert_eq!(
+            notes,
+            vec!["fixed the migration by hand", "bumped the toolchain"]
+        );
+        assert_eq!(
+            ledger.events()[1].payload,
+            Some(EventPayload::HumanIntervention {
+                description: "fixed the migration by hand".to_string()
+            })
+        );
+        assert!(serde_json::to_string(&ledger.events()[1])
+            .unwrap()
+            .contains(r#""kind":"note""#));
     }
 
     #[test]
-    fn test_is_requirement_failed() {
-        let mut ledger = Ledger::new();
-        ledger.append(sample_event()).unwrap();
-        assert!(!ledger.is_requirement_failed("REQ-01"));
+    fn test_validation_log_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ledger = Ledger::create(dir.path().join("ledger.jsonl")).unwrap();
+        let full = "Stage: Test\n".to_string() + &"panicked at src/lib.rs\n".repeat(500);
 
+        let log = ledger.save_validation_log(3, &full).unwrap().unwrap();
+        assert_eq!(log, "artifacts/3-validation.log");
         ledger
-            .append(LedgerEvent::new(2, "REQ-01", EventStatus::Failed))
+            .append(
+                LedgerEvent::new(3, "REQ-01", EventStatus::Failed)
+                    .with_validation(false)
+                    .with_validation_output("Stage: Test\n\n- tests panic")
+                    .with_validation_log(&log),
+            )
             .unwrap();
-        assert!(ledger.is_requirement_failed("REQ-01"));
+
+        let reloaded = Ledger::from_file(dir.path().join("ledger.jsonl")).unwrap();
+        let event = &reloaded.events()[0];
+        assert_eq!(
+            event.validation_output.as_deref(),
+            Some("Stage: Test\n\n- tests panic")
+        );
+        assert_eq!(reloaded.full_validation_output(event).unwrap(), Some(full));
+
+        let transcript = ledger.save_transcript(3, "Editing src/lib.rs\n").unwrap();
+        assert_eq!(transcript.as_deref(), Some("artifacts/3-agent.log"));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("artifacts/3-agent.log")).unwrap(),
+            "Editing src/lib.rs\n"
+        );
+
+        // In-memory ledgers keep only the inline summary
+        let memory = Ledger::new();
+        assert!(memory.save_validation_log(3, "out").unwrap().is_none());
+        assert_eq!(
+            memory.full_validation_output(event).unwrap().as_deref(),
+            Some("Stage: Test\n\n- tests panic")
+        );
     }
 
     #[test]
-    fn test_event_serialization() {
+    fn test_version_header() {
+        let file = NamedTempFile::new().unwrap();
+        let mut ledger = Ledger::from_file(file.path()).unwrap();
+        ledger.append(sample_event()).unwrap();
+        ledger
+            .append(LedgerEvent::new(2, "REQ-01", EventStatus::Done))
+            .unwrap();
+
+        let content = std::fs::read_to_string(file.path()).unwrap();
+        assert_eq!(content.lines().next(), Some(r#"{"ledgerVersion":1}"#));
+        assert_eq!(Ledger::from_file(file.path()).unwrap().events().len(), 2);
+        assert_eq!(Ledger::latest_iteration_fast(file.path()).unwrap(), 2);
+
+        // Legacy ledgers without a header still load and keep appending without one
+        let legacy = content.lines().skip(1).collect::<Vec<_>>().join("\n");
+        let parsed = Ledger::from_jsonl(&legacy).unwrap();
+        assert_eq!(parsed.events().len(), 2);
+
+        let mut reader = LedgerReader::<_, LedgerEvent>::new(content.as_bytes());
+        assert!(reader.next().unwrap().is_ok());
+        assert_eq!(reader.version(), Some(LEDGER_VERSION));
+
+        // A header with no events yet
+        std::fs::write(file.path(), format!("{}\n", version_header())).unwrap();
+        assert_eq!(Ledger::latest_iteration_fast(file.path()).unwrap(), 0);
+        assert!(Ledger::from_file(file.path()).unwrap().events().is_empty());
+    }
+
+    #[test]
+    fn test_newer_ledger_version_rejected() {
+        let content = format!(
+            "{{\"ledgerVersion\":{}}}\n{}\n",
+            LEDGER_VERSION + 1,
+            serde_json::to_string(&sample_event()).unwrap()
+        );
+        let err = Ledger::from_jsonl(&content).unwrap_err();
+        assert!(err.to_string().contains("newer than supported"));
+    }
+
+    #[test]
+    fn test_pending_hints() {
+        let mut ledger = Ledger::new();
+        ledger
+            .append(LedgerEvent::new(1, "REQ-01", EventStatus::Failed))
+            .unwrap();
+        ledger
+            .append(LedgerEvent::hint(1, "REQ-01", "mock the clock"))
+            .unwrap();
+        ledger
+            .append(LedgerEvent::note(1, "REQ-01", "fixed the migration"))
+            .unwrap();
+
+        let hints = ledger.pending_hints("REQ-01");
+        assert_eq!(hints.len(), 1);
+        assert_eq!(hints[0].message.as_deref(), Some("mock the clock"));
+        assert!(ledger.pending_hints("REQ-02").is_empty());
+        // Hints aren't repeated as team notes
+        assert_eq!(ledger.notes_for_requirement("REQ-01").len(), 1);
+
+        // The hint still applies while its attempt runs, then expires
+        ledger
+            .append(LedgerEvent::new(2, "REQ-01", EventStatus::Started))
+            .unwrap();
+        assert_eq!(ledger.pending_hints("REQ-01").len(), 1);
+        ledger
+            .append(LedgerEvent::new(2, "REQ-01", EventStatus::Failed))
+            .unwrap();
+        assert!(ledger.pending_hints("REQ-01").is_empty());
+    }
+
+    #[test]
+    fn test_annotations_for_iteration() {
+        let mut ledger = Ledger::new();
+        ledger
+            .append(LedgerEvent::new(7, "REQ-02", EventStatus::Failed))
+            .unwrap();
+        ledger
+            .append(LedgerEvent::new(8, "REQ-03", EventStatus::Done))
+            .unwrap();
+        ledger
+            .append(LedgerEvent::annotation(8, "REQ-02", 7, "flaky test"))
+            .unwrap();
+
+        let annotations = ledger.annotations_for_iteration(7);
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].iteration, 8);
+        assert_eq!(
+            annotations[0].payload.as_ref().unwrap().describe(),
+            "annotation on iteration 7: flaky test"
+        );
+        assert!(ledger.annotations_for_iteration(8).is_empty());
+
+        // Annotating doesn't change the requirement's outcome
+        assert!(ledger.is_requirement_failed("REQ-02"));
+        assert_eq!(ledger.latest_iteration(), 8);
+    }
+
+    #[test]
+    fn test_append_batch() {
+        let file = NamedTempFile::new().unwrap();
+        let mut ledger = Ledger::from_file(file.path()).unwrap();
+        ledger.enable_hash_chain();
+        ledger.append(sample_event()).unwrap();
+        ledger
+            .append_batch(&[
+                LedgerEvent::new(1, "REQ-01", EventStatus::InProgress),
+                LedgerEvent::new(1, "REQ-01", EventStatus::Done),
+            ])
+            .unwrap();
+
+        let reloaded = Ledger::from_file(file.path()).unwrap();
+        assert_eq!(reloaded.events(), ledger.events());
+        assert_eq!(reloaded.events().len(), 3);
+        assert!(reloaded.verify_chain().unwrap().is_empty());
+        assert_eq!(ledger.events_for_requirement("REQ-01").len(), 3);
+
+        // A failed write leaves the in-memory ledger untouched
+        let dir = tempfile::tempdir().unwrap();
+        let mut broken = Ledger::from_file(dir.path().join("missing/ledger.jsonl")).unwrap();
+        assert!(broken.append_batch(&[sample_event()]).is_err());
+        assert!(broken.events().is_empty());
+        assert!(broken.events_for_requirement("REQ-01").is_empty());
+    }
+
+    #[test]
+    fn test_events_for_requirement() {
+        let mut ledger = Ledger::new();
+        ledger.append(sample_event()).unwrap();
+        ledger
+            .append(LedgerEvent::new(2, "REQ-02", EventStatus::Started))
+            .unwrap();
+        ledger
+            .append(LedgerEvent::new(3, "REQ-01", EventStatus::Done))
+            .unwrap();
+
+        let req1_events = ledger.events_for_requirement("REQ-01");
+        assert_eq!(req1_events.len(), 2);
+    }
+
+    #[test]
+    fn test_is_requirement_failed() {
+        let mut ledger = Ledger::new();
+        ledger.append(sample_event()).unwrap();
+        assert!(!ledger.is_requirement_failed("REQ-01"));
+
+        ledger
+            .append(LedgerEvent::new(2, "REQ-01", EventStatus::Failed))
+            .unwrap();
+        assert!(ledger.is_requirement_failed("REQ-01"));
+    }
+
+    #[test]
+    fn test_event_labels_serialization() {
+        let json = serde_json::to_string(&sample_event()).unwrap();
+        assert!(!json.contains("labels"));
+
+        let event = sample_event().with_labels(["prompt-v2"]);
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"labels\":[\"prompt-v2\"]"));
+    }
+
+    #[test]
+    fn test_metrics_by_label() {
+        let mut ledger = Ledger::new();
+        for (iteration, status, label) in [
+            (1, EventStatus::Started, "a"),
+            (1, EventStatus::Done, "a"),
+            (2, EventStatus::Started, "a"),
+            (2, EventStatus::Failed, "a"),
+            (3, EventStatus::Started, "b"),
+            (3, EventStatus::Done, "b"),
+        ] {
+            ledger
+                .append(LedgerEvent::new(iteration, "REQ-01", status).with_labels([label]))
+                .unwrap();
+        }
+        ledger
+            .append(LedgerEvent::new(4, "REQ-01", EventStatus::Done))
+            .unwrap();
+
+        let metrics = ledger.metrics_by_label();
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(
+            metrics["a"],
+            LabelMetrics {
+                iterations: 2,
+                done: 1,
+                failed: 1
+            }
+        );
+        assert!((metrics["a"].success_rate() - 0.5).abs() < f64::EPSILON);
+        assert!((metrics["b"].success_rate() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_audit_events() {
+        let mut ledger = Ledger::new();
+        ledger.append(sample_event()).unwrap();
+        ledger
+            .append(
+                LedgerEvent::new(1, AUDIT_REQUIREMENT, EventStatus::Failed)
+                    .with_message("Commit abc has no REQ reference"),
+            )
+            .unwrap();
+        let audit = ledger.audit_events();
+        assert_eq!(audit.len(), 1);
+        assert!(audit[0].message.as_deref().unwrap().contains("abc"));
+    }
+
+    #[test]
+    fn test_snapshot_diff() {
+        let mut ledger = Ledger::new();
+        ledger.append(sample_event()).unwrap();
+        let snapshot = ledger.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert!(ledger.diff(&snapshot).is_empty());
+
+        ledger
+            .append(LedgerEvent::new(1, "REQ-01", EventStatus::Done))
+            .unwrap();
+        let new_events = ledger.diff(&snapshot);
+        assert_eq!(new_events.len(), 1);
+        assert_eq!(new_events[0].status, EventStatus::Done);
+
+        // A snapshot from a longer ledger yields nothing rather than panicking
+        assert!(Ledger::new().diff(&snapshot).is_empty());
+    }
+
+    #[test]
+    fn test_usage_aggregation() {
+        let mut ledger = Ledger::new();
+        ledger.append(sample_event()).unwrap();
+        for model in ["claude-haiku-4.5", "claude-haiku-4.5", "unpriced-model"] {
+            ledger
+                .append(
+                    LedgerEvent::new(1, "REQ-01", EventStatus::Done).with_usage(&TokenUsage {
+                        model: Some(model.to_string()),
+                        prompt_tokens: 1_000_000,
+                        completion_tokens: 0,
+                    }),
+                )
+                .unwrap();
+        }
+
+        let totals = ledger.usage_totals();
+        assert_eq!(totals.prompt_tokens, 3_000_000);
+        assert!((totals.estimated_cost - 2.0).abs() < 1e-9);
+
+        let by_model = ledger.usage_by_model();
+        assert_eq!(by_model.len(), 2);
+        assert_eq!(by_model["claude-haiku-4.5"].prompt_tokens, 2_000_000);
+        assert!(by_model["unpriced-model"].estimated_cost.abs() < f64::EPSILON);
+
+        let json = serde_json::to_string(&ledger.events()[1]).unwrap();
+        assert!(json.contains("\"promptTokens\":1000000"));
+        assert!(json.contains("\"estimatedCost\":1.0"));
+    }
+
+    #[test]
+    fn test_is_last_iteration_noop() {
+        let mut ledger = Ledger::new();
+        ledger.append(sample_event()).unwrap();
+        assert!(!ledger.is_last_iteration_noop("REQ-01"));
+
+        ledger
+            .append(LedgerEvent::new(1, "REQ-01", EventStatus::Failed).with_message(NO_OP_MESSAGE))
+            .unwrap();
+        assert!(ledger.is_last_iteration_noop("REQ-01"));
+
+        // Still true once the next iteration has started
+        ledger
+            .append(LedgerEvent::new(2, "REQ-01", EventStatus::Started))
+            .unwrap();
+        assert!(ledger.is_last_iteration_noop("REQ-01"));
+
+        ledger
+            .append(LedgerEvent::new(2, "REQ-01", EventStatus::Done))
+            .unwrap();
+        assert!(!ledger.is_last_iteration_noop("REQ-01"));
+    }
+
+    #[test]
+    fn test_reproducibility_serialization() {
+        let event = sample_event().with_reproducibility(Reproducibility {
+            seed: 7,
+            model: "claude-haiku-4.5".to_string(),
+            prompt_hash: Reproducibility::sha256(""),
+            ..Reproducibility::default()
+        });
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"reproducibility\":{\"seed\":7"));
+        assert!(json.contains(
+            "\"promptHash\":\"e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855\""
+        ));
+        assert!(!json.contains("toolVersions"));
+
+        let parsed: LedgerEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, event);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let mut ledger = Ledger::new();
+        ledger.append(sample_event()).unwrap();
+        ledger
+            .append(
+                LedgerEvent::new(1, "REQ-01", EventStatus::Failed)
+                    .with_validation(false)
+                    .with_message("said \"no\", twice")
+                    .with_labels(["a", "b"]),
+            )
+            .unwrap();
+
+        let csv = ledger.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("timestamp,iteration,requirement,status"));
+        assert!(lines[1].ends_with(",1,REQ-01,started,,,,,,,"));
+        assert!(lines[2].contains(",failed,false,\"said \"\"no\"\", twice\",a;b,"));
+    }
+
+    #[test]
+    fn test_event_serialization() {
         let event = sample_event().with_validation(true);
         let json = serde_json::to_string(&event).unwrap();
         assert!(json.contains("\"iteration\":1"));
@@ -418,7 +2353,13 @@ mod tests {
             .append(
                 LedgerEvent::new(2, "REQ-01", EventStatus::Done)
                     .with_validation(true)
-                    .with_message("Completed successfully"),
+                    .with_message("Completed successfully")
+                    .with_labels(["prompt-v2"])
+                    .with_usage(&TokenUsage {
+                        model: Some("claude-haiku-4.5".to_string()),
+                        prompt_tokens: 1_000,
+                        completion_tokens: 200,
+                    }),
             )
             .unwrap();
 
@@ -438,6 +2379,371 @@ mod tests {
         let data = std::fs::read(temp.path()).unwrap();
         assert!(!data.is_empty());
     }
+
+    #[test]
+    fn test_from_avro_roundtrip() {
+        let temp = NamedTempFile::new().unwrap();
+        let mut ledger = Ledger::new();
+        ledger.append(sample_event()).unwrap();
+        ledger
+            .append(
+                LedgerEvent::new(2, "REQ-01", EventStatus::Failed)
+                    .with_validation(false)
+                    .with_validation_output("Stage: Test")
+                    .with_labels(["prompt-v2"])
+                    .with_usage(&TokenUsage {
+                        model: Some("claude-haiku-4.5".to_string()),
+                        prompt_tokens: 1_000,
+                        completion_tokens: 200,
+                    })
+                    .with_reproducibility(Reproducibility {
+                        seed: 42,
+                        model: "claude-haiku-4.5".to_string(),
+                        temperature: None,
+                        prompt_hash: Reproducibility::sha256("prompt"),
+                        tree_hash: Some("abc123".to_string()),
+                        dirty_hash: None,
+                        tool_versions: BTreeMap::from([("git".to_string(), "2.45".to_string())]),
+                    }),
+            )
+            .unwrap();
+        ledger
+            .append(
+                LedgerEvent::chore(3, EventStatus::Done)
+                    .with_message("update deps")
+                    .with_commit("def456", Some("abc123"))
+                    .with_payload(EventPayload::IterationFinished { success: true }),
+            )
+            .unwrap();
+        ledger.save_avro(temp.path()).unwrap();
+
+        let loaded = Ledger::from_avro(temp.path()).unwrap();
+        assert_eq!(loaded.events(), ledger.events());
+    }
+
+    #[test]
+    fn test_from_avro_resolves_older_schema() {
+        use apache_avro::{types::Record, Schema, Writer};
+
+        // An archive written before labels and usage fields existed
+        let old_schema = Schema::parse_str(
+            r#"{"type": "record", "name": "LedgerEvent", "namespace": "com.ralph", "fields": [
+                {"name": "timestamp", "type": "string"},
+                {"name": "iteration", "type": "long"},
+                {"name": "requirement", "type": "string"},
+                {"name": "status", "type": {"type": "enum", "name": "EventStatus", "symbols": ["started", "in_progress", "done", "failed"]}},
+                {"name": "validationPassed", "type": ["null", "boolean"], "default": null},
+                {"name": "validationOutput", "type": ["null", "string"], "default": null},
+                {"name": "message", "type": ["null", "string"], "default": null}
+            ]}"#,
+        )
+        .unwrap();
+        let mut writer = Writer::new(&old_schema, Vec::new());
+        let mut record = Record::new(&old_schema).unwrap();
+        record.put("timestamp", "2026-01-19T10:00:00+00:00");
+        record.put("iteration", 3_i64);
+        record.put("requirement", "REQ-02");
+        record.put(
+            "status",
+            apache_avro::types::Value::Enum(2, "done".to_string()),
+        );
+        record.put(
+            "validationPassed",
+            Some(apache_avro::types::Value::Boolean(true)),
+        );
+        record.put("validationOutput", None::<apache_avro::types::Value>);
+        record.put("message", None::<apache_avro::types::Value>);
+        writer.append(record).unwrap();
+
+        let temp = NamedTempFile::new().unwrap();
+        std::fs::write(temp.path(), writer.into_inner().unwrap()).unwrap();
+
+        let loaded = Ledger::from_avro(temp.path()).unwrap();
+        let event = &loaded.events()[0];
+        assert_eq!(event.iteration, 3);
+        assert_eq!(event.status, EventStatus::Done);
+        assert_eq!(event.validation_passed, Some(true));
+        assert!(event.labels.is_empty());
+        assert!(event.model.is_none());
+    }
+
+    #[test]
+    fn test_merge() {
+        let at = |secs: i64| DateTime::from_timestamp(1_760_000_000 + secs, 0).unwrap();
+        let event = |secs: i64, iteration: u32, req: &str| {
+            let mut event = LedgerEvent::new(iteration, req, EventStatus::Done);
+            event.timestamp = at(secs);
+            event
+        };
+
+        let mut ours = Ledger::new();
+        ours.append(event(0, 1, "REQ-01")).unwrap();
+        ours.append(event(20, 2, "REQ-02")).unwrap();
+        let mut theirs = Ledger::new();
+        theirs.append(event(0, 1, "REQ-01")).unwrap();
+        theirs.append(event(10, 2, "REQ-03")).unwrap();
+
+        let mut merged = Ledger::new();
+        merged.merge(&theirs);
+        assert_eq!(ours.merge(&theirs), 1);
+        assert_eq!(merged.merge(&Ledger::from_jsonl(&jsonl(&ours)).unwrap()), 1);
+
+        let reqs: Vec<&str> = ours
+            .events()
+            .iter()
+            .map(|e| e.requirement.as_str())
+            .collect();
+        assert_eq!(reqs, vec!["REQ-01", "REQ-03", "REQ-02"]);
+        // Requirement lookups follow the reordered events
+        assert_eq!(ours.events_for_requirement("REQ-02")[0].timestamp, at(20));
+        assert_eq!(ours.events_for_requirement("REQ-03")[0].timestamp, at(10));
+        // Merging is order-independent
+        assert_eq!(merged.events(), ours.events());
+        assert_eq!(ours.merge(&theirs), 0);
+    }
+
+    #[test]
+    fn test_save_rewrites_ledger() {
+        let temp = NamedTempFile::new().unwrap();
+        let mut ledger = Ledger::create(temp.path()).unwrap();
+        ledger.append(sample_event()).unwrap();
+
+        let mut other = Ledger::new();
+        other
+            .append(LedgerEvent::new(2, "REQ-02", EventStatus::Started))
+            .unwrap();
+        ledger.merge(&other);
+        ledger.save(temp.path()).unwrap();
+
+        let loaded = Ledger::from_file(temp.path()).unwrap();
+        assert_eq!(loaded.events(), ledger.events());
+    }
+
+    #[test]
+    fn test_latest_iteration_fast_after_merge() {
+        let at = |secs: i64| DateTime::from_timestamp(1_760_000_000 + secs, 0).unwrap();
+        let event = |secs: i64, iteration: u32| {
+            let mut event = LedgerEvent::new(iteration, "REQ-01", EventStatus::Failed);
+            event.timestamp = at(secs);
+            event
+        };
+        let temp = NamedTempFile::new().unwrap();
+        let mut ours = Ledger::create(temp.path()).unwrap();
+        ours.append(event(0, 1)).unwrap();
+        ours.append(event(5, 10)).unwrap();
+        let mut theirs = Ledger::new();
+        theirs.append(event(10, 2)).unwrap();
+        ours.merge(&theirs);
+        ours.save(temp.path()).unwrap();
+
+        // The last line is iteration 2, but iteration 10 is still the latest
+        assert_eq!(ours.events().last().unwrap().iteration, 2);
+        assert_eq!(Ledger::latest_iteration_fast(temp.path()).unwrap(), 10);
+
+        // Appending to a merged ledger keeps it marked as rewritten
+        let mut reopened = Ledger::from_file(temp.path()).unwrap();
+        reopened.append(event(20, 3)).unwrap();
+        assert_eq!(Ledger::latest_iteration_fast(temp.path()).unwrap(), 10);
+    }
+
+    fn jsonl(ledger: &Ledger) -> String {
+        ledger
+            .events()
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap() + "\n")
+            .collect()
+    }
+
+    #[test]
+    fn test_last_agent_session() {
+        let mut ledger = Ledger::new();
+        ledger
+            .append(LedgerEvent::new(1, "REQ-01", EventStatus::Failed).with_agent_session("s-1"))
+            .unwrap();
+        ledger
+            .append(LedgerEvent::new(2, "REQ-01", EventStatus::Started))
+            .unwrap();
+        assert_eq!(ledger.last_agent_session("REQ-01").as_deref(), Some("s-1"));
+        assert!(ledger.last_agent_session("REQ-02").is_none());
+
+        // A later attempt without a session doesn't resume an older one
+        ledger
+            .append(LedgerEvent::new(2, "REQ-01", EventStatus::Failed))
+            .unwrap();
+        assert!(ledger.last_agent_session("REQ-01").is_none());
+    }
+
+    #[test]
+    fn test_commits_for_requirement() {
+        let mut ledger = Ledger::new();
+        ledger
+            .append(
+                LedgerEvent::new(1, "REQ-01", EventStatus::Failed)
+                    .with_commit("aaa111", Some("aaa111")),
+            )
+            .unwrap();
+        ledger
+            .append(
+                LedgerEvent::new(2, "REQ-01", EventStatus::Done)
+                    .with_commit("bbb222", Some("aaa111")),
+            )
+            .unwrap();
+        ledger
+            .append(
+                LedgerEvent::new(3, "REQ-02", EventStatus::Done)
+                    .with_commit("ccc333", Some("bbb222")),
+            )
+            .unwrap();
+
+        // HEAD didn't move in iteration 1, so it produced no commit
+        assert!(ledger.events()[0].diff_range.is_none());
+        assert_eq!(
+            ledger.events()[1].diff_range.as_deref(),
+            Some("aaa111..bbb222")
+        );
+        assert_eq!(ledger.commits_for_requirement("REQ-01"), vec!["bbb222"]);
+        assert_eq!(ledger.commits_for_requirement("REQ-02"), vec!["ccc333"]);
+
+        assert_eq!(
+            ledger.event_for_commit("ccc").unwrap().requirement,
+            "REQ-02"
+        );
+        assert!(ledger.event_for_commit("aaa111").is_none());
+        assert!(ledger.event_for_commit("").is_none());
+    }
+
+    #[test]
+    fn test_event_payload_serialization() {
+        let event = LedgerEvent::new(1, "REQ-01", EventStatus::InProgress).with_payload(
+            EventPayload::ValidationRun {
+                passed: false,
+                failed_stage: Some("Test".to_string()),
+            },
+        );
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(
+            r#""payload":{"type":"validation_run","passed":false,"failedStage":"Test"}"#
+        ));
+        let parsed: LedgerEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, event);
+        assert_eq!(
+            parsed.payload_or_inferred().unwrap().describe(),
+            "validation failed at Test"
+        );
+    }
+
+    #[test]
+    fn test_payload_inferred_for_legacy_events() {
+        let legacy: LedgerEvent = serde_json::from_str(
+            r#"{"timestamp":"2026-01-19T10:00:00Z","iteration":1,"requirement":"REQ-01","status":"failed","validationPassed":false}"#,
+        )
+        .unwrap();
+        assert!(legacy.payload.is_none());
+        assert_eq!(
+            legacy.payload_or_inferred(),
+            Some(EventPayload::IterationFinished { success: false })
+        );
+        assert_eq!(
+            sample_event().payload_or_inferred(),
+            Some(EventPayload::IterationStarted { full_tests: None })
+        );
+        assert!(LedgerEvent::new(1, "REQ-01", EventStatus::InProgress)
+            .payload_or_inferred()
+            .is_none());
+    }
+
+    #[test]
+    fn test_chore_events() {
+        let mut ledger = Ledger::new();
+        ledger.append(sample_event()).unwrap();
+        ledger
+            .append(LedgerEvent::chore(2, EventStatus::Done).with_message("update deps"))
+            .unwrap();
+
+        let chores = ledger.chore_events();
+        assert_eq!(chores.len(), 1);
+        assert_eq!(chores[0].requirement, CHORE_REQUIREMENT);
+        assert_eq!(chores[0].message.as_deref(), Some("update deps"));
+
+        // Requirement events omit the kind; chores record it
+        let json = serde_json::to_string(&ledger.events()[0]).unwrap();
+        assert!(!json.contains("kind"));
+        let json = serde_json::to_string(chores[0]).unwrap();
+        assert!(json.contains(r#""kind":"chore""#));
+        let parsed: LedgerEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.kind, EventKind::Chore);
+    }
+
+    #[test]
+    fn test_hash_chain_roundtrip() {
+        let temp = NamedTempFile::new().unwrap();
+        {
+            let mut ledger = Ledger::create(temp.path()).unwrap();
+            // Events before the chain is enabled stay unchained
+            ledger.append(sample_event()).unwrap();
+            ledger.enable_hash_chain();
+            ledger
+                .append(LedgerEvent::new(1, "REQ-01", EventStatus::Done))
+                .unwrap();
+            assert!(ledger.events()[0].prev_hash.is_none());
+            assert!(ledger.events()[1].prev_hash.is_some());
+        }
+
+        // Reloading keeps extending the chain
+        let mut ledger = Ledger::from_file(temp.path()).unwrap();
+        assert!(ledger.is_hash_chained());
+        ledger
+            .append(LedgerEvent::new(2, "REQ-02", EventStatus::Started))
+            .unwrap();
+        assert!(ledger.verify_chain().unwrap().is_empty());
+
+        let reloaded = Ledger::from_file(temp.path()).unwrap();
+        assert!(reloaded.verify_chain().unwrap().is_empty());
+        assert_eq!(reloaded.head_hash().unwrap(), ledger.head_hash().unwrap());
+    }
+
+    #[test]
+    fn test_hash_chain_detects_tampering() {
+        let temp = NamedTempFile::new().unwrap();
+        let mut ledger = Ledger::create(temp.path()).unwrap();
+        ledger.enable_hash_chain();
+        ledger.append(sample_event()).unwrap();
+        ledger
+            .append(LedgerEvent::new(1, "REQ-01", EventStatus::Failed))
+            .unwrap();
+        ledger
+            .append(LedgerEvent::new(2, "REQ-01", EventStatus::Started))
+            .unwrap();
+        assert_eq!(ledger.events()[0].prev_hash.as_deref(), Some(GENESIS_HASH));
+
+        // Rewrite a failure as a success
+        let content = std::fs::read_to_string(temp.path()).unwrap();
+        std::fs::write(temp.path(), content.replacen("\"failed\"", "\"done\"", 1)).unwrap();
+
+        let tampered = Ledger::from_file(temp.path()).unwrap();
+        let breaks = tampered.verify_chain().unwrap();
+        assert_eq!(breaks.len(), 1);
+        assert_eq!(breaks[0].index, 2);
+        assert_eq!(breaks[0].iteration, 2);
+
+        // Deleting an event breaks the link after it
+        let content = std::fs::read_to_string(temp.path()).unwrap();
+        let kept: Vec<&str> = content
+            .lines()
+            .filter(|l| !l.contains("failed") && !l.contains("\"done\""))
+            .collect();
+        std::fs::write(temp.path(), kept.join("\n")).unwrap();
+        let truncated = Ledger::from_file(temp.path()).unwrap();
+        assert_eq!(truncated.verify_chain().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_unchained_ledger_verifies() {
+        let mut ledger = Ledger::new();
+        ledger.append(sample_event()).unwrap();
+        assert!(!ledger.is_hash_chained());
+        assert!(ledger.verify_chain().unwrap().is_empty());
+    }
 }
 
 #[cfg(test)]
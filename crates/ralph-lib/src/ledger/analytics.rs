@@ -0,0 +1,180 @@
+// ABOUTME: Ledger analytics: per-requirement iteration counts, failure rates, and durations
+// ABOUTME: Also aggregates which validation stages fail most often
+
+use super::{EventKind, EventStatus, Ledger, AUDIT_REQUIREMENT, RUN_REQUIREMENT};
+use chrono::Duration;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Prefix the implement loop puts on recorded validation output
+const STAGE_PREFIX: &str = "Stage: ";
+
+/// Stage name used when a failure's stage cannot be determined
+pub const UNKNOWN_STAGE: &str = "unknown";
+
+/// Analytics for a single requirement
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequirementAnalytics {
+    /// Distinct iterations spent on the requirement
+    pub iterations: usize,
+    /// Iterations that ended in failure
+    pub failures: usize,
+    /// Time from the first event to the first completion (None if never done)
+    pub time_to_done: Option<Duration>,
+}
+
+impl RequirementAnalytics {
+    /// Fraction of iterations that failed (0.0 if none ran)
+    #[must_use]
+    pub fn failure_rate(&self) -> f64 {
+        if self.iterations == 0 {
+            0.0
+        } else {
+            self.failures as f64 / self.iterations as f64
+        }
+    }
+}
+
+/// Typed analytics report computed from a ledger
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnalyticsReport {
+    /// Per-requirement analytics, keyed by requirement ID
+    pub requirements: BTreeMap<String, RequirementAnalytics>,
+    /// Validation failures per stage (e.g., "test" -> 3)
+    pub stage_failures: BTreeMap<String, usize>,
+}
+
+impl AnalyticsReport {
+    /// Compute analytics from all events in the ledger (audit findings, run-level events, chores, and notes are excluded)
+    #[must_use]
+    pub fn from_ledger(ledger: &Ledger) -> Self {
+        let mut report = Self::default();
+        let mut seen: BTreeSet<(&str, u32)> = BTreeSet::new();
+        let mut first_seen: BTreeMap<&str, chrono::DateTime<chrono::Utc>> = BTreeMap::new();
+
+        for event in ledger.events() {
+            if event.requirement == AUDIT_REQUIREMENT
+                || event.requirement == RUN_REQUIREMENT
+                || event.kind != EventKind::Requirement
+            {
+                continue;
+            }
+            let req = event.requirement.as_str();
+            let entry = report.requirements.entry(req.to_string()).or_default();
+            let started = *first_seen.entry(req).or_insert(event.timestamp);
+
+            if seen.insert((req, event.iteration)) {
+                entry.iterations += 1;
+            }
+            match event.status {
+                EventStatus::Failed => entry.failures += 1,
+                EventStatus::Done if entry.time_to_done.is_none() => {
+                    entry.time_to_done = Some(event.timestamp - started);
+                }
+                _ => {}
+            }
+
+            if event.validation_passed == Some(false) {
+                let stage = event
+                    .validation_output
+                    .as_deref()
+                    .and_then(failed_stage)
+                    .unwrap_or_else(|| UNKNOWN_STAGE.to_string());
+                *report.stage_failures.entry(stage).or_default() += 1;
+            }
+        }
+        report
+    }
+
+    /// Total iterations across all requirements
+    #[must_use]
+    pub fn total_iterations(&self) -> usize {
+        self.requirements.values().map(|r| r.iterations).sum()
+    }
+
+    /// Fraction of all iterations that failed (0.0 if none ran)
+    #[must_use]
+    pub fn failure_rate(&self) -> f64 {
+        let total = self.total_iterations();
+        if total == 0 {
+            return 0.0;
+        }
+        let failures: usize = self.requirements.values().map(|r| r.failures).sum();
+        failures as f64 / total as f64
+    }
+}
+
+/// Extract the failed stage name from recorded validation output
+fn failed_stage(output: &str) -> Option<String> {
+    output
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix(STAGE_PREFIX))
+        .map(|stage| stage.trim().to_lowercase())
+        .filter(|stage| !stage.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LedgerEvent;
+
+    fn at(event: LedgerEvent, secs: i64) -> LedgerEvent {
+        LedgerEvent {
+            timestamp: chrono::DateTime::from_timestamp(secs, 0).unwrap(),
+            ..event
+        }
+    }
+
+    #[test]
+    fn test_report_from_ledger() {
+        let mut ledger = Ledger::new();
+        for event in [
+            at(LedgerEvent::new(1, "REQ-01", EventStatus::Started), 0),
+            at(
+                LedgerEvent::new(1, "REQ-01", EventStatus::Failed)
+                    .with_validation(false)
+                    .with_validation_output("Stage: Test\n\n- assertion failed"),
+                60,
+            ),
+            at(LedgerEvent::new(2, "REQ-01", EventStatus::Started), 120),
+            at(
+                LedgerEvent::new(2, "REQ-01", EventStatus::Done).with_validation(true),
+                300,
+            ),
+            at(
+                LedgerEvent::new(3, "REQ-02", EventStatus::Failed).with_validation(false),
+                400,
+            ),
+            at(
+                LedgerEvent::new(3, AUDIT_REQUIREMENT, EventStatus::Failed),
+                500,
+            ),
+        ] {
+            ledger.append(event).unwrap();
+        }
+
+        let report = AnalyticsReport::from_ledger(&ledger);
+        assert_eq!(report.requirements.len(), 2);
+
+        let req1 = &report.requirements["REQ-01"];
+        assert_eq!(req1.iterations, 2);
+        assert_eq!(req1.failures, 1);
+        assert!((req1.failure_rate() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(req1.time_to_done, Some(Duration::seconds(300)));
+
+        let req2 = &report.requirements["REQ-02"];
+        assert!(req2.time_to_done.is_none());
+
+        assert_eq!(report.stage_failures["test"], 1);
+        assert_eq!(report.stage_failures[UNKNOWN_STAGE], 1);
+        assert_eq!(report.total_iterations(), 3);
+        assert!((report.failure_rate() - 2.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_empty_report() {
+        let report = AnalyticsReport::from_ledger(&Ledger::new());
+        assert!(report.requirements.is_empty());
+        assert!(report.failure_rate().abs() < f64::EPSILON);
+    }
+}
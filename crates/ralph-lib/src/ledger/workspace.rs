@@ -0,0 +1,207 @@
+// ABOUTME: Workspace-level ledger (ralph/ledger.jsonl by default) recording cross-feature activity
+// ABOUTME: Opt-in by file presence; tracks runs started/finished and features created, split, or archived
+
+use super::LedgerReader;
+use crate::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Default location of the workspace ledger, relative to the project root (see
+/// `[paths] workspace-ledger`)
+pub const WORKSPACE_LEDGER: &str = "ralph/ledger.jsonl";
+
+/// What happened to a feature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WorkspaceActivity {
+    /// `ralph implement` started on the feature
+    RunStarted { run_id: String },
+    /// `ralph implement` finished, with requirement progress at that point
+    RunFinished { done: usize, total: usize },
+    /// A PRD was created for the feature
+    FeatureCreated,
+    /// The feature was split off from another feature
+    FeatureSplit { from: String },
+    /// The feature's ledger was archived
+    FeatureArchived,
+}
+
+/// A single cross-feature event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceEvent {
+    /// When the event occurred
+    pub timestamp: DateTime<Utc>,
+    /// Feature slug the event relates to
+    pub feature: String,
+    #[serde(flatten)]
+    pub activity: WorkspaceActivity,
+}
+
+impl WorkspaceEvent {
+    /// Create a new event with current timestamp
+    #[must_use]
+    pub fn new(feature: impl Into<String>, activity: WorkspaceActivity) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            feature: feature.into(),
+            activity,
+        }
+    }
+
+    /// One-line human-readable summary
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let what = match &self.activity {
+            WorkspaceActivity::RunStarted { run_id } => format!("run {run_id} started"),
+            WorkspaceActivity::RunFinished { done, total } => {
+                format!("run finished ({done}/{total} done)")
+            }
+            WorkspaceActivity::FeatureCreated => "created".to_string(),
+            WorkspaceActivity::FeatureSplit { from } => format!("split from '{from}'"),
+            WorkspaceActivity::FeatureArchived => "archived".to_string(),
+        };
+        format!("{}: {what}", self.feature)
+    }
+}
+
+/// Append-only ledger of activity across every feature in a project
+#[derive(Debug)]
+pub struct WorkspaceLedger {
+    path: PathBuf,
+    events: Vec<WorkspaceEvent>,
+}
+
+impl WorkspaceLedger {
+    /// Load the workspace ledger at `path`, or `None` if the project has not opted in
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or contains invalid JSON.
+    pub fn open(path: impl AsRef<Path>) -> Result<Option<Self>> {
+        let path = path.as_ref().to_path_buf();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let events =
+            LedgerReader::new(BufReader::new(File::open(&path)?)).collect::<Result<_>>()?;
+        Ok(Some(Self { path, events }))
+    }
+
+    /// Create an empty workspace ledger at `path`, opting the project in
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created or the file cannot be opened.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self::open(&path)?.unwrap_or(Self {
+            path,
+            events: Vec::new(),
+        }))
+    }
+
+    /// Append an event to the workspace ledger at `path`, if the project has opted in
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ledger cannot be read or written.
+    pub fn record(
+        path: impl AsRef<Path>,
+        feature: &str,
+        activity: WorkspaceActivity,
+    ) -> Result<()> {
+        match Self::open(path)? {
+            Some(mut ledger) => ledger.append(WorkspaceEvent::new(feature, activity)),
+            None => Ok(()),
+        }
+    }
+
+    /// Append an event (writes to file immediately)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or written.
+    pub fn append(&mut self, event: WorkspaceEvent) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&event)?)?;
+        file.flush()?;
+        self.events.push(event);
+        Ok(())
+    }
+
+    /// Get all events, oldest first
+    #[must_use]
+    pub fn events(&self) -> &[WorkspaceEvent] {
+        &self.events
+    }
+
+    /// Most recent event per feature
+    #[must_use]
+    pub fn latest_by_feature(&self) -> BTreeMap<&str, &WorkspaceEvent> {
+        let mut latest = BTreeMap::new();
+        for event in &self.events {
+            latest.insert(event.feature.as_str(), event);
+        }
+        latest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_record_is_opt_in() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(WORKSPACE_LEDGER);
+        WorkspaceLedger::record(&path, "auth", WorkspaceActivity::FeatureCreated).unwrap();
+        assert!(WorkspaceLedger::open(&path).unwrap().is_none());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_roundtrip_and_latest_by_feature() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(WORKSPACE_LEDGER);
+        WorkspaceLedger::create(&path).unwrap();
+        for (feature, activity) in [
+            ("auth", WorkspaceActivity::FeatureCreated),
+            (
+                "auth",
+                WorkspaceActivity::RunStarted {
+                    run_id: "auth-1".to_string(),
+                },
+            ),
+            (
+                "billing",
+                WorkspaceActivity::FeatureSplit {
+                    from: "auth".to_string(),
+                },
+            ),
+            ("auth", WorkspaceActivity::RunFinished { done: 2, total: 3 }),
+        ] {
+            WorkspaceLedger::record(&path, feature, activity).unwrap();
+        }
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains(r#""feature":"billing","type":"feature_split","from":"auth""#));
+
+        let ledger = WorkspaceLedger::open(&path).unwrap().unwrap();
+        assert_eq!(ledger.events().len(), 4);
+        let latest = ledger.latest_by_feature();
+        assert_eq!(latest["auth"].describe(), "auth: run finished (2/3 done)");
+        assert_eq!(latest["billing"].describe(), "billing: split from 'auth'");
+    }
+}
@@ -1,15 +1,56 @@
 // ABOUTME: Core library for Ralph CLI providing PRD automation functionality
-// ABOUTME: Includes PRD parsing, validation, ledger management, and validation profiles
+// ABOUTME: Shared building blocks for the CLI: PRDs, the ledger, validation, prompts, and run reporting
 
+pub mod api;
+pub mod budget;
+pub mod checkpoint;
+pub mod config;
+pub mod diagnostics;
 pub mod error;
+pub mod export;
+pub mod handoff;
+pub mod judge;
 pub mod ledger;
+pub mod lint;
+pub mod notify;
+pub mod otlp;
 pub mod prd;
+pub mod progress;
+pub mod prompt;
+pub mod prompt_budget;
+pub mod pull_request;
+pub mod redact;
+pub mod repo_map;
+pub mod report;
+pub mod scratchpad;
+pub mod secrets;
+pub mod selection;
+pub mod summary;
+pub mod sync;
+pub mod throttle;
+pub mod usage;
 pub mod validation;
 
+pub use checkpoint::Checkpoint;
+pub use config::{Config, ModelConfig};
 pub use error::RalphError;
-pub use ledger::{EventStatus, Ledger, LedgerEvent};
-pub use prd::{MarkdownPrd, Prd, Requirement, RequirementStatus};
-pub use validation::{ValidationConfig, ValidationProfile, ValidationResult, ValidationStage};
+pub use ledger::analytics::AnalyticsReport;
+pub use ledger::workspace::{WorkspaceActivity, WorkspaceEvent, WorkspaceLedger};
+pub use ledger::{
+    ChainBreak, EventKind, EventPayload, EventStatus, LabelMetrics, Ledger, LedgerEvent,
+    LedgerReader, LedgerSnapshot, Reproducibility, UsageTotals,
+};
+pub use prd::{
+    prd_path, ClarifyingQuestion, MarkdownPrd, Prd, PromptHints, Requirement, RequirementStatus,
+    RiskLevel,
+};
+pub use secrets::{Secret, SecretResolver};
+pub use selection::{Selection, SelectionStrategy};
+pub use usage::TokenUsage;
+pub use validation::{
+    CaptureOptions, ValidationCache, ValidationConfig, ValidationProfile, ValidationResult,
+    ValidationStage,
+};
 
 /// Result type alias using [`RalphError`]
 pub type Result<T> = std::result::Result<T, RalphError>;
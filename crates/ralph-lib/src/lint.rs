@@ -0,0 +1,230 @@
+// ABOUTME: PRD lint checks run before the implement loop
+// ABOUTME: Flags duplicate IDs, empty criteria, unverified done status, and unknown profiles
+
+use crate::{EventPayload, Ledger, Prd, RequirementStatus, ValidationConfig};
+use std::collections::HashSet;
+use std::fmt;
+
+/// A single actionable lint finding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintWarning {
+    /// Requirement the warning relates to (None for PRD-level warnings)
+    pub requirement: Option<String>,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl LintWarning {
+    fn prd(message: impl Into<String>) -> Self {
+        Self {
+            requirement: None,
+            message: message.into(),
+        }
+    }
+
+    fn requirement(id: &str, message: impl Into<String>) -> Self {
+        Self {
+            requirement: Some(id.to_string()),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.requirement {
+            Some(id) => write!(f, "{id}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Lint a PRD, optionally cross-checking its ledger and validation config
+#[must_use]
+pub fn lint_prd(
+    prd: &Prd,
+    ledger: Option<&Ledger>,
+    validation: Option<&ValidationConfig>,
+) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    let mut seen = HashSet::new();
+    for req in &prd.requirements {
+        if !seen.insert(req.id.as_str()) {
+            warnings.push(LintWarning::requirement(
+                &req.id,
+                "duplicate requirement ID",
+            ));
+        }
+        if req
+            .acceptance_criteria
+            .iter()
+            .all(|ac| ac.trim().is_empty())
+        {
+            warnings.push(LintWarning::requirement(
+                &req.id,
+                "no acceptance criteria; add at least one Given/When/Then",
+            ));
+        }
+        if req.status == RequirementStatus::Done {
+            let has_events = ledger.is_some_and(|l| !l.events_for_requirement(&req.id).is_empty());
+            if !has_events {
+                warnings.push(LintWarning::requirement(
+                    &req.id,
+                    "marked done but has no ledger events",
+                ));
+            }
+        }
+    }
+
+    // Split keeps requirement IDs: moved-in ones are exempt, moved-out ones leave gaps
+    let moved: HashSet<&str> = ledger
+        .map(|l| {
+            l.events()
+                .iter()
+                .filter(|e| matches!(e.payload, Some(EventPayload::RequirementMoved { .. })))
+                .map(|e| e.requirement.as_str())
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut number = 0;
+    for req in &prd.requirements {
+        if moved.contains(req.id.as_str()) {
+            let moved_number = req.id.strip_prefix("REQ-").and_then(|n| n.parse().ok());
+            number = moved_number.map_or(number, |n: usize| number.max(n));
+            continue;
+        }
+        let expected = loop {
+            number += 1;
+            let id = format!("REQ-{number:02}");
+            if !moved.contains(id.as_str()) {
+                break id;
+            }
+        };
+        if req.id != expected {
+            warnings.push(LintWarning::requirement(
+                &req.id,
+                format!("non-sequential ID (expected {expected})"),
+            ));
+        }
+    }
+
+    if prd.validation_profiles.is_empty() {
+        warnings.push(LintWarning::prd("no validation profiles listed"));
+    }
+    if let Some(config) = validation {
+        for name in &prd.validation_profiles {
+            if config.get(name).is_none() {
+                warnings.push(LintWarning::prd(format!(
+                    "validation profile '{name}' is neither defined in validation.json nor built in"
+                )));
+            }
+        }
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventStatus, LedgerEvent};
+
+    fn sample_prd() -> Prd {
+        Prd::from_json(
+            r#"{"schemaVersion":"1.0","slug":"f","title":"F","activeRunId":"f-1","validationProfiles":["rust-cargo"],"requirements":[
+                {"id":"REQ-01","title":"A","status":"done","acceptanceCriteria":["Given X"]},
+                {"id":"REQ-02","title":"B","status":"todo","acceptanceCriteria":["Given Y"]}
+            ]}"#,
+        )
+        .unwrap()
+    }
+
+    fn sample_config() -> ValidationConfig {
+        ValidationConfig::from_json(
+            r#"{"schemaVersion":"1.0","profiles":{"rust-cargo":{"detect":{},"commands":{}}}}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_clean_prd_has_no_warnings() {
+        let mut ledger = Ledger::new();
+        ledger
+            .append(LedgerEvent::new(1, "REQ-01", EventStatus::Done))
+            .unwrap();
+        let warnings = lint_prd(&sample_prd(), Some(&ledger), Some(&sample_config()));
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn test_done_without_ledger_events() {
+        let warnings = lint_prd(&sample_prd(), None, None);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].requirement.as_deref(), Some("REQ-01"));
+    }
+
+    #[test]
+    fn test_duplicate_and_non_sequential_ids() {
+        let mut prd = sample_prd();
+        prd.requirements[0].status = RequirementStatus::Todo;
+        prd.requirements[1].id = "REQ-01".to_string();
+        let warnings = lint_prd(&prd, None, None);
+        assert!(warnings
+            .iter()
+            .any(|w| w.message == "duplicate requirement ID"));
+        assert!(warnings
+            .iter()
+            .any(|w| w.message.starts_with("non-sequential ID")));
+    }
+
+    #[test]
+    fn test_split_ids_are_not_non_sequential() {
+        let mut prd = sample_prd();
+        prd.requirements[0].status = RequirementStatus::Todo;
+        prd.requirements[1].id = "REQ-03".to_string();
+        assert_eq!(lint_prd(&prd, None, None).len(), 1);
+
+        // REQ-02 moved out of this feature
+        let mut ledger = Ledger::new();
+        ledger
+            .append(LedgerEvent::moved(
+                0,
+                "REQ-02",
+                "f",
+                "g",
+                "Split into feature 'g'",
+            ))
+            .unwrap();
+        assert!(lint_prd(&prd, Some(&ledger), None).is_empty());
+
+        // REQ-03 moved into this feature, with REQ-04 planned after it
+        prd.requirements[0].id = "REQ-03".to_string();
+        prd.requirements[1].id = "REQ-04".to_string();
+        let mut ledger = Ledger::new();
+        ledger
+            .append(LedgerEvent::moved(
+                0,
+                "REQ-03",
+                "g",
+                "f",
+                "Split from feature 'g'",
+            ))
+            .unwrap();
+        assert!(lint_prd(&prd, Some(&ledger), None).is_empty());
+    }
+
+    #[test]
+    fn test_empty_criteria_and_unknown_profile() {
+        let mut prd = sample_prd();
+        prd.requirements[0].status = RequirementStatus::Todo;
+        prd.requirements[1].acceptance_criteria.clear();
+        prd.validation_profiles.push("elixir-mix".to_string());
+        let warnings = lint_prd(&prd, None, Some(&sample_config()));
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0]
+            .to_string()
+            .starts_with("REQ-02: no acceptance criteria"));
+        assert!(warnings[1].message.contains("'elixir-mix'"));
+    }
+}
@@ -0,0 +1,724 @@
+// ABOUTME: Run notifications: POSTs templated JSON webhooks when iterations fail, requirements finish, or runs end
+// ABOUTME: Configured in ralph/notify.json; delivered best-effort via curl like remote ledger sync
+
+use crate::ledger::{EventKind, EventPayload, EventStatus, Ledger, LedgerEvent};
+use crate::secrets::{Secret, SecretResolver};
+use crate::sync::{post_json, Upload};
+use crate::{RalphError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+
+/// Default project notification settings file, relative to the repository root (see
+/// `[paths] notify`)
+pub const NOTIFY_CONFIG_FILE: &str = "ralph/notify.json";
+
+/// Failed iterations of one requirement before a desktop notification is shown
+pub const DESKTOP_REPEATED_FAILURES: usize = 2;
+
+/// What a notification is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyEvent {
+    /// An iteration finished without completing its requirement
+    IterationFailed,
+    /// A requirement passed validation and was marked done
+    RequirementDone,
+    /// The implementation loop stopped, finished or not
+    RunFinished,
+}
+
+impl NotifyEvent {
+    /// Every event, which targets receive unless they list their own
+    pub const ALL: [Self; 3] = [
+        Self::IterationFailed,
+        Self::RequirementDone,
+        Self::RunFinished,
+    ];
+
+    /// Name as written in `notify.json` and payloads
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::IterationFailed => "iteration_failed",
+            Self::RequirementDone => "requirement_done",
+            Self::RunFinished => "run_finished",
+        }
+    }
+}
+
+impl fmt::Display for NotifyEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where notifications are sent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum NotifyTarget {
+    /// POST a JSON payload to an endpoint
+    Webhook {
+        url: String,
+        /// Events to send (default: all)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        events: Option<Vec<NotifyEvent>>,
+        /// Name of a secret sent as a bearer token (resolved from env, .env, or keychain)
+        #[serde(
+            default,
+            rename = "tokenSecret",
+            skip_serializing_if = "Option::is_none"
+        )]
+        token_secret: Option<String>,
+        /// JSON body with `{name}` placeholders for the [`Notification`] fields (default:
+        /// the notification itself)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload: Option<Value>,
+    },
+    /// Post the compact progress line to a Slack incoming webhook
+    Slack {
+        /// Webhook URL in plain text; prefer `urlSecret`, since the URL is a credential
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        /// Name of a secret holding the webhook URL (resolved from env, .env, or keychain)
+        #[serde(default, rename = "urlSecret", skip_serializing_if = "Option::is_none")]
+        url_secret: Option<String>,
+        /// Events to send (default: all)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        events: Option<Vec<NotifyEvent>>,
+    },
+    /// Post the compact progress line to a Discord channel webhook
+    Discord {
+        /// Webhook URL in plain text; prefer `urlSecret`, since the URL is a credential
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        /// Name of a secret holding the webhook URL (resolved from env, .env, or keychain)
+        #[serde(default, rename = "urlSecret", skip_serializing_if = "Option::is_none")]
+        url_secret: Option<String>,
+        /// Events to send (default: all)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        events: Option<Vec<NotifyEvent>>,
+    },
+    /// Show a desktop notification (via notify-send or osascript) when the run ends or a
+    /// requirement fails [`DESKTOP_REPEATED_FAILURES`] times
+    Desktop {
+        /// Events to show (default: failures and the run's end)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        events: Option<Vec<NotifyEvent>>,
+    },
+}
+
+/// Project notification settings, stored in [`NOTIFY_CONFIG_FILE`] unless `[paths] notify`
+/// says otherwise
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyConfig {
+    /// Targets notifications are sent to
+    #[serde(default)]
+    pub targets: Vec<NotifyTarget>,
+}
+
+impl NotifyConfig {
+    /// Load notification config from a JSON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or contains invalid JSON.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref())?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Load the notification config at `path`, or no targets if there is none
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but is not valid notification config.
+    pub fn open(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::from_file(path).map_err(|e| RalphError::Config(format!("{}: {e}", path.display())))
+    }
+}
+
+/// Progress counts from the ledger at the time of a notification
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerSummary {
+    /// Requirements completed so far
+    pub requirements_done: usize,
+    /// Requirements in the PRD
+    pub requirements_total: usize,
+    /// Iterations run on the feature
+    pub iterations: u32,
+    /// Failed iterations of the notification's requirement (of the whole run for
+    /// `run_finished`)
+    pub failed_iterations: usize,
+}
+
+/// One notification, as sent when a target has no payload template
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    pub event: NotifyEvent,
+    /// Feature slug
+    pub slug: String,
+    /// Requirement the event is about (none for `run_finished`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requirement: Option<String>,
+    /// `failed`, `done`, `complete`, or `stopped`
+    pub status: String,
+    pub iteration: u32,
+    pub summary: LedgerSummary,
+    /// One-line description for humans
+    pub message: String,
+    /// Compact progress line for chat, e.g. `auth: 4/7 done, REQ-05 failed validation twice`
+    pub progress: String,
+}
+
+impl Notification {
+    /// Body for `template`: strings that are exactly `{name}` become that field's JSON value,
+    /// and other `{name}` placeholders are replaced with its text
+    #[must_use]
+    pub fn render(&self, template: &Value) -> Value {
+        let fields = match serde_json::to_value(self) {
+            Ok(Value::Object(fields)) => fields,
+            _ => Map::new(),
+        };
+        fill(template, &fields)
+    }
+}
+
+fn fill(template: &Value, fields: &Map<String, Value>) -> Value {
+    match template {
+        Value::String(text) => {
+            let whole = text
+                .strip_prefix('{')
+                .and_then(|rest| rest.strip_suffix('}'))
+                .and_then(|name| fields.get(name));
+            if let Some(value) = whole {
+                return value.clone();
+            }
+            let mut text = text.clone();
+            for (name, value) in fields {
+                let value = match value {
+                    Value::String(value) => value.clone(),
+                    other => other.to_string(),
+                };
+                text = text.replace(&format!("{{{name}}}"), &value);
+            }
+            Value::String(text)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|item| fill(item, fields)).collect()),
+        Value::Object(entries) => Value::Object(
+            entries
+                .iter()
+                .map(|(key, value)| (key.clone(), fill(value, fields)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+impl NotifyTarget {
+    /// Whether this target wants `event`
+    #[must_use]
+    pub fn wants(&self, event: NotifyEvent) -> bool {
+        match self {
+            Self::Webhook { events, .. }
+            | Self::Slack { events, .. }
+            | Self::Discord { events, .. } => events.as_ref().map_or(true, |e| e.contains(&event)),
+            Self::Desktop { events } => events
+                .as_ref()
+                .map_or(event != NotifyEvent::RequirementDone, |e| {
+                    e.contains(&event)
+                }),
+        }
+    }
+
+    /// Whether this target is sent `notification`: one of its events and, for the desktop,
+    /// not a requirement's first failure
+    #[must_use]
+    pub fn accepts(&self, notification: &Notification) -> bool {
+        if !self.wants(notification.event) {
+            return false;
+        }
+        match self {
+            Self::Desktop { .. } => {
+                notification.event != NotifyEvent::IterationFailed
+                    || notification.summary.failed_iterations >= DESKTOP_REPEATED_FAILURES
+            }
+            _ => true,
+        }
+    }
+
+    /// Deliver `notification` to this target
+    ///
+    /// With the `desktop-notify` feature, desktop notifications go through the platform's
+    /// notification API; everything else runs the command from [`NotifyTarget::upload`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the notification can't be built or delivered.
+    pub fn deliver(&self, notification: &Notification, secrets: &SecretResolver) -> Result<()> {
+        #[cfg(feature = "desktop-notify")]
+        if let Self::Desktop { .. } = self {
+            return notify_rust::Notification::new()
+                .appname("ralph")
+                .summary(&desktop_title(notification))
+                .body(&notification.progress)
+                .show()
+                .map(|_| ())
+                .map_err(|e| RalphError::Command(format!("desktop notification: {e}")));
+        }
+        self.upload(notification, secrets)?.run()
+    }
+
+    /// Build the command that delivers `notification` to this target
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required secret or webhook URL is missing, or desktop
+    /// notifications are not supported on this platform.
+    pub fn upload(&self, notification: &Notification, secrets: &SecretResolver) -> Result<Upload> {
+        match self {
+            Self::Webhook {
+                url,
+                token_secret,
+                payload,
+                ..
+            } => {
+                let body = match payload {
+                    Some(template) => notification.render(template),
+                    None => serde_json::to_value(notification)?,
+                };
+                let token = token_secret
+                    .as_deref()
+                    .map(|name| secrets.require(name))
+                    .transpose()?;
+                Ok(post_json(url, &body.to_string(), token.as_ref()))
+            }
+            Self::Slack {
+                url, url_secret, ..
+            } => Ok(post_json(
+                webhook_url("slack", url.as_deref(), url_secret.as_deref(), secrets)?.expose(),
+                &json!({ "text": notification.progress }).to_string(),
+                None,
+            )),
+            Self::Discord {
+                url, url_secret, ..
+            } => Ok(post_json(
+                webhook_url("discord", url.as_deref(), url_secret.as_deref(), secrets)?.expose(),
+                &json!({ "content": notification.progress }).to_string(),
+                None,
+            )),
+            Self::Desktop { .. } => desktop(&desktop_title(notification), &notification.progress),
+        }
+    }
+}
+
+/// Title of the desktop notification for `notification`
+fn desktop_title(notification: &Notification) -> String {
+    format!("ralph: {}", notification.slug)
+}
+
+/// Command showing a desktop notification
+fn desktop(title: &str, body: &str) -> Result<Upload> {
+    if cfg!(target_os = "macos") {
+        let quote = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+        Ok(Upload {
+            program: "osascript",
+            args: vec![
+                "-e".to_string(),
+                format!(
+                    "display notification \"{}\" with title \"{}\"",
+                    quote(body),
+                    quote(title)
+                ),
+            ],
+            stdin: String::new(),
+        })
+    } else if cfg!(windows) {
+        Err(RalphError::Config(
+            "desktop notifications need notify-send (Linux), osascript (macOS), or a build \
+             with the desktop-notify feature"
+                .to_string(),
+        ))
+    } else {
+        Ok(Upload {
+            program: "notify-send",
+            args: vec![
+                "--app-name=ralph".to_string(),
+                title.to_string(),
+                body.to_string(),
+            ],
+            stdin: String::new(),
+        })
+    }
+}
+
+/// A chat target's webhook URL: the `url_secret` secret if named, else the plain `url`
+fn webhook_url(
+    kind: &str,
+    url: Option<&str>,
+    url_secret: Option<&str>,
+    secrets: &SecretResolver,
+) -> Result<Secret> {
+    match (url_secret, url) {
+        (Some(name), _) => secrets.require(name),
+        (None, Some(url)) => Ok(Secret::new(url)),
+        (None, None) => Err(RalphError::Config(format!(
+            "{kind} notify target needs a url or urlSecret"
+        ))),
+    }
+}
+
+/// `once`, `twice`, or `N times`
+fn times(count: usize) -> String {
+    match count {
+        1 => "once".to_string(),
+        2 => "twice".to_string(),
+        n => format!("{n} times"),
+    }
+}
+
+/// Sends notifications for a feature's run to every configured target
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    config: NotifyConfig,
+    slug: String,
+    requirements_total: usize,
+    secrets: SecretResolver,
+    last_error: Option<String>,
+}
+
+impl Notifier {
+    /// Create a notifier for a feature with `requirements_total` requirements
+    #[must_use]
+    pub fn new(
+        config: NotifyConfig,
+        slug: impl Into<String>,
+        requirements_total: usize,
+        secrets: SecretResolver,
+    ) -> Self {
+        Self {
+            config,
+            slug: slug.into(),
+            requirements_total,
+            secrets,
+            last_error: None,
+        }
+    }
+
+    /// The notification an appended event calls for, if any: a finished iteration's
+    /// failure or its requirement's completion
+    #[must_use]
+    pub fn notification_for(&self, event: &LedgerEvent, ledger: &Ledger) -> Option<Notification> {
+        if event.kind != EventKind::Requirement {
+            return None;
+        }
+        let Some(EventPayload::IterationFinished { success }) = event.payload_or_inferred() else {
+            return None;
+        };
+        let requirement = &event.requirement;
+        let summary = self.summarize(ledger, Some(requirement));
+        let (kind, status, message, outcome) = match (success, event.status == EventStatus::Done) {
+            (true, true) => (
+                NotifyEvent::RequirementDone,
+                "done",
+                format!("{requirement} done"),
+                format!("{requirement} done"),
+            ),
+            _ => (
+                NotifyEvent::IterationFailed,
+                "failed",
+                format!(
+                    "{requirement} failed iteration {}{}",
+                    event.iteration,
+                    event
+                        .message
+                        .as_deref()
+                        .map_or(String::new(), |m| format!(": {m}"))
+                ),
+                format!(
+                    "{requirement} failed{} {}",
+                    if event.validation_passed == Some(false) {
+                        " validation"
+                    } else {
+                        ""
+                    },
+                    times(summary.failed_iterations)
+                ),
+            ),
+        };
+        Some(Notification {
+            event: kind,
+            slug: self.slug.clone(),
+            requirement: Some(requirement.clone()),
+            status: status.to_string(),
+            iteration: event.iteration,
+            message: format!(
+                "{}: {message} ({}/{} requirements done)",
+                self.slug, summary.requirements_done, summary.requirements_total
+            ),
+            progress: self.progress_line(&summary, &outcome),
+            summary,
+        })
+    }
+
+    /// Send the notification `event` calls for, if any (see [`Notifier::notification_for`])
+    pub fn observe(&mut self, event: &LedgerEvent, ledger: &Ledger) {
+        if let Some(notification) = self.notification_for(event, ledger) {
+            self.send(&notification);
+        }
+    }
+
+    /// Announce that the run stopped with `done` of `total` requirements complete, and why
+    /// if it stopped early
+    pub fn run_finished(
+        &mut self,
+        ledger: &Ledger,
+        done: usize,
+        total: usize,
+        reason: Option<&str>,
+    ) {
+        let mut summary = self.summarize(ledger, None);
+        summary.requirements_done = done;
+        summary.requirements_total = total;
+        let (status, outcome) = match reason {
+            None if done == total => ("complete", "run complete".to_string()),
+            None => ("stopped", "run stopped".to_string()),
+            Some(reason) => ("stopped", format!("run stopped ({reason})")),
+        };
+        self.send(&Notification {
+            event: NotifyEvent::RunFinished,
+            slug: self.slug.clone(),
+            requirement: None,
+            status: status.to_string(),
+            iteration: ledger.latest_iteration(),
+            message: format!("{}: {outcome}, {done}/{total} requirements done", self.slug),
+            progress: self.progress_line(&summary, &outcome),
+            summary,
+        });
+    }
+
+    /// `slug: done/total done, outcome`
+    fn progress_line(&self, summary: &LedgerSummary, outcome: &str) -> String {
+        format!(
+            "{}: {}/{} done, {outcome}",
+            self.slug, summary.requirements_done, summary.requirements_total
+        )
+    }
+
+    /// Deliver `notification` to every target that wants it
+    ///
+    /// Failures never propagate; the most recent is kept in [`Notifier::last_error`].
+    pub fn send(&mut self, notification: &Notification) {
+        for target in &self.config.targets {
+            if !target.accepts(notification) {
+                continue;
+            }
+            if let Err(e) = target.deliver(notification, &self.secrets) {
+                self.last_error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Most recent delivery error
+    #[must_use]
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Ledger counts, with failed iterations of `requirement` (or of every requirement)
+    fn summarize(&self, ledger: &Ledger, requirement: Option<&str>) -> LedgerSummary {
+        let events = ledger
+            .events()
+            .iter()
+            .filter(|e| e.kind == EventKind::Requirement);
+        let done: BTreeSet<&str> = events
+            .clone()
+            .filter(|e| e.status == EventStatus::Done)
+            .map(|e| e.requirement.as_str())
+            .collect();
+        let failed_iterations = events
+            .filter(|e| requirement.map_or(true, |id| e.requirement == id))
+            .filter(|e| {
+                matches!(
+                    e.payload_or_inferred(),
+                    Some(EventPayload::IterationFinished { success: false })
+                )
+            })
+            .count();
+        LedgerSummary {
+            requirements_done: done.len(),
+            requirements_total: self.requirements_total,
+            iterations: ledger.latest_iteration(),
+            failed_iterations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::SecretBackend;
+    use crate::sync::curl_config_value;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_notifications_from_ledger_events() {
+        let mut ledger = Ledger::new();
+        let failed = LedgerEvent::new(1, "REQ-01", EventStatus::Failed)
+            .with_message("validation failed")
+            .with_validation(false)
+            .with_payload(EventPayload::IterationFinished { success: false });
+        let done = LedgerEvent::new(2, "REQ-01", EventStatus::Done)
+            .with_payload(EventPayload::IterationFinished { success: true });
+        ledger.append(failed.clone()).unwrap();
+        ledger.append(done.clone()).unwrap();
+
+        let notifier = Notifier::new(NotifyConfig::default(), "auth", 3, SecretResolver::new());
+        let notification = notifier.notification_for(&failed, &ledger).unwrap();
+        assert_eq!(notification.event, NotifyEvent::IterationFailed);
+        assert_eq!(
+            notification.message,
+            "auth: REQ-01 failed iteration 1: validation failed (1/3 requirements done)"
+        );
+        assert_eq!(notification.summary.failed_iterations, 1);
+        assert_eq!(
+            notification.progress,
+            "auth: 1/3 done, REQ-01 failed validation once"
+        );
+
+        let notification = notifier.notification_for(&done, &ledger).unwrap();
+        assert_eq!(notification.event, NotifyEvent::RequirementDone);
+        assert_eq!(notification.status, "done");
+        assert_eq!(notification.summary.iterations, 2);
+
+        let started = LedgerEvent::new(3, "REQ-02", EventStatus::Started);
+        assert!(notifier.notification_for(&started, &ledger).is_none());
+    }
+
+    #[test]
+    fn test_desktop_waits_for_repeated_failures() {
+        let mut ledger = Ledger::new();
+        let desktop = NotifyTarget::Desktop { events: None };
+        let notifier = Notifier::new(NotifyConfig::default(), "auth", 3, SecretResolver::new());
+        let mut shown = Vec::new();
+        for iteration in 1..=2 {
+            let failed =
+                LedgerEvent::new(iteration, "REQ-05", EventStatus::Failed).with_validation(false);
+            ledger.append(failed.clone()).unwrap();
+            let notification = notifier.notification_for(&failed, &ledger).unwrap();
+            shown.push(desktop.accepts(&notification));
+        }
+        assert_eq!(shown, vec![false, true]);
+
+        let done = LedgerEvent::new(3, "REQ-05", EventStatus::Done);
+        ledger.append(done.clone()).unwrap();
+        assert!(!desktop.accepts(&notifier.notification_for(&done, &ledger).unwrap()));
+        assert!(desktop.wants(NotifyEvent::RunFinished));
+    }
+
+    #[test]
+    fn test_webhook_payload_template() {
+        let config: NotifyConfig = serde_json::from_str(
+            r#"{"targets":[{"type":"webhook","url":"https://hooks.example.com/ralph",
+                "events":["run_finished"],
+                "payload":{"text":"{slug} is {status}","counts":"{summary}","iteration":"{iteration}"}}]}"#,
+        )
+        .unwrap();
+        let target = &config.targets[0];
+        assert!(target.wants(NotifyEvent::RunFinished));
+        assert!(!target.wants(NotifyEvent::IterationFailed));
+
+        let notification = Notification {
+            event: NotifyEvent::RunFinished,
+            slug: "auth".to_string(),
+            requirement: None,
+            status: "complete".to_string(),
+            iteration: 7,
+            summary: LedgerSummary {
+                requirements_done: 3,
+                requirements_total: 3,
+                iterations: 7,
+                failed_iterations: 2,
+            },
+            message: "auth: run complete".to_string(),
+            progress: "auth: 3/3 done, run complete".to_string(),
+        };
+        let upload = target
+            .upload(&notification, &SecretResolver::new())
+            .unwrap();
+        assert_eq!(
+            curl_config_value(&upload.stdin, "url").as_deref(),
+            Some("https://hooks.example.com/ralph")
+        );
+        let body = curl_config_value(&upload.stdin, "data-binary").unwrap();
+        let body: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "text": "auth is complete",
+                "counts": {"requirementsDone": 3, "requirementsTotal": 3, "iterations": 7, "failedIterations": 2},
+                "iteration": 7
+            })
+        );
+
+        let chat: NotifyConfig = serde_json::from_str(
+            r#"{"targets":[{"type":"slack","url":"https://hooks.slack.com/services/T/B/X"},
+                {"type":"discord","url":"https://discord.com/api/webhooks/1/x"}]}"#,
+        )
+        .unwrap();
+        let bodies: Vec<Value> = chat
+            .targets
+            .iter()
+            .map(|target| {
+                let upload = target
+                    .upload(&notification, &SecretResolver::new())
+                    .unwrap();
+                serde_json::from_str(&curl_config_value(&upload.stdin, "data-binary").unwrap())
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(
+            bodies,
+            vec![
+                json!({"text": "auth: 3/3 done, run complete"}),
+                json!({"content": "auth: 3/3 done, run complete"})
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chat_webhook_url_secret() {
+        let dir = tempdir().unwrap();
+        let env = dir.path().join(".env");
+        let url = "https://hooks.slack.com/services/T000/B000/XXXXSECRET";
+        std::fs::write(&env, format!("RALPH_SLACK_WEBHOOK={url}\n")).unwrap();
+        let secrets = SecretResolver::new().with_backend(SecretBackend::DotEnv(env));
+        let config: NotifyConfig = serde_json::from_str(
+            r#"{"targets":[{"type":"slack","urlSecret":"RALPH_SLACK_WEBHOOK"},{"type":"discord"}]}"#,
+        )
+        .unwrap();
+        let notifier = Notifier::new(NotifyConfig::default(), "auth", 1, SecretResolver::new());
+        let mut ledger = Ledger::new();
+        let done = LedgerEvent::new(1, "REQ-01", EventStatus::Done);
+        ledger.append(done.clone()).unwrap();
+        let notification = notifier.notification_for(&done, &ledger).unwrap();
+
+        let upload = config.targets[0].upload(&notification, &secrets).unwrap();
+        assert!(upload.args.iter().all(|arg| !arg.contains("XXXXSECRET")));
+        assert_eq!(
+            curl_config_value(&upload.stdin, "url").as_deref(),
+            Some(url)
+        );
+
+        // Neither a URL nor a secret naming one
+        assert!(config.targets[1].upload(&notification, &secrets).is_err());
+    }
+}
@@ -0,0 +1,320 @@
+// ABOUTME: Converts ledger history into OpenTelemetry traces (OTLP/JSON)
+// ABOUTME: Spans nest run -> requirement -> iteration -> validation stage for tracing UIs like Jaeger or Tempo
+
+use crate::ledger::{EventPayload, EventStatus, Ledger, LedgerEvent, Reproducibility};
+use crate::sync::Upload;
+use crate::{RalphError, Result};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+
+/// Standard environment variable naming the collector's base URL
+pub const OTLP_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
+/// `Span.SpanKind` internal
+const SPAN_KIND_INTERNAL: u8 = 1;
+
+/// `Status.StatusCode` values
+const STATUS_OK: u8 = 1;
+const STATUS_ERROR: u8 = 2;
+
+/// Convert a feature's ledger into an OTLP/JSON `ExportTraceServiceRequest`
+///
+/// Span IDs are derived from the ledger contents, so exporting the same ledger twice
+/// yields the same trace. Ledgers don't record when validation began, so validation
+/// stage spans are instants at the moment validation finished.
+///
+/// # Errors
+///
+/// Returns an error if the ledger has no events.
+pub fn ledger_to_otlp(ledger: &Ledger, feature: &str) -> Result<Value> {
+    let events = ledger.events();
+    let (Some(first), Some(last)) = (events.first(), events.last()) else {
+        return Err(RalphError::Ledger(format!(
+            "Ledger for '{feature}' has no events to export"
+        )));
+    };
+
+    let seed = format!("{feature}@{}", first.timestamp.to_rfc3339());
+    let trace_id = hex_id(&seed, 16);
+    let span = |path: &str| hex_id(&format!("{seed}/{path}"), 8);
+    let run_id = span("run");
+
+    let mut spans = vec![span_json(
+        &trace_id,
+        &run_id,
+        None,
+        &format!("ralph run {feature}"),
+        (first.timestamp, last.timestamp),
+        vec![attr("ralph.feature", feature)],
+        None,
+    )];
+
+    for requirement in requirements_in_order(events) {
+        let req_events: Vec<&LedgerEvent> = events
+            .iter()
+            .filter(|e| e.requirement == requirement)
+            .collect();
+        let req_id = span(&requirement);
+        spans.push(span_json(
+            &trace_id,
+            &req_id,
+            Some(&run_id),
+            &requirement,
+            time_range(&req_events),
+            vec![attr("ralph.requirement", &requirement)],
+            None,
+        ));
+
+        let mut iterations: Vec<u32> = req_events.iter().map(|e| e.iteration).collect();
+        iterations.dedup();
+        for iteration in iterations {
+            let iter_events: Vec<&LedgerEvent> = req_events
+                .iter()
+                .copied()
+                .filter(|e| e.iteration == iteration)
+                .collect();
+            let iter_id = span(&format!("{requirement}/{iteration}"));
+            spans.push(iteration_span(
+                &trace_id,
+                &iter_id,
+                &req_id,
+                &requirement,
+                iteration,
+                &iter_events,
+            ));
+
+            for (index, event) in iter_events.iter().enumerate() {
+                if let Some(EventPayload::ValidationRun {
+                    passed,
+                    failed_stage,
+                }) = &event.payload
+                {
+                    let stage = failed_stage.as_deref().unwrap_or("all stages");
+                    spans.push(span_json(
+                        &trace_id,
+                        &span(&format!("{requirement}/{iteration}/validation/{index}")),
+                        Some(&iter_id),
+                        &format!("validation: {stage}"),
+                        (event.timestamp, event.timestamp),
+                        vec![attr("ralph.validation.stage", stage)],
+                        (!passed).then(|| format!("{stage} failed")),
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [attr("service.name", "ralph"), attr("ralph.feature", feature)]
+            },
+            "scopeSpans": [{
+                "scope": {"name": "ralph", "version": env!("CARGO_PKG_VERSION")},
+                "spans": spans
+            }]
+        }]
+    }))
+}
+
+/// POST an OTLP/JSON trace export to a collector's `/v1/traces` endpoint (via curl)
+///
+/// # Errors
+///
+/// Returns an error if curl cannot be run or the collector rejects the request.
+pub fn send_otlp(endpoint: &str, request: &Value) -> Result<()> {
+    Upload {
+        program: "curl",
+        args: vec![
+            "--silent".to_string(),
+            "--show-error".to_string(),
+            "--fail".to_string(),
+            "--header".to_string(),
+            "Content-Type: application/json".to_string(),
+            "--data-binary".to_string(),
+            "@-".to_string(),
+            format!("{}/v1/traces", endpoint.trim_end_matches('/')),
+        ],
+        stdin: request.to_string(),
+    }
+    .run()
+}
+
+/// Requirements in the order they first appear in the ledger
+fn requirements_in_order(events: &[LedgerEvent]) -> Vec<String> {
+    let mut requirements: Vec<String> = Vec::new();
+    for event in events {
+        if !requirements.contains(&event.requirement) {
+            requirements.push(event.requirement.clone());
+        }
+    }
+    requirements
+}
+
+/// Span for one iteration, carrying the final event's outcome, usage, and commit
+fn iteration_span(
+    trace_id: &str,
+    span_id: &str,
+    parent: &str,
+    requirement: &str,
+    iteration: u32,
+    events: &[&LedgerEvent],
+) -> Value {
+    let mut attributes = vec![
+        attr("ralph.requirement", requirement),
+        json!({"key": "ralph.iteration", "value": {"intValue": iteration.to_string()}}),
+    ];
+    let outcome = events.last().copied();
+    if let Some(event) = outcome {
+        attributes.push(attr("ralph.status", &format!("{:?}", event.status)));
+        if let Some(model) = &event.model {
+            attributes.push(attr("ralph.model", model));
+        }
+        for (key, tokens) in [
+            ("ralph.tokens.prompt", event.prompt_tokens),
+            ("ralph.tokens.completion", event.completion_tokens),
+        ] {
+            if let Some(tokens) = tokens {
+                attributes.push(json!({"key": key, "value": {"intValue": tokens.to_string()}}));
+            }
+        }
+        if let Some(sha) = &event.commit_sha {
+            attributes.push(attr("vcs.commit.sha", sha));
+        }
+    }
+    let error = outcome
+        .filter(|e| e.status == EventStatus::Failed)
+        .map(|e| e.message.clone().unwrap_or_else(|| "failed".to_string()));
+
+    let mut span = span_json(
+        trace_id,
+        span_id,
+        Some(parent),
+        &format!("{requirement} iteration {iteration}"),
+        time_range(events),
+        attributes,
+        error,
+    );
+    // Notes, annotations, and other payloads become span events
+    span["events"] = events
+        .iter()
+        .filter_map(|e| {
+            e.payload.as_ref().map(|payload| {
+                json!({
+                    "timeUnixNano": unix_nanos(e.timestamp),
+                    "name": payload.describe(),
+                })
+            })
+        })
+        .collect();
+    span
+}
+
+fn span_json(
+    trace_id: &str,
+    span_id: &str,
+    parent: Option<&str>,
+    name: &str,
+    (start, end): (DateTime<Utc>, DateTime<Utc>),
+    attributes: Vec<Value>,
+    error: Option<String>,
+) -> Value {
+    let status = match error {
+        Some(message) => json!({"code": STATUS_ERROR, "message": message}),
+        None => json!({"code": STATUS_OK}),
+    };
+    json!({
+        "traceId": trace_id,
+        "spanId": span_id,
+        "parentSpanId": parent.unwrap_or_default(),
+        "name": name,
+        "kind": SPAN_KIND_INTERNAL,
+        "startTimeUnixNano": unix_nanos(start),
+        "endTimeUnixNano": unix_nanos(end),
+        "attributes": attributes,
+        "status": status,
+    })
+}
+
+fn time_range(events: &[&LedgerEvent]) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = events.iter().map(|e| e.timestamp).min().unwrap_or_default();
+    let end = events.iter().map(|e| e.timestamp).max().unwrap_or_default();
+    (start, end)
+}
+
+/// OTLP/JSON encodes 64-bit integers as strings
+fn unix_nanos(time: DateTime<Utc>) -> String {
+    time.timestamp_nanos_opt().unwrap_or_default().to_string()
+}
+
+fn attr(key: &str, value: &str) -> Value {
+    json!({"key": key, "value": {"stringValue": value}})
+}
+
+/// Hex ID of `bytes` bytes derived from a seed
+fn hex_id(seed: &str, bytes: usize) -> String {
+    Reproducibility::sha256(seed)[..bytes * 2].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_ledger() -> Ledger {
+        Ledger::from_jsonl(
+            r#"{"timestamp":"2026-01-19T10:00:00Z","iteration":1,"requirement":"REQ-01","status":"started"}
+{"timestamp":"2026-01-19T10:05:00Z","iteration":1,"requirement":"REQ-01","status":"in_progress","payload":{"type":"validation_run","passed":false,"failedStage":"Test"}}
+{"timestamp":"2026-01-19T10:05:01Z","iteration":1,"requirement":"REQ-01","status":"failed","model":"gpt-5","promptTokens":1200}
+{"timestamp":"2026-01-19T10:06:00Z","iteration":2,"requirement":"REQ-01","status":"done"}
+{"timestamp":"2026-01-19T10:20:00Z","iteration":3,"requirement":"REQ-02","status":"done"}
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_span_hierarchy() {
+        let request = ledger_to_otlp(&sample_ledger(), "auth").unwrap();
+        let spans = request["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap();
+        let by_name = |name: &str| spans.iter().find(|s| s["name"] == name).unwrap();
+
+        // run, 2 requirements, 3 iterations, 1 validation stage
+        assert_eq!(spans.len(), 7);
+        let run = by_name("ralph run auth");
+        let req = by_name("REQ-01");
+        let iteration = by_name("REQ-01 iteration 1");
+        let stage = by_name("validation: Test");
+        assert_eq!(run["parentSpanId"], "");
+        assert_eq!(req["parentSpanId"], run["spanId"]);
+        assert_eq!(iteration["parentSpanId"], req["spanId"]);
+        assert_eq!(stage["parentSpanId"], iteration["spanId"]);
+        assert!(spans.iter().all(|s| s["traceId"] == run["traceId"]));
+
+        assert_eq!(iteration["status"]["code"], STATUS_ERROR);
+        assert_eq!(stage["status"]["code"], STATUS_ERROR);
+        assert_eq!(by_name("REQ-01 iteration 2")["status"]["code"], STATUS_OK);
+        assert_eq!(
+            run["endTimeUnixNano"],
+            unix_nanos("2026-01-19T10:20:00Z".parse().unwrap())
+        );
+        assert!(iteration["attributes"]
+            .as_array()
+            .unwrap()
+            .contains(&json!({"key": "ralph.tokens.prompt", "value": {"intValue": "1200"}})));
+    }
+
+    #[test]
+    fn test_ids_are_stable_and_sized() {
+        let ledger = sample_ledger();
+        let first = ledger_to_otlp(&ledger, "auth").unwrap();
+        assert_eq!(first, ledger_to_otlp(&ledger, "auth").unwrap());
+
+        let span = &first["resourceSpans"][0]["scopeSpans"][0]["spans"][0];
+        assert_eq!(span["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(span["spanId"].as_str().unwrap().len(), 16);
+
+        assert!(ledger_to_otlp(&Ledger::new(), "auth").is_err());
+    }
+}
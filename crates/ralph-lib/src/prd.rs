@@ -3,7 +3,30 @@
 
 use crate::{RalphError, Result};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Locate a feature's PRD in its task directory
+///
+/// Prefers `prd.json`, then `prd.toml`; returns the `prd.json` path when neither exists.
+#[must_use]
+pub fn prd_path(task_dir: impl AsRef<Path>) -> PathBuf {
+    let task_dir = task_dir.as_ref();
+    let toml_path = task_dir.join("prd.toml");
+    let json_path = task_dir.join("prd.json");
+    if !json_path.exists() && toml_path.exists() {
+        toml_path
+    } else {
+        json_path
+    }
+}
+
+/// Whether a path should be treated as a TOML PRD (by extension)
+fn is_toml_path(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "toml")
+}
+
+/// Title of the auto-generated documentation requirement
+pub const DOCS_REQUIREMENT_TITLE: &str = "Update docs and CHANGELOG for this feature";
 
 /// Status of a requirement
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -16,6 +39,56 @@ pub enum RequirementStatus {
     Blocked,
 }
 
+/// How risky a requirement's change is, driving validation depth
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskLevel {
+    /// Never runs the full test sweep
+    Low,
+    /// Runs the full test sweep every 5th iteration
+    #[default]
+    Medium,
+    /// Always runs the full test sweep
+    High,
+}
+
+impl RiskLevel {
+    /// Whether the full test sweep should run at the given iteration
+    #[must_use]
+    pub fn runs_full_tests(self, iteration: u32) -> bool {
+        match self {
+            Self::Low => false,
+            Self::Medium => iteration % 5 == 0,
+            Self::High => true,
+        }
+    }
+}
+
+/// Per-requirement guidance merged into the implementer prompt
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptHints {
+    /// Extra instructions for the implementer
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instructions: Vec<String>,
+    /// Constraints the implementation must respect
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constraints: Vec<String>,
+    /// Approaches the implementer must not take
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbidden_approaches: Vec<String>,
+}
+
+impl PromptHints {
+    /// Whether there is no guidance at all
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+            && self.constraints.is_empty()
+            && self.forbidden_approaches.is_empty()
+    }
+}
+
 /// A single requirement in a PRD
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -28,6 +101,21 @@ pub struct Requirement {
     pub status: RequirementStatus,
     /// Acceptance criteria (Given/When/Then format)
     pub acceptance_criteria: Vec<String>,
+    /// Risk level (defaults to medium when absent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<RiskLevel>,
+    /// Guidance for the implementer specific to this requirement
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_hints: Option<PromptHints>,
+    /// Paths (or glob patterns) the requirement is expected to touch
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    /// Scheduling priority for the `priority` selection strategy; lower runs first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+    /// Fields not known to Ralph, preserved across read-modify-write cycles
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Product Requirements Document
@@ -46,17 +134,45 @@ pub struct Prd {
     pub validation_profiles: Vec<String>,
     /// List of requirements
     pub requirements: Vec<Requirement>,
+    /// Requirements a human is doing by hand, which the implement loop leaves alone
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skip: Vec<String>,
+    /// Fields not known to Ralph, preserved across read-modify-write cycles
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Prd {
-    /// Load a PRD from a JSON file
+    /// Load a PRD from a JSON or TOML file (detected by `.toml` extension)
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be read or contains invalid JSON.
+    /// Returns an error if the file cannot be read or contains invalid JSON/TOML.
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
         let content = std::fs::read_to_string(path.as_ref())?;
-        Self::from_json(&content)
+        if is_toml_path(path.as_ref()) {
+            Self::from_toml(&content)
+        } else {
+            Self::from_json(&content)
+        }
+    }
+
+    /// Parse a PRD from a TOML string
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML is invalid.
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        toml::from_str(toml).map_err(|e| RalphError::Toml(e.to_string()))
+    }
+
+    /// Serialize the PRD to a TOML string
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails (e.g., an extra field holds a null).
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self).map_err(|e| RalphError::Toml(e.to_string()))
     }
 
     /// Parse a PRD from a JSON string
@@ -86,14 +202,18 @@ impl Prd {
         serde_json::to_string_pretty(self).map_err(RalphError::from)
     }
 
-    /// Save the PRD to a JSON file
+    /// Save the PRD to a JSON or TOML file (detected by `.toml` extension)
     ///
     /// # Errors
     ///
     /// Returns an error if serialization fails or the file cannot be written.
     pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
-        let json = self.to_json_pretty()?;
-        std::fs::write(path.as_ref(), json)?;
+        let content = if is_toml_path(path.as_ref()) {
+            self.to_toml()?
+        } else {
+            self.to_json_pretty()?
+        };
+        std::fs::write(path.as_ref(), content)?;
         Ok(())
     }
 
@@ -159,6 +279,137 @@ impl Prd {
         }
     }
 
+    /// Whether the requirement is on the PRD's skip list
+    #[must_use]
+    pub fn is_skipped(&self, req_id: &str) -> bool {
+        self.skip.iter().any(|id| id == req_id)
+    }
+
+    /// Whether any requirement is still to do or in progress, ignoring skipped ones
+    #[must_use]
+    pub fn has_remaining_work(&self) -> bool {
+        self.requirements.iter().any(|r| {
+            matches!(
+                r.status,
+                RequirementStatus::Todo | RequirementStatus::InProgress
+            ) && !self.is_skipped(&r.id)
+        })
+    }
+
+    /// Generate the next sequential requirement ID (e.g., "REQ-04")
+    #[must_use]
+    pub fn next_requirement_id(&self) -> String {
+        let max = self
+            .requirements
+            .iter()
+            .filter_map(|r| r.id.strip_prefix("REQ-"))
+            .filter_map(|n| n.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0);
+        format!("REQ-{:02}", max + 1)
+    }
+
+    /// Append the documentation requirement if the PRD doesn't have one yet
+    ///
+    /// Returns the ID of the appended requirement, or `None` if it already exists.
+    pub fn append_docs_requirement(&mut self) -> Option<String> {
+        if self
+            .requirements
+            .iter()
+            .any(|r| r.title == DOCS_REQUIREMENT_TITLE)
+        {
+            return None;
+        }
+
+        let id = self.next_requirement_id();
+        self.requirements.push(Requirement {
+            id: id.clone(),
+            title: DOCS_REQUIREMENT_TITLE.to_string(),
+            status: RequirementStatus::Todo,
+            acceptance_criteria: vec![
+                format!(
+                    "Given feature '{}' is implemented, when reading the project docs, then they describe the new behavior",
+                    self.slug
+                ),
+                "Given the CHANGELOG, when reading the unreleased section, then it lists this feature"
+                    .to_string(),
+            ],
+            risk: None,
+            prompt_hints: None,
+            paths: Vec::new(),
+            priority: None,
+            extra: serde_json::Map::new(),
+        });
+        Some(id)
+    }
+
+    /// Pick up to `max` pending requirements whose declared paths are pairwise disjoint
+    ///
+    /// Requirements without `paths` may touch anything and are never batched; skipped ones
+    /// are left out.
+    #[must_use]
+    pub fn parallel_batch(&self, max: usize) -> Vec<&Requirement> {
+        let mut batch: Vec<&Requirement> = Vec::new();
+        for req in &self.requirements {
+            if batch.len() >= max {
+                break;
+            }
+            let pending = matches!(
+                req.status,
+                RequirementStatus::Todo | RequirementStatus::InProgress
+            );
+            if pending
+                && !self.is_skipped(&req.id)
+                && !req.paths.is_empty()
+                && !batch
+                    .iter()
+                    .any(|other| paths_overlap(&req.paths, &other.paths))
+            {
+                batch.push(req);
+            }
+        }
+        batch
+    }
+
+    /// Move the given requirements out of this PRD into a new PRD for `new_slug`
+    ///
+    /// The new PRD inherits the schema version and validation profiles and gets a fresh run ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any requirement ID is not present in this PRD.
+    pub fn split_off(&mut self, new_slug: &str, req_ids: &[String]) -> Result<Prd> {
+        if let Some(missing) = req_ids
+            .iter()
+            .find(|id| !self.requirements.iter().any(|r| &r.id == *id))
+        {
+            return Err(RalphError::PrdValidation(format!(
+                "Requirement {missing} not found in PRD '{}'",
+                self.slug
+            )));
+        }
+
+        let (moved, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.requirements)
+            .into_iter()
+            .partition(|r| req_ids.contains(&r.id));
+        self.requirements = kept;
+        let (moved_skip, kept_skip): (Vec<_>, Vec<_>) = std::mem::take(&mut self.skip)
+            .into_iter()
+            .partition(|id| req_ids.contains(id));
+        self.skip = kept_skip;
+
+        Ok(Prd {
+            schema_version: self.schema_version.clone(),
+            slug: new_slug.to_string(),
+            title: new_slug.replace('-', " "),
+            active_run_id: format!("{new_slug}-{}", chrono::Utc::now().format("%Y%m%d-%H%M%S")),
+            validation_profiles: self.validation_profiles.clone(),
+            requirements: moved,
+            skip: moved_skip,
+            extra: serde_json::Map::new(),
+        })
+    }
+
     /// Generate markdown with RALPH markers for managed sections
     #[must_use]
     pub fn to_markdown_with_markers(&self, planning_log: Option<&str>) -> String {
@@ -199,6 +450,95 @@ impl Prd {
     }
 }
 
+/// A clarifying question captured during planning, with the user's answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClarifyingQuestion {
+    /// The question text
+    pub question: String,
+    /// The answer (empty if unanswered)
+    pub answer: String,
+}
+
+impl ClarifyingQuestion {
+    /// Parse a questions file where each question is a `## Q<n>: <question>` heading
+    /// followed by the user's inline answer
+    #[must_use]
+    pub fn parse_all(content: &str) -> Vec<Self> {
+        fn flush(
+            current: &mut Option<(String, Vec<&str>)>,
+            questions: &mut Vec<ClarifyingQuestion>,
+        ) {
+            if let Some((question, lines)) = current.take() {
+                questions.push(ClarifyingQuestion {
+                    question,
+                    answer: lines.join("\n").trim().to_string(),
+                });
+            }
+        }
+
+        let mut questions = Vec::new();
+        let mut current: Option<(String, Vec<&str>)> = None;
+
+        for line in content.lines() {
+            if let Some(heading) = line.strip_prefix("## Q") {
+                flush(&mut current, &mut questions);
+                let question = heading.split_once(':').map_or(heading, |(_, q)| q);
+                current = Some((question.trim().to_string(), Vec::new()));
+            } else if line.starts_with('#') {
+                flush(&mut current, &mut questions);
+            } else if let Some((_, lines)) = current.as_mut() {
+                lines.push(line);
+            }
+        }
+        flush(&mut current, &mut questions);
+        questions
+    }
+
+    /// Render a set of answered questions as a planning log entry
+    #[must_use]
+    pub fn to_planning_log(questions: &[Self]) -> String {
+        use std::fmt::Write;
+        let mut log = format!(
+            "### Clarifying Q&A ({})\n",
+            chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+        );
+        for (i, q) in questions.iter().enumerate() {
+            let answer = if q.answer.is_empty() {
+                "_(unanswered)_"
+            } else {
+                &q.answer
+            };
+            let _ = write!(log, "\n**Q{}:** {}\n**A:** {}\n", i + 1, q.question, answer);
+        }
+        log
+    }
+}
+
+/// Check whether two sets of path patterns could touch the same files
+///
+/// Patterns are compared by their literal directory prefix (up to the first glob character),
+/// so `src/api/**` overlaps `src/api/routes.rs` but not `src/web`.
+#[must_use]
+pub fn paths_overlap(a: &[String], b: &[String]) -> bool {
+    fn prefix(pattern: &str) -> Vec<&str> {
+        let literal = pattern
+            .find(['*', '?', '['])
+            .map_or(pattern, |idx| &pattern[..idx]);
+        literal
+            .split('/')
+            .filter(|part| !part.is_empty() && *part != ".")
+            .collect()
+    }
+
+    a.iter().any(|x| {
+        let x = prefix(x);
+        b.iter().any(|y| {
+            let y = prefix(y);
+            x.starts_with(&y) || y.starts_with(&x)
+        })
+    })
+}
+
 /// Manages markdown files with RALPH markers
 pub struct MarkdownPrd {
     content: String,
@@ -298,7 +638,14 @@ mod tests {
                 title: "Test requirement".to_string(),
                 status: RequirementStatus::Todo,
                 acceptance_criteria: vec!["Given X, when Y, then Z".to_string()],
+                risk: None,
+                prompt_hints: None,
+                paths: Vec::new(),
+                priority: None,
+                extra: serde_json::Map::new(),
             }],
+            skip: Vec::new(),
+            extra: serde_json::Map::new(),
         }
     }
 
@@ -319,6 +666,33 @@ mod tests {
         assert_eq!(prd, loaded);
     }
 
+    #[test]
+    fn test_prd_toml_roundtrip() {
+        let mut prd = sample_prd();
+        prd.requirements[0].risk = Some(RiskLevel::High);
+        prd.extra
+            .insert("owner".to_string(), serde_json::json!("team-a"));
+        let toml = prd.to_toml().unwrap();
+        assert!(toml.contains("slug = \"test-feature\""));
+        assert!(toml.contains("[[requirements]]"));
+        assert_eq!(Prd::from_toml(&toml).unwrap(), prd);
+    }
+
+    #[test]
+    fn test_prd_toml_file_detected_by_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let prd = sample_prd();
+        assert_eq!(prd_path(dir.path()), dir.path().join("prd.json"));
+
+        let path = dir.path().join("prd.toml");
+        prd.save(&path).unwrap();
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("schemaVersion = "));
+        assert_eq!(Prd::from_file(&path).unwrap(), prd);
+        assert_eq!(prd_path(dir.path()), path);
+    }
+
     #[test]
     fn test_requirement_status_serialization() {
         assert_eq!(
@@ -356,6 +730,162 @@ mod tests {
         assert!(!prd.update_requirement_status("REQ-99", RequirementStatus::Done));
     }
 
+    #[test]
+    fn test_has_remaining_work() {
+        let mut prd = sample_prd();
+        assert!(prd.has_remaining_work());
+        prd.skip.push("REQ-01".to_string());
+        assert!(!prd.has_remaining_work());
+        prd.skip.clear();
+        prd.update_requirement_status("REQ-01", RequirementStatus::Done);
+        assert!(!prd.has_remaining_work());
+    }
+
+    #[test]
+    fn test_next_requirement_id() {
+        let mut prd = sample_prd();
+        assert_eq!(prd.next_requirement_id(), "REQ-02");
+        prd.requirements[0].id = "REQ-09".to_string();
+        assert_eq!(prd.next_requirement_id(), "REQ-10");
+    }
+
+    #[test]
+    fn test_append_docs_requirement_once() {
+        let mut prd = sample_prd();
+        assert_eq!(prd.append_docs_requirement(), Some("REQ-02".to_string()));
+        assert_eq!(prd.requirements.len(), 2);
+        assert_eq!(prd.requirements[1].title, DOCS_REQUIREMENT_TITLE);
+        assert_eq!(prd.requirements[1].status, RequirementStatus::Todo);
+        assert_eq!(prd.append_docs_requirement(), None);
+        assert_eq!(prd.requirements.len(), 2);
+    }
+
+    #[test]
+    fn test_unknown_fields_preserved() {
+        let json = r#"{"schemaVersion":"1.0","slug":"x","title":"X","activeRunId":"x-1","validationProfiles":[],"owner":"team-a","requirements":[{"id":"REQ-01","title":"T","status":"todo","acceptanceCriteria":[],"estimate":3}]}"#;
+        let mut prd = Prd::from_json(json).unwrap();
+        assert_eq!(prd.extra["owner"], "team-a");
+        assert_eq!(prd.requirements[0].extra["estimate"], 3);
+
+        prd.update_requirement_status("REQ-01", RequirementStatus::Done);
+        let value: serde_json::Value = serde_json::from_str(&prd.to_json().unwrap()).unwrap();
+        assert_eq!(value["owner"], "team-a");
+        assert_eq!(value["requirements"][0]["estimate"], 3);
+        assert_eq!(value["requirements"][0]["status"], "done");
+    }
+
+    #[test]
+    fn test_risk_level_cadence() {
+        assert!(!RiskLevel::Low.runs_full_tests(5));
+        assert!(!RiskLevel::Medium.runs_full_tests(4));
+        assert!(RiskLevel::Medium.runs_full_tests(5));
+        assert!(RiskLevel::High.runs_full_tests(1));
+    }
+
+    #[test]
+    fn test_risk_level_parsing() {
+        let json =
+            r#"{"id":"REQ-01","title":"T","status":"todo","acceptanceCriteria":[],"risk":"high"}"#;
+        let req: Requirement = serde_json::from_str(json).unwrap();
+        assert_eq!(req.risk, Some(RiskLevel::High));
+        assert!(req.extra.is_empty());
+
+        let prd = sample_prd();
+        assert!(!prd.to_json().unwrap().contains("risk"));
+    }
+
+    #[test]
+    fn test_paths_overlap() {
+        let paths = |items: &[&str]| items.iter().map(|s| (*s).to_string()).collect::<Vec<_>>();
+        assert!(paths_overlap(
+            &paths(&["src/api/**"]),
+            &paths(&["src/api/routes.rs"])
+        ));
+        assert!(paths_overlap(&paths(&["./src"]), &paths(&["src/web/"])));
+        assert!(!paths_overlap(
+            &paths(&["src/api"]),
+            &paths(&["src/apiv2", "docs"])
+        ));
+        // A bare glob covers everything
+        assert!(paths_overlap(&paths(&["*.rs"]), &paths(&["src/lib.rs"])));
+    }
+
+    #[test]
+    fn test_parallel_batch() {
+        let mut prd = sample_prd();
+        for (id, paths) in [
+            ("REQ-02", vec!["src/api/**"]),
+            ("REQ-03", vec!["src/api/routes.rs"]),
+            ("REQ-04", vec!["src/web"]),
+        ] {
+            let mut req = prd.requirements[0].clone();
+            req.id = id.to_string();
+            req.paths = paths.into_iter().map(String::from).collect();
+            prd.requirements.push(req);
+        }
+
+        // REQ-01 declares no paths, REQ-03 overlaps REQ-02
+        let ids: Vec<&str> = prd
+            .parallel_batch(4)
+            .iter()
+            .map(|r| r.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["REQ-02", "REQ-04"]);
+        assert_eq!(prd.parallel_batch(1).len(), 1);
+
+        prd.update_requirement_status("REQ-02", RequirementStatus::Done);
+        let ids: Vec<&str> = prd
+            .parallel_batch(4)
+            .iter()
+            .map(|r| r.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["REQ-03", "REQ-04"]);
+
+        prd.skip.push("REQ-04".to_string());
+        let ids: Vec<&str> = prd
+            .parallel_batch(4)
+            .iter()
+            .map(|r| r.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["REQ-03"]);
+    }
+
+    #[test]
+    fn test_split_off() {
+        let mut prd = sample_prd();
+        prd.append_docs_requirement();
+
+        let split = prd
+            .split_off("docs-feature", &["REQ-02".to_string()])
+            .unwrap();
+        assert_eq!(prd.requirements.len(), 1);
+        assert_eq!(prd.requirements[0].id, "REQ-01");
+        assert_eq!(split.slug, "docs-feature");
+        assert_eq!(split.validation_profiles, prd.validation_profiles);
+        assert_eq!(split.requirements.len(), 1);
+        assert_eq!(split.requirements[0].id, "REQ-02");
+        assert!(split.active_run_id.starts_with("docs-feature-"));
+    }
+
+    #[test]
+    fn test_split_off_unknown_requirement() {
+        let mut prd = sample_prd();
+        assert!(prd.split_off("other", &["REQ-99".to_string()]).is_err());
+        assert_eq!(prd.requirements.len(), 1);
+    }
+
+    #[test]
+    fn test_prompt_hints_parsing() {
+        let json = r#"{"id":"REQ-01","title":"T","status":"todo","acceptanceCriteria":[],"promptHints":{"constraints":["No new deps"],"forbiddenApproaches":["unsafe"]}}"#;
+        let req: Requirement = serde_json::from_str(json).unwrap();
+        let hints = req.prompt_hints.unwrap();
+        assert!(hints.instructions.is_empty());
+        assert_eq!(hints.constraints, vec!["No new deps"]);
+        assert_eq!(hints.forbidden_approaches, vec!["unsafe"]);
+        assert!(!hints.is_empty());
+        assert!(PromptHints::default().is_empty());
+    }
+
     #[test]
     fn test_parse_example_prd() {
         let json = r#"{"schemaVersion":"1.0","slug":"example-feature","title":"Example feature","activeRunId":"example-20260119-1","validationProfiles":["rust-cargo"],"requirements":[{"id":"REQ-01","title":"Add endpoint","status":"todo","acceptanceCriteria":["Given valid request, when calling POST /v1/example, then returns 200"]}]}"#;
@@ -376,6 +906,22 @@ mod tests {
         assert!(md.contains("<!-- RALPH:END PLANNING_LOG -->"));
     }
 
+    #[test]
+    fn test_parse_clarifying_questions() {
+        let content = "# Clarifying questions\n\nIntro text\n\n## Q1: Who are the users?\n\nInternal admins.\n\n## Q2: Any latency target?\n\n## Q3: Auth?\nOAuth\nvia SSO\n";
+        let questions = ClarifyingQuestion::parse_all(content);
+        assert_eq!(questions.len(), 3);
+        assert_eq!(questions[0].question, "Who are the users?");
+        assert_eq!(questions[0].answer, "Internal admins.");
+        assert_eq!(questions[1].answer, "");
+        assert_eq!(questions[2].answer, "OAuth\nvia SSO");
+
+        let log = ClarifyingQuestion::to_planning_log(&questions);
+        assert!(log.starts_with("### Clarifying Q&A"));
+        assert!(log.contains("**Q1:** Who are the users?\n**A:** Internal admins."));
+        assert!(log.contains("**Q2:** Any latency target?\n**A:** _(unanswered)_"));
+    }
+
     #[test]
     fn test_markdown_prd_get_section() {
         let content = "# Title\n\n<!-- RALPH:BEGIN PLANNING_LOG -->\nSome notes\n<!-- RALPH:END PLANNING_LOG -->\n";
@@ -429,6 +975,11 @@ mod proptests {
                 title,
                 status,
                 acceptance_criteria: criteria,
+                risk: None,
+                prompt_hints: None,
+                paths: Vec::new(),
+                priority: None,
+                extra: serde_json::Map::new(),
             })
     }
 
@@ -446,6 +997,8 @@ mod proptests {
                 active_run_id: run_id,
                 validation_profiles: vec!["rust-cargo".to_string()],
                 requirements,
+                skip: Vec::new(),
+                extra: serde_json::Map::new(),
             })
     }
 
@@ -0,0 +1,136 @@
+// ABOUTME: Machine-readable progress events for 'ralph implement --progress-format json'
+// ABOUTME: One timestamped JSON object per line for wrappers, IDEs, and CI dashboards
+
+use crate::{RalphError, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::str::FromStr;
+
+/// How `ralph implement` reports progress on stdout
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProgressFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One [`ProgressRecord`] per line, with human-readable output moved to stderr
+    Json,
+}
+
+impl ProgressFormat {
+    /// Names accepted on the command line
+    pub const NAMES: [&'static str; 2] = ["text", "json"];
+}
+
+impl FromStr for ProgressFormat {
+    type Err = RalphError;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match name {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(RalphError::Config(format!(
+                "unknown progress format '{other}' (expected one of: {})",
+                Self::NAMES.join(", ")
+            ))),
+        }
+    }
+}
+
+/// A point in the implementation loop's lifecycle
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    /// The agent is about to be asked to work on a requirement (or chore)
+    IterationStarted {
+        iteration: u32,
+        requirement: String,
+        title: String,
+        full_tests: bool,
+    },
+    /// The implementer agent exited (or could not be run)
+    AgentFinished {
+        iteration: u32,
+        requirement: String,
+        success: bool,
+        duration_secs: f64,
+    },
+    /// One validation stage ran
+    ValidationStage {
+        iteration: u32,
+        requirement: String,
+        /// Validation profile, when the PRD runs more than one
+        #[serde(skip_serializing_if = "Option::is_none")]
+        profile: Option<String>,
+        stage: String,
+        passed: bool,
+        /// The stage failed but its profile allows it to
+        allowed_failure: bool,
+        /// Skipped because it already passed on the same tree state
+        cached: bool,
+    },
+    /// A requirement passed validation and was marked done
+    RequirementDone { iteration: u32, requirement: String },
+    /// The loop stopped: `complete`, `incomplete` (work left), or `blocked`
+    RunFinished {
+        slug: String,
+        done: usize,
+        total: usize,
+        outcome: String,
+    },
+}
+
+/// A [`ProgressEvent`] with the time it happened, as written to the stream
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProgressRecord {
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: ProgressEvent,
+}
+
+impl ProgressRecord {
+    /// `event`, happening now
+    #[must_use]
+    pub fn now(event: ProgressEvent) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            event,
+        }
+    }
+
+    /// The record as a single line of JSON
+    #[must_use]
+    pub fn to_json_line(&self) -> String {
+        serde_json::to_string(self).expect("progress records always serialize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_progress_json_line() {
+        let record = ProgressRecord {
+            timestamp: Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap(),
+            event: ProgressEvent::ValidationStage {
+                iteration: 3,
+                requirement: "REQ-01".to_string(),
+                profile: None,
+                stage: "lint".to_string(),
+                passed: false,
+                allowed_failure: false,
+                cached: false,
+            },
+        };
+        assert_eq!(
+            record.to_json_line(),
+            r#"{"timestamp":"2025-01-02T03:04:05Z","event":"validation_stage","iteration":3,"requirement":"REQ-01","stage":"lint","passed":false,"allowed_failure":false,"cached":false}"#
+        );
+        assert_eq!(
+            "json".parse::<ProgressFormat>().unwrap(),
+            ProgressFormat::Json
+        );
+        assert!("yaml".parse::<ProgressFormat>().is_err());
+    }
+}
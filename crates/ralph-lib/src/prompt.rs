@@ -0,0 +1,271 @@
+// ABOUTME: Agent prompt templates, read from the repository's prompts directory or built in
+// ABOUTME: Renders a Handlebars-style subset: {{name}} variables, {{#if name}} blocks, and {{! comments}}
+
+use crate::{RalphError, Result};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Values substituted into a template, by variable name
+pub type PromptVars = BTreeMap<&'static str, String>;
+
+/// Built-in prompt for an implementation iteration
+const DEFAULT_IMPLEMENT_TEMPLATE: &str =
+    include_str!("../../../templates/.ralph/prompts/implement.md");
+
+/// Built-in prompt for a chore
+const DEFAULT_CHORE_TEMPLATE: &str = include_str!("../../../templates/.ralph/prompts/chore.md");
+
+/// The prompts Ralph sends to the implementer agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptKind {
+    /// One iteration on a requirement
+    Implement,
+    /// A housekeeping chore (`ralph implement --chore`)
+    Chore,
+}
+
+impl PromptKind {
+    /// Template file name inside the prompts directory
+    #[must_use]
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Implement => "implement.md",
+            Self::Chore => "chore.md",
+        }
+    }
+
+    /// Variables a template of this kind may use
+    #[must_use]
+    pub fn variables(self) -> &'static [&'static str] {
+        match self {
+            Self::Implement => &[
+                "slug",
+                "feature_title",
+                "req_id",
+                "req_title",
+                "iteration",
+                "acceptance_criteria",
+                "validation_stages",
+                "full_tests",
+                "prompt_hints",
+                "operator_hints",
+                "scratchpad",
+                "repo_map",
+                "team_notes",
+                "previous_noop",
+                "validation_failure",
+            ],
+            Self::Chore => &[
+                "slug",
+                "feature_title",
+                "iteration",
+                "description",
+                "scratchpad",
+            ],
+        }
+    }
+
+    fn default_source(self) -> &'static str {
+        match self {
+            Self::Implement => DEFAULT_IMPLEMENT_TEMPLATE,
+            Self::Chore => DEFAULT_CHORE_TEMPLATE,
+        }
+    }
+}
+
+/// A parsed prompt template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptTemplate {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Text(String),
+    Var(String),
+    If(String, Vec<Node>),
+}
+
+impl PromptTemplate {
+    /// The repository's template for `kind` from `dir`, or the built-in one if it has none
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template file cannot be read, is malformed, or uses a
+    /// variable `kind` doesn't provide.
+    pub fn load(dir: impl AsRef<Path>, kind: PromptKind) -> Result<Self> {
+        let path = dir.as_ref().join(kind.file_name());
+        if !path.exists() {
+            return Self::parse(kind.default_source(), kind);
+        }
+        Self::parse(&std::fs::read_to_string(&path)?, kind).map_err(|e| match e {
+            RalphError::Config(message) => {
+                RalphError::Config(format!("{}: {message}", path.display()))
+            }
+            other => other,
+        })
+    }
+
+    /// Parse a template of `kind`
+    ///
+    /// Block tags and comments on a line of their own take the line with them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a tag is unclosed or unbalanced, or names a variable `kind`
+    /// doesn't provide.
+    pub fn parse(source: &str, kind: PromptKind) -> Result<Self> {
+        let invalid = |message: String| RalphError::Config(format!("prompt template: {message}"));
+        let known = |name: &str| {
+            if kind.variables().contains(&name) {
+                Ok(name.to_string())
+            } else {
+                Err(invalid(format!(
+                    "unknown variable '{name}' (expected one of: {})",
+                    kind.variables().join(", ")
+                )))
+            }
+        };
+
+        let mut open: Vec<(String, Vec<Node>)> = Vec::new();
+        let mut nodes = Vec::new();
+        let mut rest = source;
+        let mut at_line_start = true;
+        while let Some(start) = rest.find("{{") {
+            let mut text = &rest[..start];
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or_else(|| invalid("unclosed '{{'".to_string()))?;
+            let tag = after[..end].trim();
+            rest = &after[end + 2..];
+
+            // A block tag or comment alone on its line doesn't leave a blank line behind
+            let line_start = text.rfind('\n').map_or(0, |i| i + 1);
+            let standalone = tag.starts_with(['#', '/', '!'])
+                && (at_line_start || line_start > 0)
+                && text[line_start..].trim().is_empty()
+                && (rest.is_empty() || rest.starts_with('\n') || rest.starts_with("\r\n"));
+            if standalone {
+                text = &text[..line_start];
+                rest = rest
+                    .strip_prefix("\r\n")
+                    .or_else(|| rest.strip_prefix('\n'))
+                    .unwrap_or(rest);
+            }
+            at_line_start = standalone;
+            if !text.is_empty() {
+                nodes.push(Node::Text(text.to_string()));
+            }
+
+            if tag.starts_with('!') {
+                continue;
+            } else if let Some(name) = tag.strip_prefix("#if ") {
+                open.push((known(name.trim())?, std::mem::take(&mut nodes)));
+            } else if tag == "/if" {
+                let (name, outer) = open
+                    .pop()
+                    .ok_or_else(|| invalid("'{{/if}}' without '{{#if}}'".to_string()))?;
+                let body = std::mem::replace(&mut nodes, outer);
+                nodes.push(Node::If(name, body));
+            } else {
+                nodes.push(Node::Var(known(tag)?));
+            }
+        }
+        if let Some((name, _)) = open.last() {
+            return Err(invalid(format!("'{{{{#if {name}}}}}' is never closed")));
+        }
+        if !rest.is_empty() {
+            nodes.push(Node::Text(rest.to_string()));
+        }
+        Ok(Self { nodes })
+    }
+
+    /// Render with `vars`, trimming trailing whitespace; missing variables render empty
+    #[must_use]
+    pub fn render(&self, vars: &PromptVars) -> String {
+        let mut out = String::new();
+        render_nodes(&self.nodes, vars, &mut out);
+        out.truncate(out.trim_end().len());
+        out
+    }
+}
+
+fn render_nodes(nodes: &[Node], vars: &PromptVars, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var(name) => out.push_str(vars.get(name.as_str()).map_or("", String::as_str)),
+            Node::If(name, body) => {
+                if vars
+                    .get(name.as_str())
+                    .is_some_and(|value| !value.is_empty())
+                {
+                    render_nodes(body, vars, out);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_render_template() {
+        let template = PromptTemplate::parse(
+            "{{! header }}\nChore {{ description }} for {{slug}}.\n{{#if scratchpad}}\n\nNotes: {{scratchpad}}\n{{/if}}\n",
+            PromptKind::Chore,
+        )
+        .unwrap();
+        let mut vars = PromptVars::new();
+        vars.insert("description", "bump deps".to_string());
+        vars.insert("slug", "auth".to_string());
+        assert_eq!(template.render(&vars), "Chore bump deps for auth.");
+
+        vars.insert("scratchpad", "notes.md".to_string());
+        assert_eq!(
+            template.render(&vars),
+            "Chore bump deps for auth.\n\nNotes: notes.md"
+        );
+    }
+
+    #[test]
+    fn test_template_errors() {
+        for bad in [
+            "{{req_id}}",
+            "{{slug",
+            "{{#if slug}}never closed",
+            "{{/if}}",
+        ] {
+            assert!(
+                PromptTemplate::parse(bad, PromptKind::Chore).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_prefers_repository_template() {
+        let dir = tempdir().unwrap();
+        let mut vars = PromptVars::new();
+        vars.insert("req_id", "REQ-01".to_string());
+        vars.insert("validation_stages", "fmt -> lint".to_string());
+        let builtin = PromptTemplate::load(dir.path(), PromptKind::Implement)
+            .unwrap()
+            .render(&vars);
+        assert!(builtin.starts_with("Implement requirement REQ-01"));
+        assert!(builtin.contains("\n\nValidation: fmt -> lint\n\n"));
+        assert!(!builtin.contains("PREVIOUS ITERATION"));
+
+        std::fs::write(dir.path().join("implement.md"), "Do {{req_id}}.\n").unwrap();
+        let custom = PromptTemplate::load(dir.path(), PromptKind::Implement).unwrap();
+        assert_eq!(custom.render(&vars), "Do REQ-01.");
+
+        std::fs::write(dir.path().join("chore.md"), "{{req_id}}").unwrap();
+        let err = PromptTemplate::load(dir.path(), PromptKind::Chore).unwrap_err();
+        assert!(err.to_string().contains("unknown variable 'req_id'"));
+    }
+}
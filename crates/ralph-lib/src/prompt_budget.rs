@@ -0,0 +1,272 @@
+// ABOUTME: Token-aware prompt assembly: counts tokens with a BPE tokenizer and fits sections into a budget
+// ABOUTME: Fixed sections stay whole; flexible ones (failure output, repo map, notes) share what's left
+
+use crate::prompt::{PromptTemplate, PromptVars};
+use std::sync::OnceLock;
+use tiktoken_rs::CoreBPE;
+
+/// Characters per token assumed when converting character limits to token limits
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// How a flexible section is shortened when it doesn't fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trim {
+    /// Keep the first and last lines (where errors and summaries are), dropping the middle
+    HeadAndTail,
+    /// Keep the first lines (for ranked lists)
+    FirstLines,
+    /// Keep the last lines (for lists oldest first)
+    LastLines,
+}
+
+/// A prompt variable that may be trimmed to fit the budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Flexible {
+    /// Template variable holding the section
+    pub name: &'static str,
+    /// Share of the remaining budget relative to the other flexible sections
+    pub weight: usize,
+    /// How the section is shortened
+    pub trim: Trim,
+    /// Ceiling on the section regardless of the budget
+    pub max_tokens: Option<usize>,
+}
+
+/// Token count of `text`
+///
+/// Counted with the `cl100k_base` BPE encoding. The implementer models' own tokenizers
+/// aren't public, but they split text close enough to it for budgeting prompts. If the
+/// encoding can't be loaded, falls back to [`approximate_tokens`].
+#[must_use]
+pub fn estimate_tokens(text: &str) -> usize {
+    static ENCODING: OnceLock<Option<CoreBPE>> = OnceLock::new();
+    match ENCODING.get_or_init(|| tiktoken_rs::cl100k_base().ok()) {
+        Some(bpe) => bpe.encode_ordinary(text).len(),
+        None => approximate_tokens(text),
+    }
+}
+
+/// Approximate token count of `text`, without a tokenizer
+///
+/// Runs of letters and digits count as a token per [`CHARS_PER_TOKEN`] characters and every
+/// other non-space character as a token of its own.
+#[must_use]
+pub fn approximate_tokens(text: &str) -> usize {
+    let mut tokens = 0;
+    let mut run: usize = 0;
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            run += 1;
+            continue;
+        }
+        tokens += run.div_ceil(CHARS_PER_TOKEN);
+        run = 0;
+        if !c.is_whitespace() {
+            tokens += 1;
+        }
+    }
+    tokens + run.div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Render `template` with the `flexible` sections of `vars` trimmed to fit about `budget` tokens
+///
+/// The template text and the other variables are always kept. What they leave of the budget
+/// is split across the flexible sections by weight, and sections that need less than their
+/// share pass the rest on to the others.
+#[must_use]
+pub fn fit(
+    template: &PromptTemplate,
+    mut vars: PromptVars,
+    budget: usize,
+    flexible: &[Flexible],
+) -> String {
+    // A blank stand-in keeps each section's surrounding text in the fixed cost
+    let mut fixed_vars = vars.clone();
+    for section in flexible {
+        if let Some(text) = fixed_vars.get_mut(section.name) {
+            if !text.is_empty() {
+                *text = " ".to_string();
+            }
+        }
+    }
+    let mut available = budget.saturating_sub(estimate_tokens(&template.render(&fixed_vars)));
+
+    // Each section wants its whole text, up to its own ceiling
+    let mut pending: Vec<(Flexible, usize)> = flexible
+        .iter()
+        .filter_map(|section| {
+            let tokens = estimate_tokens(vars.get(section.name)?);
+            let wanted = section.max_tokens.map_or(tokens, |max| tokens.min(max));
+            (tokens > 0).then_some((*section, wanted))
+        })
+        .collect();
+
+    // Sections wanting less than their share get what they want; the rest split what's left
+    loop {
+        let weights: usize = pending
+            .iter()
+            .map(|(section, _)| section.weight.max(1))
+            .sum();
+        let share = |section: &Flexible| available * section.weight.max(1) / weights.max(1);
+        let Some(index) = pending
+            .iter()
+            .position(|(section, wanted)| *wanted <= share(section))
+        else {
+            for (section, _) in &pending {
+                let allowance = share(section);
+                trim_var(&mut vars, section, allowance);
+            }
+            break;
+        };
+        let (section, wanted) = pending.remove(index);
+        trim_var(&mut vars, &section, wanted);
+        available -= wanted;
+    }
+
+    template.render(&vars)
+}
+
+fn trim_var(vars: &mut PromptVars, section: &Flexible, max_tokens: usize) {
+    if let Some(text) = vars.get_mut(section.name) {
+        *text = trim(text, max_tokens, section.trim);
+    }
+}
+
+/// Shorten `text` by whole lines to about `max_tokens`, noting how many lines were left out
+#[must_use]
+pub fn trim(text: &str, max_tokens: usize, how: Trim) -> String {
+    if estimate_tokens(text) <= max_tokens {
+        return text.to_string();
+    }
+    let lines: Vec<&str> = text.lines().collect();
+    let costs: Vec<usize> = lines.iter().map(|line| estimate_tokens(line) + 1).collect();
+    // Room for the "... (N lines omitted) ..." marker
+    let mut left = max_tokens.saturating_sub(16);
+    let mut take = |cost: usize| {
+        let fits = cost <= left;
+        if fits {
+            left -= cost;
+        }
+        fits
+    };
+
+    let (head, tail) = match how {
+        Trim::FirstLines => (costs.iter().take_while(|&&cost| take(cost)).count(), 0),
+        Trim::LastLines => (
+            0,
+            costs.iter().rev().take_while(|&&cost| take(cost)).count(),
+        ),
+        Trim::HeadAndTail => {
+            // Alternate so both the first error and the final summary survive
+            let (mut head, mut tail) = (0, 0);
+            let mut from_head = true;
+            while head + tail < lines.len() {
+                let index = if from_head {
+                    head
+                } else {
+                    lines.len() - 1 - tail
+                };
+                if !take(costs[index]) {
+                    break;
+                }
+                if from_head {
+                    head += 1;
+                } else {
+                    tail += 1;
+                }
+                from_head = !from_head;
+            }
+            (head, tail)
+        }
+    };
+
+    let omitted = lines.len() - head - tail;
+    let mut kept: Vec<String> = lines[..head].iter().map(|line| line.to_string()).collect();
+    kept.push(format!("... ({omitted} lines omitted) ..."));
+    kept.extend(
+        lines[lines.len() - tail..]
+            .iter()
+            .map(|line| line.to_string()),
+    );
+    kept.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prompt::PromptKind;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("fix the parser"), 3);
+        assert!(estimate_tokens("error[E0308]: mismatched types") > 3);
+    }
+
+    #[test]
+    fn test_approximate_tokens() {
+        assert_eq!(approximate_tokens(""), 0);
+        assert_eq!(approximate_tokens("fix the parser"), 4);
+        assert_eq!(approximate_tokens("error[E0308]: mismatched"), 10);
+    }
+
+    #[test]
+    fn test_trim() {
+        let text = (1..=20)
+            .map(|n| format!("line {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(trim(&text, 1_000, Trim::HeadAndTail), text);
+
+        // Line costs depend on the tokenizer, so check the shape rather than exact counts
+        let kept = trim(&text, 30, Trim::HeadAndTail);
+        assert!(kept.starts_with("line 1\n"));
+        assert!(kept.contains("lines omitted"));
+        assert!(kept.ends_with("line 20"));
+        assert!(estimate_tokens(&kept) <= 30);
+        let first = trim(&text, 30, Trim::FirstLines);
+        assert!(first.starts_with("line 1\n") && first.ends_with("lines omitted) ..."));
+        let last = trim(&text, 30, Trim::LastLines);
+        assert!(last.starts_with("... (") && last.ends_with("line 20"));
+    }
+
+    #[test]
+    fn test_fit_shares_budget() {
+        let template = PromptTemplate::parse(
+            "Fix {{req_id}}.\n{{#if repo_map}}\n{{repo_map}}\n{{/if}}\n{{#if validation_failure}}\n{{validation_failure}}\n{{/if}}",
+            PromptKind::Implement,
+        )
+        .unwrap();
+        let failure = (1..=200)
+            .map(|n| format!("error {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut vars = PromptVars::new();
+        vars.insert("req_id", "REQ-01".to_string());
+        vars.insert("repo_map", "- src/lib.rs: parse".to_string());
+        vars.insert("validation_failure", failure);
+        let sections = [
+            Flexible {
+                name: "validation_failure",
+                weight: 3,
+                trim: Trim::HeadAndTail,
+                max_tokens: None,
+            },
+            Flexible {
+                name: "repo_map",
+                weight: 1,
+                trim: Trim::FirstLines,
+                max_tokens: None,
+            },
+        ];
+
+        let prompt = fit(&template, vars.clone(), 200, &sections);
+        assert!(estimate_tokens(&prompt) <= 200);
+        assert!(prompt.starts_with("Fix REQ-01.\n- src/lib.rs: parse\nerror 1\n"));
+        assert!(prompt.contains("lines omitted"));
+        assert!(prompt.ends_with("error 200"));
+
+        let roomy = fit(&template, vars, 100_000, &sections);
+        assert!(!roomy.contains("lines omitted"));
+    }
+}
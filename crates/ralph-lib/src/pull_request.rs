@@ -0,0 +1,165 @@
+// ABOUTME: Draft pull request text for a finished implementation run
+// ABOUTME: Builds the title and markdown body from the PRD's requirements and the run's ledger events
+
+use crate::{EventPayload, EventStatus, Ledger, Prd, RequirementStatus};
+use chrono::{DateTime, Utc};
+use std::fmt::Write;
+
+/// Pull request title for the feature
+#[must_use]
+pub fn render_pr_title(prd: &Prd) -> String {
+    format!("[ralph] {}", prd.title)
+}
+
+/// Render the markdown body of the run's pull request
+///
+/// Only ledger events recorded at or after `started_at` count toward the run's statistics.
+#[must_use]
+pub fn render_pr_body(prd: &Prd, ledger: &Ledger, started_at: DateTime<Utc>) -> String {
+    let events: Vec<_> = ledger
+        .events()
+        .iter()
+        .filter(|e| e.timestamp >= started_at)
+        .collect();
+    let done = prd
+        .requirements
+        .iter()
+        .filter(|r| r.status == RequirementStatus::Done)
+        .count();
+
+    let mut body = format!(
+        "Implements **{}** (`{}`), run `{}`.\n\n",
+        prd.title, prd.slug, prd.active_run_id
+    );
+    let stop_reason = events.iter().rev().find_map(|e| match &e.payload {
+        Some(EventPayload::RunAborted { reason }) => Some(reason.as_str()),
+        _ => None,
+    });
+    match stop_reason {
+        Some(reason) => {
+            let _ = writeln!(
+                body,
+                "⚠️ The loop stopped early ({reason}): {done}/{} requirements done.\n",
+                prd.requirements.len()
+            );
+        }
+        None => {
+            let _ = writeln!(
+                body,
+                "{done}/{} requirements done.\n",
+                prd.requirements.len()
+            );
+        }
+    }
+
+    body.push_str("## Requirements\n\n| Requirement | Status | Iterations |\n|---|---|---|\n");
+    for req in &prd.requirements {
+        let iterations = events
+            .iter()
+            .filter(|e| e.requirement == req.id && e.status == EventStatus::Started)
+            .count();
+        let status = match req.status {
+            RequirementStatus::Done => "✅ done",
+            _ if prd.is_skipped(&req.id) => "⏭️ skipped",
+            RequirementStatus::InProgress => "🔄 in progress",
+            RequirementStatus::Todo => "⬜ todo",
+            RequirementStatus::Blocked => "🚫 blocked",
+        };
+        let _ = writeln!(
+            body,
+            "| {} {} | {status} | {iterations} |",
+            req.id,
+            req.title.replace('|', "\\|"),
+        );
+    }
+
+    let iterations = events
+        .iter()
+        .filter(|e| e.status == EventStatus::Started)
+        .count();
+    let failed = events
+        .iter()
+        .filter(|e| e.status == EventStatus::Failed)
+        .count();
+    let usage = ledger.usage_since(started_at);
+    body.push_str("\n## Run\n\n");
+    let _ = writeln!(body, "- Iterations: {iterations} ({failed} failed)");
+    let _ = writeln!(
+        body,
+        "- Tokens: {} input, {} output (~${:.2})",
+        usage.prompt_tokens, usage.completion_tokens, usage.estimated_cost
+    );
+    if let Some(first) = events.first() {
+        let _ = writeln!(
+            body,
+            "- Started: {}",
+            first.timestamp.format("%Y-%m-%d %H:%M UTC")
+        );
+    }
+
+    let _ = writeln!(
+        body,
+        "\n_Opened as a draft by `ralph implement`; see `ralph status {}` for the full ledger._",
+        prd.slug
+    );
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::RUN_REQUIREMENT;
+    use crate::{LedgerEvent, TokenUsage};
+
+    #[test]
+    fn test_render_pr_body() {
+        let prd = Prd::from_json(
+            r#"{"schemaVersion":"1.0","slug":"auth","title":"Login","activeRunId":"auth-2","validationProfiles":[],"requirements":[
+                {"id":"REQ-01","title":"Form","status":"done","acceptanceCriteria":[]},
+                {"id":"REQ-02","title":"Session | cookie","status":"in_progress","acceptanceCriteria":[]}
+            ]}"#,
+        )
+        .unwrap();
+        let started_at = Utc::now();
+        let mut ledger = Ledger::new();
+        let mut earlier = LedgerEvent::new(1, "REQ-01", EventStatus::Started);
+        earlier.timestamp = started_at - chrono::Duration::days(1);
+        ledger.append(earlier).unwrap();
+        ledger
+            .append(LedgerEvent::new(2, "REQ-01", EventStatus::Started))
+            .unwrap();
+        ledger
+            .append(
+                LedgerEvent::new(2, "REQ-01", EventStatus::Done).with_usage(&TokenUsage {
+                    model: Some("claude-haiku-4.5".to_string()),
+                    prompt_tokens: 1_000_000,
+                    completion_tokens: 0,
+                }),
+            )
+            .unwrap();
+        ledger
+            .append(LedgerEvent::new(3, "REQ-02", EventStatus::Started))
+            .unwrap();
+        ledger
+            .append(LedgerEvent::new(3, "REQ-02", EventStatus::Failed))
+            .unwrap();
+        ledger
+            .append(
+                LedgerEvent::new(3, RUN_REQUIREMENT, EventStatus::InProgress).with_payload(
+                    EventPayload::RunAborted {
+                        reason: "max iterations (2) reached".to_string(),
+                    },
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(render_pr_title(&prd), "[ralph] Login");
+        let body = render_pr_body(&prd, &ledger, started_at);
+        assert!(body.starts_with("Implements **Login** (`auth`), run `auth-2`."));
+        assert!(body.contains("stopped early (max iterations (2) reached): 1/2 requirements done"));
+        assert!(body.contains("| REQ-01 Form | ✅ done | 1 |"));
+        assert!(body.contains("| REQ-02 Session \\| cookie | 🔄 in progress | 1 |"));
+        assert!(body.contains("- Iterations: 2 (1 failed)"));
+        assert!(body.contains("1000000 input, 0 output (~$1.00)"));
+    }
+}
@@ -0,0 +1,283 @@
+// ABOUTME: Lightweight repository map for implementer prompts: likely-relevant files and their symbols
+// ABOUTME: Ranks files by requirement keywords, declared paths, and files the ledger shows recent work on
+
+use crate::prd::paths_overlap;
+use crate::{Ledger, Requirement};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Most files a repo map lists
+pub const REPO_MAP_FILES: usize = 15;
+
+/// Most symbols listed per file
+const SYMBOLS_PER_FILE: usize = 12;
+
+/// Files larger than this are listed by path only
+const MAX_SCANNED_BYTES: u64 = 256 * 1024;
+
+/// Ledger events per requirement searched for file mentions
+const RECENT_EVENTS: usize = 10;
+
+/// Extensions of files whose top-level symbols are extracted
+const SOURCE_EXTENSIONS: [&str; 17] = [
+    "rs", "py", "go", "ts", "tsx", "js", "jsx", "java", "kt", "rb", "c", "h", "cc", "cpp", "hpp",
+    "cs", "swift",
+];
+
+/// Keywords that introduce a named top-level item in the supported languages
+const ITEM_KEYWORDS: [&str; 12] = [
+    "fn",
+    "struct",
+    "enum",
+    "trait",
+    "type",
+    "mod",
+    "def",
+    "class",
+    "function",
+    "interface",
+    "func",
+    "const",
+];
+
+/// Modifiers skipped before an item keyword
+const ITEM_MODIFIERS: [&str; 10] = [
+    "pub",
+    "pub(crate)",
+    "pub(super)",
+    "export",
+    "default",
+    "async",
+    "unsafe",
+    "public",
+    "static",
+    "abstract",
+];
+
+/// Words too common in requirements to say anything about which files matter
+const STOPWORDS: [&str; 23] = [
+    "given", "when", "then", "with", "that", "this", "should", "from", "into", "have", "must",
+    "each", "only", "able", "will", "they", "their", "there", "which", "user", "users", "also",
+    "after",
+];
+
+/// What a repo map is built for
+#[derive(Debug, Clone, Default)]
+pub struct RepoMapQuery {
+    /// Lowercase words from the requirement, matched against paths and symbols
+    pub keywords: Vec<String>,
+    /// Paths or globs the requirement declares it touches
+    pub declared_paths: Vec<String>,
+    /// Files recent iterations changed or failed on, most recent first
+    pub recent: Vec<String>,
+}
+
+impl RepoMapQuery {
+    /// Query for `req`, with recent files from its ledger history and `changed` (files its
+    /// recent iterations changed, most recent first)
+    ///
+    /// Only paths in `files` count as mentions in the ledger.
+    #[must_use]
+    pub fn for_requirement(
+        req: &Requirement,
+        ledger: &Ledger,
+        files: &[String],
+        changed: &[String],
+    ) -> Self {
+        let mut recent: Vec<String> = changed.to_vec();
+        let events = ledger.events_for_requirement(&req.id);
+        for event in events.iter().rev().take(RECENT_EVENTS) {
+            for text in [&event.validation_output, &event.message]
+                .into_iter()
+                .flatten()
+            {
+                recent.extend(
+                    files
+                        .iter()
+                        .filter(|file| text.contains(file.as_str()))
+                        .cloned(),
+                );
+            }
+        }
+        let mut seen = BTreeSet::new();
+        recent.retain(|file| seen.insert(file.clone()));
+
+        Self {
+            keywords: keywords(req),
+            declared_paths: req.paths.clone(),
+            recent,
+        }
+    }
+}
+
+/// Distinct lowercase words of four or more letters from the requirement's title and criteria
+#[must_use]
+pub fn keywords(req: &Requirement) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    std::iter::once(&req.title)
+        .chain(&req.acceptance_criteria)
+        .flat_map(|text| text.split(|c: char| !c.is_alphanumeric() && c != '_'))
+        .map(str::to_lowercase)
+        .filter(|word| word.len() >= 4 && !STOPWORDS.contains(&word.as_str()))
+        .filter(|word| seen.insert(word.clone()))
+        .collect()
+}
+
+/// Render up to [`REPO_MAP_FILES`] of `files` (paths relative to `root`) most relevant to
+/// `query`, one `- path: symbols` line each, or an empty string if none match
+#[must_use]
+pub fn build(root: &Path, files: &[String], query: &RepoMapQuery) -> String {
+    let mut scored: Vec<(u32, &String, Vec<String>)> = files
+        .iter()
+        .filter_map(|file| {
+            let symbols = symbols(&root.join(file));
+            let score = score(file, &symbols, query);
+            (score > 0).then_some((score, file, symbols))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+
+    scored
+        .into_iter()
+        .take(REPO_MAP_FILES)
+        .map(|(_, file, symbols)| {
+            if symbols.is_empty() {
+                format!("- {file}")
+            } else {
+                let shown = &symbols[..symbols.len().min(SYMBOLS_PER_FILE)];
+                let more = symbols.len() - shown.len();
+                let suffix = if more > 0 {
+                    format!(" (+{more} more)")
+                } else {
+                    String::new()
+                };
+                format!("- {file}: {}{suffix}", shown.join(", "))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Relevance of `file` to `query`; 0 leaves it out of the map
+fn score(file: &str, symbols: &[String], query: &RepoMapQuery) -> u32 {
+    let path = file.to_lowercase();
+    let mut score = 0;
+    if let Some(rank) = query.recent.iter().position(|recent| recent == file) {
+        score += 8u32.saturating_sub(rank as u32).max(4);
+    }
+    if paths_overlap(&query.declared_paths, &[file.to_string()]) {
+        score += 5;
+    }
+    let symbols: Vec<String> = symbols.iter().map(|s| s.to_lowercase()).collect();
+    for keyword in &query.keywords {
+        if path.contains(keyword.as_str()) {
+            score += 2;
+        }
+        if symbols
+            .iter()
+            .any(|symbol| symbol.contains(keyword.as_str()))
+        {
+            score += 1;
+        }
+    }
+    score
+}
+
+/// Names of the top-level items defined in a source file, in file order
+fn symbols(path: &Path) -> Vec<String> {
+    let is_source = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext));
+    let small = std::fs::metadata(path).is_ok_and(|meta| meta.len() <= MAX_SCANNED_BYTES);
+    if !is_source || !small {
+        return Vec::new();
+    }
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    let mut seen = BTreeSet::new();
+    content
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .filter_map(item_name)
+        .filter(|name| seen.insert(name.clone()))
+        .collect()
+}
+
+/// Name of the item a line declares, e.g. `parse` for `pub fn parse(input: &str)`
+fn item_name(line: &str) -> Option<String> {
+    let mut tokens = line
+        .split_whitespace()
+        .skip_while(|token| ITEM_MODIFIERS.contains(token));
+    if !ITEM_KEYWORDS.contains(&tokens.next()?) {
+        return None;
+    }
+    let name: String = tokens
+        .next()?
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventStatus, LedgerEvent, Prd};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_build_repo_map() {
+        let dir = tempdir().unwrap();
+        let write = |path: &str, content: &str| {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(
+            "src/session.rs",
+            "pub struct Session;\n\nimpl Session {\n    fn inner() {}\n}\n\npub(crate) fn expire_sessions() {}\n",
+        );
+        write(
+            "src/login.py",
+            "class LoginForm:\n    def submit(self):\n        pass\n",
+        );
+        write("src/util.rs", "pub fn pad() {}\n");
+        write("README.md", "# Login\n");
+        let files: Vec<String> = ["README.md", "src/login.py", "src/session.rs", "src/util.rs"]
+            .map(String::from)
+            .to_vec();
+
+        let prd = Prd::from_json(
+            r#"{"schemaVersion":"1.0","slug":"s","title":"t","activeRunId":"r","validationProfiles":[],"requirements":[
+                {"id":"REQ-01","title":"Expire idle sessions","status":"todo","acceptanceCriteria":["Given a login, when idle, then the session expires"]}
+            ]}"#,
+        )
+        .unwrap();
+        let req = &prd.requirements[0];
+        assert_eq!(
+            keywords(req),
+            vec!["expire", "idle", "sessions", "login", "session", "expires"]
+        );
+
+        let mut ledger = Ledger::new();
+        ledger
+            .append(
+                LedgerEvent::new(1, "REQ-01", EventStatus::Failed)
+                    .with_validation(false)
+                    .with_validation_output("error: README.md:1 broken link"),
+            )
+            .unwrap();
+        let query = RepoMapQuery::for_requirement(req, &ledger, &files, &[]);
+        assert_eq!(query.recent, vec!["README.md"]);
+
+        let map = build(dir.path(), &files, &query);
+        assert_eq!(
+            map,
+            "- README.md\n- src/session.rs: Session, expire_sessions\n- src/login.py: LoginForm"
+        );
+        assert!(build(dir.path(), &files, &RepoMapQuery::default()).is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+// ABOUTME: Serializes validation results for CI systems
+// ABOUTME: Produces JUnit XML (one suite per profile) and SARIF 2.1.0 (results located by extracted diagnostics)
+
+use crate::diagnostics::Severity;
+use crate::ValidationResult;
+use serde_json::{json, Value};
+use std::fmt::Write;
+
+/// SARIF schema the report conforms to
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Render validation results as JUnit XML: a suite per profile, a test case per stage
+#[must_use]
+pub fn to_junit_xml(runs: &[(&str, Vec<ValidationResult>)]) -> String {
+    let count = |pred: fn(&ValidationResult) -> bool| {
+        runs.iter()
+            .flat_map(|(_, results)| results)
+            .filter(|r| pred(r))
+            .count()
+    };
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <testsuites name=\"ralph validation\" tests=\"{}\" failures=\"{}\">\n",
+        count(|_| true),
+        count(|r| !r.success)
+    );
+    for (profile, results) in runs {
+        let failures = results.iter().filter(|r| !r.success).count();
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\">",
+            escape(profile),
+            results.len()
+        );
+        for result in results {
+            let stage = result.stage.as_str();
+            let _ = write!(
+                xml,
+                "    <testcase name=\"{stage}\" classname=\"{}.{stage}\"",
+                escape(profile)
+            );
+            if result.success {
+                xml.push_str(" />\n");
+                continue;
+            }
+            let message = match result.exit_code {
+                Some(code) => format!("{stage} failed with exit code {code}"),
+                None => format!("{stage} failed"),
+            };
+            let _ = writeln!(
+                xml,
+                ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                escape(&message),
+                escape(&result.output)
+            );
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Render validation results as a SARIF 2.1.0 log
+///
+/// Rules are named `<profile>/<stage>`. A failed stage yields a located result per
+/// diagnostic extracted from its output, or one result carrying the whole output if
+/// the tool's output had none. Failures of `allowFailure` stages are at most warnings.
+#[must_use]
+pub fn to_sarif(runs: &[(&str, Vec<ValidationResult>)]) -> Value {
+    let mut rules = Vec::new();
+    let mut results = Vec::new();
+    for (profile, stage_results) in runs {
+        for result in stage_results {
+            let rule_id = format!("{profile}/{}", result.stage.as_str());
+            rules.push(json!({
+                "id": rule_id,
+                "shortDescription": {
+                    "text": format!("{} stage of validation profile '{profile}'", result.stage.as_str())
+                }
+            }));
+            let rule_index = rules.len() - 1;
+            if result.success {
+                continue;
+            }
+            let level = |severity: Severity| {
+                if result.allowed_failure {
+                    Severity::Warning.as_str()
+                } else {
+                    severity.as_str()
+                }
+            };
+            if result.diagnostics.is_empty() {
+                results.push(json!({
+                    "ruleId": rule_id,
+                    "ruleIndex": rule_index,
+                    "level": level(Severity::Error),
+                    "message": { "text": result.output }
+                }));
+            }
+            for diagnostic in &result.diagnostics {
+                let mut region = json!({});
+                if let Some(line) = diagnostic.line {
+                    region["startLine"] = json!(line);
+                }
+                if let Some(column) = diagnostic.column {
+                    region["startColumn"] = json!(column);
+                }
+                let message = match &diagnostic.code {
+                    Some(code) => format!("[{code}] {}", diagnostic.message),
+                    None => diagnostic.message.clone(),
+                };
+                results.push(json!({
+                    "ruleId": rule_id,
+                    "ruleIndex": rule_index,
+                    "level": level(diagnostic.severity),
+                    "message": { "text": message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": diagnostic.file },
+                            "region": region
+                        }
+                    }]
+                }));
+            }
+        }
+    }
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "ralph",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules
+                }
+            },
+            "results": results
+        }]
+    })
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ValidationStage;
+
+    fn result(stage: ValidationStage, success: bool, output: &str) -> ValidationResult {
+        ValidationResult {
+            stage,
+            success,
+            output: output.to_string(),
+            exit_code: Some(if success { 0 } else { 101 }),
+            full_output_path: None,
+            attempts: 1,
+            cached: false,
+            diagnostics: Vec::new(),
+            allowed_failure: false,
+        }
+    }
+
+    fn sample_runs() -> Vec<(&'static str, Vec<ValidationResult>)> {
+        vec![(
+            "rust-cargo",
+            vec![
+                result(ValidationStage::Fmt, true, ""),
+                result(ValidationStage::Lint, false, "error: unused <T>"),
+            ],
+        )]
+    }
+
+    #[test]
+    fn test_junit_xml() {
+        let xml = to_junit_xml(&sample_runs());
+        assert!(xml.contains("<testsuites name=\"ralph validation\" tests=\"2\" failures=\"1\">"));
+        assert!(xml.contains("<testcase name=\"fmt\" classname=\"rust-cargo.fmt\" />"));
+        assert!(xml.contains(
+            "<failure message=\"lint failed with exit code 101\">error: unused &lt;T&gt;</failure>"
+        ));
+    }
+
+    #[test]
+    fn test_sarif() {
+        let sarif = to_sarif(&sample_runs());
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        assert_eq!(run["results"][0]["ruleId"], "rust-cargo/lint");
+        assert_eq!(run["results"][0]["ruleIndex"], 1);
+        assert_eq!(run["results"][0]["message"]["text"], "error: unused <T>");
+    }
+
+    #[test]
+    fn test_sarif_locates_diagnostics() {
+        let mut runs = sample_runs();
+        runs[0].1[1].diagnostics = crate::diagnostics::parse(
+            "src/a.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.",
+        );
+        let sarif = to_sarif(&runs);
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(
+            result["message"]["text"],
+            "[TS2322] Type 'string' is not assignable to type 'number'."
+        );
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/a.ts");
+        assert_eq!(location["region"]["startLine"], 3);
+        assert_eq!(result["level"], "error");
+
+        runs[0].1[1].allowed_failure = true;
+        assert_eq!(to_sarif(&runs)["runs"][0]["results"][0]["level"], "warning");
+    }
+}
@@ -0,0 +1,90 @@
+// ABOUTME: Persistent per-feature scratchpad giving the stateless agent working memory
+// ABOUTME: Creates the file on demand and keeps it within a size cap
+
+use crate::Result;
+use std::path::{Path, PathBuf};
+
+/// Scratchpad file name inside a feature's task directory
+pub const SCRATCHPAD_FILE: &str = "scratchpad.md";
+
+/// Size above which the scratchpad is compacted before the next iteration
+pub const DEFAULT_MAX_BYTES: usize = 8 * 1024;
+
+const TEMPLATE: &str = "# Scratchpad\n\n\
+Working notes and TODOs carried between iterations. Keep entries short; \
+Ralph compacts this file when it grows too large.\n\n## Notes\n\n## TODO\n";
+
+/// Path of the scratchpad for a feature's task directory
+#[must_use]
+pub fn scratchpad_path(task_dir: impl AsRef<Path>) -> PathBuf {
+    task_dir.as_ref().join(SCRATCHPAD_FILE)
+}
+
+/// Create the scratchpad with a starter template if it doesn't exist
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn ensure(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, TEMPLATE)?;
+    }
+    Ok(())
+}
+
+/// Keep the most recent content that fits in `max_bytes`, starting at a line boundary
+///
+/// Used when an LLM summary of an oversized scratchpad isn't available.
+#[must_use]
+pub fn truncate_to_tail(content: &str, max_bytes: usize) -> String {
+    if content.len() <= max_bytes {
+        return content.to_string();
+    }
+    let mut start = content.len() - max_bytes;
+    while !content.is_char_boundary(start) {
+        start += 1;
+    }
+    let tail = &content[start..];
+    let tail = tail.find('\n').map_or(tail, |idx| &tail[idx + 1..]);
+    format!("# Scratchpad\n\n_(older notes trimmed)_\n\n{tail}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ensure_creates_once() {
+        let temp = TempDir::new().unwrap();
+        let path = scratchpad_path(temp.path().join("feature"));
+        ensure(&path).unwrap();
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("# Scratchpad"));
+
+        std::fs::write(&path, "my notes").unwrap();
+        ensure(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "my notes");
+    }
+
+    #[test]
+    fn test_truncate_to_tail() {
+        assert_eq!(truncate_to_tail("short", 100), "short");
+
+        let content = (1..=100)
+            .map(|i| format!("note {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let trimmed = truncate_to_tail(&content, 40);
+        assert!(trimmed.contains("older notes trimmed"));
+        assert!(trimmed.ends_with("note 100"));
+        assert!(!trimmed.contains("note 1\n"));
+        // Starts at a whole line
+        assert!(trimmed.lines().nth(4).unwrap().starts_with("note "));
+    }
+}
@@ -0,0 +1,231 @@
+// ABOUTME: Secret resolution for integrations (env vars, .env files, OS keychain)
+// ABOUTME: Secrets are masked in Debug/Display and intentionally not serializable
+
+use crate::{RalphError, Result};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Keychain service name used for Ralph secrets
+pub const KEYCHAIN_SERVICE: &str = "ralph";
+
+/// A resolved secret value
+///
+/// Does not implement `Serialize`, so it cannot end up in the ledger or config files.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    /// Wrap a raw secret value
+    #[must_use]
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// Access the raw value (only at the point of use)
+    #[must_use]
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Masked form safe for logs (keeps a short prefix only for long values)
+    #[must_use]
+    pub fn masked(&self) -> String {
+        if self.0.chars().count() >= 12 {
+            let prefix: String = self.0.chars().take(4).collect();
+            format!("{prefix}****")
+        } else {
+            "****".to_string()
+        }
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret({})", self.masked())
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.masked())
+    }
+}
+
+/// A place secrets can be looked up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretBackend {
+    /// Process environment variables
+    Env,
+    /// A dotenv-style `KEY=value` file
+    DotEnv(PathBuf),
+    /// OS keychain (macOS `security`, Linux `secret-tool`) under the given service
+    Keychain(String),
+}
+
+impl SecretBackend {
+    /// Look up a secret by name in this backend
+    #[must_use]
+    pub fn lookup(&self, name: &str) -> Option<Secret> {
+        match self {
+            Self::Env => std::env::var(name).ok().filter(|v| !v.is_empty()),
+            Self::DotEnv(path) => lookup_dotenv(path, name),
+            Self::Keychain(service) => lookup_keychain(service, name),
+        }
+        .map(Secret)
+    }
+}
+
+/// Resolves secrets from an ordered list of backends (first match wins)
+#[derive(Debug, Clone, Default)]
+pub struct SecretResolver {
+    backends: Vec<SecretBackend>,
+}
+
+impl SecretResolver {
+    /// Create a resolver with no backends
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Standard resolution order for a repository: env, `<root>/.env`, then the OS keychain
+    #[must_use]
+    pub fn for_repo(root: impl AsRef<Path>) -> Self {
+        Self::new()
+            .with_backend(SecretBackend::Env)
+            .with_backend(SecretBackend::DotEnv(root.as_ref().join(".env")))
+            .with_backend(SecretBackend::Keychain(KEYCHAIN_SERVICE.to_string()))
+    }
+
+    /// Append a backend to the resolution order
+    #[must_use]
+    pub fn with_backend(mut self, backend: SecretBackend) -> Self {
+        self.backends.push(backend);
+        self
+    }
+
+    /// Resolve a secret, returning `None` if no backend has it
+    #[must_use]
+    pub fn resolve(&self, name: &str) -> Option<Secret> {
+        self.backends.iter().find_map(|b| b.lookup(name))
+    }
+
+    /// Resolve a secret that must be present
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the secret if no backend has it.
+    pub fn require(&self, name: &str) -> Result<Secret> {
+        self.resolve(name).ok_or_else(|| {
+            RalphError::Secret(format!(
+                "{name} not found in environment, .env, or keychain"
+            ))
+        })
+    }
+}
+
+/// Parse a dotenv file for a single key
+fn lookup_dotenv(path: &Path, name: &str) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    content.lines().find_map(|line| {
+        let line = line.trim();
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        if line.starts_with('#') {
+            return None;
+        }
+        let (key, value) = line.split_once('=')?;
+        if key.trim() != name {
+            return None;
+        }
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            .unwrap_or(value);
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+/// Query the OS keychain via its command-line tool
+fn lookup_keychain(service: &str, name: &str) -> Option<String> {
+    let output = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["find-generic-password", "-s", service, "-a", name, "-w"])
+            .output()
+    } else if cfg!(unix) {
+        Command::new("secret-tool")
+            .args(["lookup", "service", service, "account", name])
+            .output()
+    } else {
+        return None;
+    };
+
+    let output = output.ok().filter(|o| o.status.success())?;
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_secret_is_masked() {
+        let secret = Secret::new("ghp_abcdefghijklmnop");
+        assert_eq!(secret.to_string(), "ghp_****");
+        assert_eq!(format!("{secret:?}"), "Secret(ghp_****)");
+        assert_eq!(Secret::new("short").masked(), "****");
+        assert_eq!(secret.expose(), "ghp_abcdefghijklmnop");
+    }
+
+    #[test]
+    fn test_dotenv_lookup() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(".env");
+        std::fs::write(
+            &path,
+            "# comment\nexport SLACK_TOKEN=\"xoxb-123\"\nJIRA_TOKEN='abc'\nEMPTY=\n",
+        )
+        .unwrap();
+
+        let backend = SecretBackend::DotEnv(path);
+        assert_eq!(backend.lookup("SLACK_TOKEN").unwrap().expose(), "xoxb-123");
+        assert_eq!(backend.lookup("JIRA_TOKEN").unwrap().expose(), "abc");
+        assert!(backend.lookup("EMPTY").is_none());
+        assert!(backend.lookup("MISSING").is_none());
+    }
+
+    #[test]
+    fn test_resolver_order() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".env"),
+            "RALPH_TEST_SECRET_ORDER=from-file\n",
+        )
+        .unwrap();
+        let resolver = SecretResolver::new()
+            .with_backend(SecretBackend::Env)
+            .with_backend(SecretBackend::DotEnv(dir.path().join(".env")));
+
+        assert_eq!(
+            resolver
+                .resolve("RALPH_TEST_SECRET_ORDER")
+                .unwrap()
+                .expose(),
+            "from-file"
+        );
+        std::env::set_var("RALPH_TEST_SECRET_ORDER", "from-env");
+        assert_eq!(
+            resolver
+                .resolve("RALPH_TEST_SECRET_ORDER")
+                .unwrap()
+                .expose(),
+            "from-env"
+        );
+        std::env::remove_var("RALPH_TEST_SECRET_ORDER");
+        assert!(resolver.require("RALPH_TEST_SECRET_MISSING").is_err());
+    }
+}
@@ -0,0 +1,191 @@
+// ABOUTME: Strategies for choosing the requirement the implement loop works on next
+// ABOUTME: Built-ins: fifo, priority, fewest-failures-first, and round-robin across requirements
+
+use crate::handoff::failed_attempts;
+use crate::{Ledger, Prd, RalphError, Requirement, RequirementStatus, Result};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Chooses the next requirement to implement
+pub trait SelectionStrategy {
+    /// The pending (todo or in-progress, not skipped) requirement to work on next, or `None`
+    /// when none remain
+    fn select<'a>(&self, prd: &'a Prd, ledger: &Ledger) -> Option<&'a Requirement>;
+}
+
+/// First pending requirement in PRD order
+pub struct Fifo;
+
+/// Pending requirement with the lowest `priority`; unprioritized ones go last, ties in PRD order
+pub struct Priority;
+
+/// Pending requirement with the fewest failed attempts, ties in PRD order
+pub struct FewestFailures;
+
+/// Pending requirement after the one the ledger last worked on, wrapping around
+pub struct RoundRobin;
+
+fn is_pending(prd: &Prd, req: &Requirement) -> bool {
+    matches!(
+        req.status,
+        RequirementStatus::Todo | RequirementStatus::InProgress
+    ) && !prd.is_skipped(&req.id)
+}
+
+fn pending(prd: &Prd) -> impl Iterator<Item = &Requirement> {
+    prd.requirements.iter().filter(|r| is_pending(prd, r))
+}
+
+impl SelectionStrategy for Fifo {
+    fn select<'a>(&self, prd: &'a Prd, _ledger: &Ledger) -> Option<&'a Requirement> {
+        pending(prd).next()
+    }
+}
+
+impl SelectionStrategy for Priority {
+    fn select<'a>(&self, prd: &'a Prd, _ledger: &Ledger) -> Option<&'a Requirement> {
+        pending(prd).min_by_key(|r| r.priority.unwrap_or(u32::MAX))
+    }
+}
+
+impl SelectionStrategy for FewestFailures {
+    fn select<'a>(&self, prd: &'a Prd, ledger: &Ledger) -> Option<&'a Requirement> {
+        pending(prd).min_by_key(|r| failed_attempts(ledger, &r.id))
+    }
+}
+
+impl SelectionStrategy for RoundRobin {
+    fn select<'a>(&self, prd: &'a Prd, ledger: &Ledger) -> Option<&'a Requirement> {
+        let last = ledger.events().iter().rev().find_map(|event| {
+            prd.requirements
+                .iter()
+                .position(|r| r.id == event.requirement)
+        });
+        let start = last.map_or(0, |index| index + 1);
+        let count = prd.requirements.len();
+        (0..count)
+            .map(|offset| &prd.requirements[(start + offset) % count])
+            .find(|r| is_pending(prd, r))
+    }
+}
+
+/// Built-in strategy chosen by `[implement] selection`, `RALPH_SELECTION`, or `--selection`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Selection {
+    /// [`Fifo`]
+    #[default]
+    Fifo,
+    /// [`Priority`]
+    Priority,
+    /// [`FewestFailures`]
+    FewestFailures,
+    /// [`RoundRobin`]
+    RoundRobin,
+}
+
+impl Selection {
+    /// Names accepted in config, the environment, and on the command line
+    pub const NAMES: [&'static str; 4] = ["fifo", "priority", "fewest-failures", "round-robin"];
+
+    /// The strategy this selects
+    #[must_use]
+    pub fn strategy(self) -> &'static dyn SelectionStrategy {
+        match self {
+            Self::Fifo => &Fifo,
+            Self::Priority => &Priority,
+            Self::FewestFailures => &FewestFailures,
+            Self::RoundRobin => &RoundRobin,
+        }
+    }
+}
+
+impl FromStr for Selection {
+    type Err = RalphError;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match name {
+            "fifo" => Ok(Self::Fifo),
+            "priority" => Ok(Self::Priority),
+            "fewest-failures" => Ok(Self::FewestFailures),
+            "round-robin" => Ok(Self::RoundRobin),
+            other => Err(RalphError::Config(format!(
+                "unknown selection strategy '{other}' (expected one of: {})",
+                Self::NAMES.join(", ")
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventStatus, LedgerEvent};
+
+    fn prd() -> Prd {
+        Prd::from_json(
+            r#"{"schemaVersion":"1.0","slug":"s","title":"t","activeRunId":"r","validationProfiles":[],
+                "requirements":[
+                    {"id":"REQ-01","title":"a","status":"done","acceptanceCriteria":[]},
+                    {"id":"REQ-02","title":"b","status":"in_progress","acceptanceCriteria":[]},
+                    {"id":"REQ-03","title":"c","status":"todo","acceptanceCriteria":[],"priority":2},
+                    {"id":"REQ-04","title":"d","status":"todo","acceptanceCriteria":[],"priority":1}
+                ]}"#,
+        )
+        .unwrap()
+    }
+
+    fn selected(selection: Selection, prd: &Prd, ledger: &Ledger) -> String {
+        selection.strategy().select(prd, ledger).unwrap().id.clone()
+    }
+
+    #[test]
+    fn test_strategies() {
+        let prd = prd();
+        let mut ledger = Ledger::new();
+        assert_eq!(selected(Selection::Fifo, &prd, &ledger), "REQ-02");
+        assert_eq!(selected(Selection::Priority, &prd, &ledger), "REQ-04");
+
+        ledger
+            .append(LedgerEvent::new(1, "REQ-02", EventStatus::Failed))
+            .unwrap();
+        assert_eq!(selected(Selection::Fifo, &prd, &ledger), "REQ-02");
+        assert_eq!(selected(Selection::FewestFailures, &prd, &ledger), "REQ-03");
+        assert_eq!(selected(Selection::RoundRobin, &prd, &ledger), "REQ-03");
+
+        ledger
+            .append(LedgerEvent::new(2, "REQ-04", EventStatus::Failed))
+            .unwrap();
+        assert_eq!(selected(Selection::RoundRobin, &prd, &ledger), "REQ-02");
+    }
+
+    #[test]
+    fn test_skipped_requirements_are_not_selected() {
+        let mut prd = prd();
+        prd.skip = vec!["REQ-02".to_string(), "REQ-04".to_string()];
+        let ledger = Ledger::new();
+        for selection in [
+            Selection::Fifo,
+            Selection::Priority,
+            Selection::FewestFailures,
+            Selection::RoundRobin,
+        ] {
+            assert_eq!(selected(selection, &prd, &ledger), "REQ-03");
+        }
+
+        prd.skip.push("REQ-03".to_string());
+        assert!(Selection::Fifo.strategy().select(&prd, &ledger).is_none());
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        for name in Selection::NAMES {
+            let selection: Selection = name.parse().unwrap();
+            assert_eq!(
+                serde_json::to_value(selection).unwrap(),
+                serde_json::json!(name)
+            );
+        }
+        assert!("lifo".parse::<Selection>().is_err());
+    }
+}
@@ -0,0 +1,228 @@
+// ABOUTME: Offline summaries of failed validation output, with no model or network calls
+// ABOUTME: Pulls compiler errors, failing tests, panics, and assertion messages out with regexes
+
+use crate::diagnostics::{self, Severity};
+use regex::{Captures, Regex};
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+/// Most findings listed in a summary
+pub const MAX_FINDINGS: usize = 10;
+
+/// Lines after a rustc error header searched for its `-->` location
+const LOCATION_LOOKAHEAD: usize = 4;
+
+/// Lines after a panic header taken as its message
+const PANIC_MESSAGE_LINES: usize = 3;
+
+/// rustc errors that only repeat that earlier errors happened
+const NOISE_PREFIXES: [&str; 4] = [
+    "aborting due to",
+    "could not compile",
+    "test failed",
+    "failed to",
+];
+
+/// Line formats recognized in plain-text tool output
+struct Patterns {
+    /// rustc and clippy header: `error[E0308]: mismatched types`
+    rust_error: Regex,
+    /// rustc location: `  --> src/lib.rs:10:5`
+    rust_location: Regex,
+    /// gcc, clang, mypy, and the like: `src/a.c:3:7: error: ...`
+    located_error: Regex,
+    /// go build and vet: `./main.go:10:2: undefined: foo`
+    go_error: Regex,
+    /// Rust panic: `thread 'tests::parse' panicked at src/lib.rs:40:9:`
+    panic: Regex,
+    /// pytest short summary: `FAILED tests/test_a.py::test_b - AssertionError: ...`
+    pytest_failed: Regex,
+    /// go test: `--- FAIL: TestParse (0.00s)`
+    go_failed: Regex,
+    /// jest: `● Parser › rejects empty input`
+    jest_failed: Regex,
+    /// Exception or assertion line, optionally with pytest's `E` prefix
+    exception: Regex,
+}
+
+impl Patterns {
+    fn get() -> &'static Self {
+        static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+        PATTERNS.get_or_init(|| {
+            let regex = |pattern: &str| Regex::new(pattern).expect("summary patterns are valid");
+            Self {
+                rust_error: regex(r"^error(?:\[(?P<code>[^\]]+)\])?: (?P<message>.+)$"),
+                rust_location: regex(r"^\s*--> (?P<location>\S+:\d+:\d+)"),
+                located_error: regex(r"^\S+:\d+(?::\d+)?: (?:fatal )?error(?:\[[^\]]+\])?: .+$"),
+                go_error: regex(r"^\S+\.go:\d+:\d+: .+$"),
+                panic: regex(r"^thread '(?P<test>[^']+)' panicked at (?P<rest>.+)$"),
+                pytest_failed: regex(r"^FAILED (?P<test>\S+)(?: - (?P<message>.+))?$"),
+                go_failed: regex(r"^\s*--- FAIL: (?P<test>\S+)"),
+                jest_failed: regex(r"^\s*● (?P<test>.+)$"),
+                exception: regex(
+                    r"^(?:E\s+)?(?P<finding>[A-Z][\w.]*(?:Error|Exception|Failure): .+)$",
+                ),
+            }
+        })
+    }
+
+    /// What line `index` of `lines` reports, if anything
+    fn finding(&self, lines: &[&str], index: usize) -> Option<String> {
+        let line = lines[index].trim_end();
+
+        if let Some(caps) = self.rust_error.captures(line) {
+            let message = &caps["message"];
+            if NOISE_PREFIXES
+                .iter()
+                .any(|noise| message.starts_with(noise))
+            {
+                return None;
+            }
+            let code = caps
+                .name("code")
+                .map_or(String::new(), |code| format!("[{}]", code.as_str()));
+            let location = lines[index + 1..]
+                .iter()
+                .take(LOCATION_LOOKAHEAD)
+                .find_map(|line| self.rust_location.captures(line))
+                .map_or(String::new(), |caps| format!("{}: ", &caps["location"]));
+            return Some(format!("{location}error{code}: {message}"));
+        }
+        if self.located_error.is_match(line) || self.go_error.is_match(line) {
+            return Some(line.trim_start().to_string());
+        }
+        if let Some(caps) = self.panic.captures(line) {
+            let rest = &caps["rest"];
+            // Since Rust 1.73 the message follows on its own lines
+            let message = match rest.strip_suffix(':') {
+                Some(location) => {
+                    let message: Vec<&str> = lines[index + 1..]
+                        .iter()
+                        .map(|line| line.trim())
+                        .take_while(|line| !line.is_empty() && !line.starts_with("note:"))
+                        .take(PANIC_MESSAGE_LINES)
+                        .collect();
+                    format!("{location}: {}", message.join("; "))
+                }
+                None => rest.to_string(),
+            };
+            return Some(format!("{} panicked at {message}", &caps["test"]));
+        }
+        if let Some(caps) = self.pytest_failed.captures(line) {
+            return Some(failed_test(&caps));
+        }
+        if let Some(caps) = self
+            .go_failed
+            .captures(line)
+            .or_else(|| self.jest_failed.captures(line))
+        {
+            return Some(failed_test(&caps));
+        }
+        self.exception
+            .captures(line)
+            .map(|caps| caps["finding"].to_string())
+    }
+}
+
+fn failed_test(caps: &Captures) -> String {
+    match caps.name("message") {
+        Some(message) => format!("{} failed: {}", &caps["test"], message.as_str()),
+        None => format!("{} failed", &caps["test"]),
+    }
+}
+
+/// Bullet list of the errors, failing tests, and assertion messages in `output`, or `None`
+/// if it has none this recognizes
+///
+/// Machine-readable diagnostics (see [`diagnostics::parse`]) come first, then findings from
+/// plain-text compiler and test runner output, in order and without repeats.
+#[must_use]
+pub fn summarize(output: &str) -> Option<String> {
+    let patterns = Patterns::get();
+    let lines: Vec<&str> = output.lines().collect();
+    let mut seen = BTreeSet::new();
+    let findings: Vec<String> = diagnostics::parse(output)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .map(|diagnostic| diagnostic.to_string())
+        .chain((0..lines.len()).filter_map(|index| patterns.finding(&lines, index)))
+        .filter(|finding| seen.insert(finding.clone()))
+        .collect();
+    if findings.is_empty() {
+        return None;
+    }
+
+    let mut summary: Vec<String> = findings
+        .iter()
+        .take(MAX_FINDINGS)
+        .map(|finding| format!("- {finding}"))
+        .collect();
+    if findings.len() > MAX_FINDINGS {
+        summary.push(format!("- ... and {} more", findings.len() - MAX_FINDINGS));
+    }
+    Some(summary.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_rust() {
+        let output = "Stage: typecheck
+error[E0308]: mismatched types
+  --> src/lib.rs:10:5
+   |
+10 |     \"a\"
+   |     ^^^ expected `u32`, found `&str`
+
+error: aborting due to 1 previous error
+error: could not compile `demo` (lib) due to 1 previous error
+
+thread 'tests::parses_empty' panicked at src/parser.rs:40:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+";
+        assert_eq!(
+            summarize(output).unwrap(),
+            "- src/lib.rs:10:5: error[E0308]: mismatched types\n\
+             - tests::parses_empty panicked at src/parser.rs:40:9: \
+             assertion `left == right` failed; left: 1; right: 2"
+        );
+    }
+
+    #[test]
+    fn test_summarize_other_tools() {
+        let output = r#"src/a.c:3:7: error: use of undeclared identifier 'x'
+./main.go:10:2: undefined: foo
+--- FAIL: TestParse (0.00s)
+  ● Parser › rejects empty input
+E       AssertionError: assert 1 == 2
+FAILED tests/test_a.py::test_b - AssertionError: assert 1 == 2
+{"reason":"compiler-message","message":{"message":"unused variable","code":null,"level":"warning","spans":[{"file_name":"src/x.rs","line_start":1,"column_start":1,"is_primary":true}]}}"#;
+        assert_eq!(
+            summarize(output).unwrap(),
+            "- src/a.c:3:7: error: use of undeclared identifier 'x'\n\
+             - ./main.go:10:2: undefined: foo\n\
+             - TestParse failed\n\
+             - Parser › rejects empty input failed\n\
+             - AssertionError: assert 1 == 2\n\
+             - tests/test_a.py::test_b failed: AssertionError: assert 1 == 2"
+        );
+    }
+
+    #[test]
+    fn test_summarize_caps_findings() {
+        assert_eq!(summarize("Stage: lint\nsomething went wrong\n"), None);
+
+        let output = (1..=12)
+            .map(|n| format!("src/a.c:{n}:1: error: bad {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let summary = summarize(&output).unwrap();
+        assert_eq!(summary.lines().count(), MAX_FINDINGS + 1);
+        assert!(summary.ends_with("- ... and 2 more"));
+    }
+}
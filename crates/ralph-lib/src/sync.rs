@@ -0,0 +1,621 @@
+// ABOUTME: Optional remote sync of ledger events to HTTP endpoints and object stores
+// ABOUTME: Pushes each appended event via curl, aws, or gcloud so runs can be aggregated centrally
+
+use crate::ledger::LedgerEvent;
+use crate::secrets::{Secret, SecretResolver};
+use crate::validation::{kill_process_tree, TIMEOUT_POLL_INTERVAL};
+use crate::{RalphError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long an upload command may run before it is killed and counted as failed
+pub const UPLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Undelivered events kept for retry; older ones are dropped past this
+pub const MAX_PENDING_EVENTS: usize = 1000;
+
+/// Where ledger events are pushed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SyncTarget {
+    /// POST each event as JSON to an endpoint
+    Http {
+        url: String,
+        /// Name of a secret sent as a bearer token (resolved from env, .env, or keychain)
+        #[serde(
+            default,
+            rename = "tokenSecret",
+            skip_serializing_if = "Option::is_none"
+        )]
+        token_secret: Option<String>,
+    },
+    /// Upload each event as an object to an S3 bucket (via the `aws` CLI)
+    S3 {
+        bucket: String,
+        #[serde(default)]
+        prefix: String,
+    },
+    /// Upload each event as an object to a GCS bucket (via the `gcloud` CLI)
+    Gcs {
+        bucket: String,
+        #[serde(default)]
+        prefix: String,
+    },
+}
+
+/// Project sync settings, stored in `ralph/sync.json` unless `[paths] sync` says otherwise
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConfig {
+    /// Name identifying this project remotely (defaults to the repository directory name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Targets every event is pushed to
+    #[serde(default)]
+    pub targets: Vec<SyncTarget>,
+}
+
+impl SyncConfig {
+    /// Load sync config from a JSON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or contains invalid JSON.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref())?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+/// A single external command that delivers one event to one target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Upload {
+    /// Program to run (curl, aws, gcloud)
+    pub program: &'static str,
+    /// Arguments (never contain secrets)
+    pub args: Vec<String>,
+    /// Data written to the program's stdin
+    pub stdin: String,
+}
+
+impl SyncTarget {
+    /// Build the command that pushes `event` for a project's feature to this target
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be serialized or a required secret is missing.
+    pub fn upload(
+        &self,
+        project: &str,
+        slug: &str,
+        event: &LedgerEvent,
+        secrets: &SecretResolver,
+    ) -> Result<Upload> {
+        let body = serde_json::to_string(&serde_json::json!({
+            "project": project,
+            "feature": slug,
+            "event": event,
+        }))?;
+
+        Ok(match self {
+            Self::Http { url, token_secret } => {
+                let token: Option<Secret> = token_secret
+                    .as_deref()
+                    .map(|name| secrets.require(name))
+                    .transpose()?;
+                post_json(url, &body, token.as_ref())
+            }
+            Self::S3 { bucket, prefix } => Upload {
+                program: "aws",
+                args: vec![
+                    "s3".to_string(),
+                    "cp".to_string(),
+                    "-".to_string(),
+                    format!("s3://{bucket}/{}", object_key(prefix, project, slug, event)),
+                ],
+                stdin: body,
+            },
+            Self::Gcs { bucket, prefix } => Upload {
+                program: "gcloud",
+                args: vec![
+                    "storage".to_string(),
+                    "cp".to_string(),
+                    "-".to_string(),
+                    format!("gs://{bucket}/{}", object_key(prefix, project, slug, event)),
+                ],
+                stdin: body,
+            },
+        })
+    }
+}
+
+impl Upload {
+    /// Run the upload command, killing it after [`UPLOAD_TIMEOUT`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the program cannot be started, exits unsuccessfully, or times out.
+    pub fn run(&self) -> Result<()> {
+        self.run_with_timeout(UPLOAD_TIMEOUT)
+    }
+
+    /// Run the upload command, killing it after `timeout`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the program cannot be started, exits unsuccessfully, or times out.
+    pub fn run_with_timeout(&self, timeout: Duration) -> Result<()> {
+        let mut command = Command::new(self.program);
+        command
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+        // Own process group, so a timeout also kills anything the program spawned
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        let mut child = command
+            .spawn()
+            .map_err(|e| RalphError::Command(format!("{}: {e}", self.program)))?;
+
+        // Feed stdin and drain stderr on threads, so a stuck program can't block us past
+        // the deadline
+        if let Some(mut stdin) = child.stdin.take() {
+            let input = self.stdin.clone();
+            std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+        }
+        let stderr = child.stderr.take().map(|mut pipe| {
+            std::thread::spawn(move || {
+                let mut text = String::new();
+                let _ = pipe.read_to_string(&mut text);
+                text
+            })
+        });
+
+        let deadline = Instant::now() + timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                kill_process_tree(&mut child);
+                return Err(RalphError::Command(format!(
+                    "{}: timed out after {}s",
+                    self.program,
+                    timeout.as_secs()
+                )));
+            }
+            std::thread::sleep(TIMEOUT_POLL_INTERVAL.min(deadline - now));
+        };
+        if status.success() {
+            Ok(())
+        } else {
+            let stderr = stderr
+                .and_then(|handle| handle.join().ok())
+                .unwrap_or_default();
+            Err(RalphError::Command(format!(
+                "{}: {}",
+                self.program,
+                stderr.trim()
+            )))
+        }
+    }
+}
+
+/// curl command POSTing the JSON `body` to `url`
+///
+/// Only fixed flags go on the command line: the URL, headers, and body are passed as a
+/// curl config on stdin, so tokens and ledger contents never show up in the process list
+/// and large events don't run into argument size limits.
+pub(crate) fn post_json(url: &str, body: &str, token: Option<&Secret>) -> Upload {
+    let mut config = format!("url = {}\n", curl_quote(url));
+    config.push_str("header = \"Content-Type: application/json\"\n");
+    if let Some(token) = token {
+        let header = format!("Authorization: Bearer {}", token.expose());
+        config.push_str(&format!("header = {}\n", curl_quote(&header)));
+    }
+    config.push_str(&format!("data-binary = {}\n", curl_quote(body)));
+    Upload {
+        program: "curl",
+        args: vec![
+            "--silent".to_string(),
+            "--show-error".to_string(),
+            "--fail".to_string(),
+            "--config".to_string(),
+            "-".to_string(),
+        ],
+        stdin: config,
+    }
+}
+
+/// `text` as a double-quoted curl config value
+fn curl_quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Value of the first `key = "..."` line of a curl config, unquoted
+#[cfg(test)]
+pub(crate) fn curl_config_value(config: &str, key: &str) -> Option<String> {
+    let quoted = config
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(" = \""))?
+        .strip_suffix('"')?;
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        value.push(if c == '\\' {
+            match chars.next()? {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                escaped => escaped,
+            }
+        } else {
+            c
+        });
+    }
+    Some(value)
+}
+
+/// A queued event and the targets it has yet to reach, by index
+#[derive(Debug, Clone)]
+struct Pending {
+    event: LedgerEvent,
+    targets: Vec<usize>,
+}
+
+/// Pushes appended events to every configured target, queueing events that fail
+///
+/// The queue lives in memory: it holds at most [`MAX_PENDING_EVENTS`], and events still
+/// undelivered when the process exits are dropped (the local ledger keeps them).
+#[derive(Debug, Clone)]
+pub struct LedgerSync {
+    config: SyncConfig,
+    project: String,
+    slug: String,
+    secrets: SecretResolver,
+    pending: Vec<Pending>,
+    last_error: Option<String>,
+}
+
+impl LedgerSync {
+    /// Create a sync layer for a feature; `default_project` is used if the config names none
+    #[must_use]
+    pub fn new(
+        config: SyncConfig,
+        default_project: impl Into<String>,
+        slug: impl Into<String>,
+        secrets: SecretResolver,
+    ) -> Self {
+        let project = config
+            .project
+            .clone()
+            .unwrap_or_else(|| default_project.into());
+        Self {
+            config,
+            project,
+            slug: slug.into(),
+            secrets,
+            pending: Vec::new(),
+            last_error: None,
+        }
+    }
+
+    /// Queue an event and try to deliver everything queued
+    ///
+    /// Failures never propagate: undelivered events stay queued for the next push, up to
+    /// [`MAX_PENDING_EVENTS`].
+    pub fn push(&mut self, event: &LedgerEvent) {
+        self.pending.push(Pending {
+            event: event.clone(),
+            targets: (0..self.config.targets.len()).collect(),
+        });
+        self.flush();
+        let overflow = self.pending.len().saturating_sub(MAX_PENDING_EVENTS);
+        if overflow > 0 {
+            self.pending.drain(..overflow);
+            self.last_error = Some(format!(
+                "sync queue full; dropped {overflow} undelivered event(s)"
+            ));
+        }
+    }
+
+    /// Retry delivery of queued events, returning how many remain queued
+    ///
+    /// Each event is retried only on the targets it hasn't reached yet.
+    pub fn flush(&mut self) -> usize {
+        let (project, slug, secrets) = (&self.project, &self.slug, &self.secrets);
+        let targets = &self.config.targets;
+        flush_pending(&mut self.pending, &mut self.last_error, |index, event| {
+            targets[index].upload(project, slug, event, secrets)?.run()
+        })
+    }
+
+    /// Events not yet delivered
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Most recent delivery error
+    #[must_use]
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+}
+
+/// Deliver queued events in order with `deliver(target, event)`, returning how many remain
+///
+/// A target that fails an event gets none of the later ones this time, so each target
+/// sees events in order; the other targets carry on.
+fn flush_pending(
+    pending: &mut Vec<Pending>,
+    last_error: &mut Option<String>,
+    mut deliver: impl FnMut(usize, &LedgerEvent) -> Result<()>,
+) -> usize {
+    let mut failed = HashSet::new();
+    for Pending { event, targets } in pending.iter_mut() {
+        targets.retain(|&target| {
+            if failed.contains(&target) {
+                return true;
+            }
+            match deliver(target, event) {
+                Ok(()) => false,
+                Err(e) => {
+                    *last_error = Some(e.to_string());
+                    failed.insert(target);
+                    true
+                }
+            }
+        });
+    }
+    pending.retain(|queued| !queued.targets.is_empty());
+    pending.len()
+}
+
+/// Object name for an event: `<prefix>/<project>/<feature>/<timestamp>-<iteration>-<requirement>.json`
+fn object_key(prefix: &str, project: &str, slug: &str, event: &LedgerEvent) -> String {
+    let name = format!(
+        "{project}/{slug}/{}-{}-{}.json",
+        event.timestamp.format("%Y%m%dT%H%M%S%.6fZ"),
+        event.iteration,
+        event.requirement
+    );
+    match prefix.trim_matches('/') {
+        "" => name,
+        prefix => format!("{prefix}/{name}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::EventStatus;
+    use crate::secrets::SecretBackend;
+    use tempfile::tempdir;
+
+    fn sample_event() -> LedgerEvent {
+        let mut event = LedgerEvent::new(3, "REQ-01", EventStatus::Done);
+        event.timestamp = chrono::DateTime::parse_from_rfc3339("2026-01-19T10:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        event
+    }
+
+    #[test]
+    fn test_config_parsing() {
+        let config: SyncConfig = serde_json::from_str(
+            r#"{"project":"api","targets":[
+                {"type":"http","url":"https://ralph.example.com/events","tokenSecret":"RALPH_SYNC_TOKEN"},
+                {"type":"s3","bucket":"ledgers"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.project.as_deref(), Some("api"));
+        assert_eq!(
+            config.targets[1],
+            SyncTarget::S3 {
+                bucket: "ledgers".to_string(),
+                prefix: String::new()
+            }
+        );
+    }
+
+    #[test]
+    fn test_http_upload_keeps_token_out_of_args() {
+        let dir = tempdir().unwrap();
+        let env = dir.path().join(".env");
+        std::fs::write(&env, "RALPH_SYNC_TOKEN=tok-123\n").unwrap();
+        let secrets = SecretResolver::new().with_backend(SecretBackend::DotEnv(env));
+
+        let target = SyncTarget::Http {
+            url: "https://ralph.example.com/events".to_string(),
+            token_secret: Some("RALPH_SYNC_TOKEN".to_string()),
+        };
+        let upload = target
+            .upload("api", "auth", &sample_event(), &secrets)
+            .unwrap();
+        assert_eq!(upload.program, "curl");
+        assert_eq!(upload.args.last().unwrap(), "-");
+        assert!(upload
+            .args
+            .iter()
+            .all(|arg| !arg.contains("tok-123") && !arg.contains("example.com")));
+        assert_eq!(
+            curl_config_value(&upload.stdin, "url").as_deref(),
+            Some("https://ralph.example.com/events")
+        );
+        assert!(upload
+            .stdin
+            .contains(r#"header = "Authorization: Bearer tok-123""#));
+        let body = curl_config_value(&upload.stdin, "data-binary").unwrap();
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["feature"], "auth");
+        assert_eq!(body["event"]["requirement"], "REQ-01");
+
+        // A configured but missing secret is an error, not an unauthenticated request
+        assert!(target
+            .upload("api", "auth", &sample_event(), &SecretResolver::new())
+            .is_err());
+    }
+
+    #[test]
+    fn test_object_store_keys() {
+        let secrets = SecretResolver::new();
+        let s3 = SyncTarget::S3 {
+            bucket: "ledgers".to_string(),
+            prefix: "/ralph/".to_string(),
+        };
+        let upload = s3.upload("api", "auth", &sample_event(), &secrets).unwrap();
+        assert_eq!(
+            upload.args.last().unwrap(),
+            "s3://ledgers/ralph/api/auth/20260119T100000.000000Z-3-REQ-01.json"
+        );
+        assert!(upload.stdin.contains(r#""requirement":"REQ-01""#));
+
+        let gcs = SyncTarget::Gcs {
+            bucket: "ledgers".to_string(),
+            prefix: String::new(),
+        };
+        let upload = gcs
+            .upload("api", "auth", &sample_event(), &secrets)
+            .unwrap();
+        assert_eq!(upload.program, "gcloud");
+        assert!(upload
+            .args
+            .last()
+            .unwrap()
+            .starts_with("gs://ledgers/api/auth/"));
+    }
+
+    #[test]
+    fn test_failed_push_stays_queued() {
+        let config = SyncConfig {
+            project: None,
+            targets: vec![SyncTarget::Http {
+                url: "https://ralph.example.com/events".to_string(),
+                token_secret: Some("RALPH_SYNC_TOKEN_UNSET_IN_TESTS".to_string()),
+            }],
+        };
+        let mut sync = LedgerSync::new(config, "repo", "auth", SecretResolver::new());
+        sync.push(&sample_event());
+        sync.push(&sample_event());
+        assert_eq!(sync.pending(), 2);
+        assert!(sync
+            .last_error()
+            .unwrap()
+            .contains("RALPH_SYNC_TOKEN_UNSET_IN_TESTS"));
+    }
+
+    #[test]
+    fn test_retry_skips_targets_already_reached() {
+        let mut pending = vec![Pending {
+            event: sample_event(),
+            targets: vec![0, 1],
+        }];
+        let mut last_error = None;
+        let mut calls = Vec::new();
+        let remaining = flush_pending(&mut pending, &mut last_error, |target, _| {
+            calls.push(target);
+            if target == 1 {
+                return Err(RalphError::Command("target 1 down".to_string()));
+            }
+            Ok(())
+        });
+        assert_eq!(remaining, 1);
+        assert_eq!(calls, vec![0, 1]);
+        assert!(last_error.unwrap().contains("target 1 down"));
+
+        // Target 0 already has the event, so only target 1 is retried
+        calls.clear();
+        let remaining = flush_pending(&mut pending, &mut None, |target, _| {
+            calls.push(target);
+            Ok(())
+        });
+        assert_eq!(remaining, 0);
+        assert_eq!(calls, vec![1]);
+    }
+
+    #[test]
+    fn test_failing_target_does_not_hold_back_others() {
+        let event = |requirement: &str| LedgerEvent::new(1, requirement, EventStatus::Done);
+        let mut pending: Vec<Pending> = ["REQ-01", "REQ-02", "REQ-03"]
+            .iter()
+            .map(|requirement| Pending {
+                event: event(requirement),
+                targets: vec![0, 1],
+            })
+            .collect();
+        let mut calls = Vec::new();
+        let remaining = flush_pending(&mut pending, &mut None, |target, event| {
+            calls.push((target, event.requirement.clone()));
+            if target == 1 && event.requirement == "REQ-01" {
+                return Err(RalphError::Command("rejected".to_string()));
+            }
+            Ok(())
+        });
+
+        // Target 0 gets every event; target 1 stops at the one it rejected
+        assert_eq!(remaining, 3);
+        assert_eq!(
+            calls,
+            vec![
+                (0, "REQ-01".to_string()),
+                (1, "REQ-01".to_string()),
+                (0, "REQ-02".to_string()),
+                (0, "REQ-03".to_string()),
+            ]
+        );
+        assert!(pending.iter().all(|queued| queued.targets == vec![1]));
+    }
+
+    #[test]
+    fn test_queue_is_capped() {
+        let config = SyncConfig {
+            project: None,
+            targets: vec![SyncTarget::Http {
+                url: "https://ralph.example.com/events".to_string(),
+                token_secret: Some("RALPH_SYNC_TOKEN_UNSET_IN_TESTS".to_string()),
+            }],
+        };
+        let mut sync = LedgerSync::new(config, "repo", "auth", SecretResolver::new());
+        for _ in 0..=MAX_PENDING_EVENTS {
+            sync.push(&sample_event());
+        }
+        assert_eq!(sync.pending(), MAX_PENDING_EVENTS);
+        assert!(sync.last_error().unwrap().contains("dropped 1"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_upload_times_out() {
+        let upload = Upload {
+            program: "sleep",
+            args: vec!["5".to_string()],
+            stdin: String::new(),
+        };
+        let started = Instant::now();
+        let err = upload
+            .run_with_timeout(Duration::from_millis(200))
+            .unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+}
@@ -0,0 +1,284 @@
+// ABOUTME: Politeness controls for agent invocations
+// ABOUTME: Spaces out calls, caps concurrency, and backs off with jitter on rate limits and network errors
+
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// First retry delay after a rate-limit signal; doubles on each further retry
+const BASE_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Longest single backoff
+const MAX_BACKOFF: Duration = Duration::from_secs(600);
+
+/// Phrases the copilot CLI (and the APIs behind it) use when throttling; a bare
+/// "429" would also match line numbers and test counts, so it only counts next to
+/// an HTTP status marker
+const RATE_LIMIT_SIGNALS: [&str; 8] = [
+    "rate limit",
+    "rate-limit",
+    "ratelimit",
+    "too many requests",
+    "http 429",
+    "status 429",
+    "status code 429",
+    "quota exceeded",
+];
+
+/// Phrases that mean the agent couldn't reach the model service
+const NETWORK_SIGNALS: [&str; 10] = [
+    "network error",
+    "connection reset",
+    "connection refused",
+    "econnreset",
+    "etimedout",
+    "enotfound",
+    "socket hang up",
+    "could not resolve host",
+    "502 bad gateway",
+    "503 service unavailable",
+];
+
+/// Lines at the end of the agent's output searched for rate-limit and network signals
+const SIGNAL_TAIL_LINES: usize = 20;
+
+/// Shared limiter for agent calls, safe to use from parallel lanes
+#[derive(Debug)]
+pub struct Throttle {
+    min_delay: Duration,
+    max_concurrent: usize,
+    max_retries: u32,
+    state: Mutex<ThrottleState>,
+    slot_freed: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct ThrottleState {
+    active: usize,
+    next_start: Option<Instant>,
+}
+
+/// A running agent call; frees its concurrency slot when dropped
+#[derive(Debug)]
+pub struct ThrottlePermit<'a> {
+    throttle: &'a Throttle,
+}
+
+impl Throttle {
+    /// Create a throttle
+    ///
+    /// `max_concurrent` of 0 means unlimited; `max_retries` bounds retries after transient failures.
+    #[must_use]
+    pub fn new(min_delay: Duration, max_concurrent: usize, max_retries: u32) -> Self {
+        Self {
+            min_delay,
+            max_concurrent,
+            max_retries,
+            state: Mutex::new(ThrottleState::default()),
+            slot_freed: Condvar::new(),
+        }
+    }
+
+    /// A throttle that never waits and never retries
+    #[must_use]
+    pub fn unlimited() -> Self {
+        Self::new(Duration::ZERO, 0, 0)
+    }
+
+    /// Retries allowed after transient failures
+    #[must_use]
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Wait for a free slot and for the minimum delay since the previous call started
+    pub fn acquire(&self) -> ThrottlePermit<'_> {
+        let mut state = self.lock();
+        while self.max_concurrent > 0 && state.active >= self.max_concurrent {
+            state = self
+                .slot_freed
+                .wait(state)
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+        }
+        state.active += 1;
+
+        // Reserve a start time so concurrent callers are spaced out too
+        let now = Instant::now();
+        let start = state.next_start.map_or(now, |next| next.max(now));
+        state.next_start = Some(start + self.min_delay);
+        drop(state);
+
+        std::thread::sleep(start.saturating_duration_since(now));
+        ThrottlePermit { throttle: self }
+    }
+
+    /// Hold off every caller (including the current one on its next acquire) for `delay`
+    pub fn pause(&self, delay: Duration) {
+        let mut state = self.lock();
+        let resume = Instant::now() + delay;
+        state.next_start = Some(state.next_start.map_or(resume, |next| next.max(resume)));
+    }
+
+    /// Delay before retry number `attempt` (0-based): exponential, capped
+    #[must_use]
+    pub fn backoff(attempt: u32) -> Duration {
+        BASE_BACKOFF
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_BACKOFF)
+    }
+
+    /// [`Throttle::backoff`] scaled to a random point between half and all of it, so
+    /// parallel lanes that failed together don't retry together
+    #[must_use]
+    pub fn backoff_with_jitter(attempt: u32) -> Duration {
+        let random = RandomState::new().build_hasher().finish();
+        let fraction = 0.5 + (random % 1_000) as f64 / 2_000.0;
+        Self::backoff(attempt).mul_f64(fraction)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ThrottleState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl Drop for ThrottlePermit<'_> {
+    fn drop(&mut self) {
+        self.throttle.lock().active -= 1;
+        self.throttle.slot_freed.notify_one();
+    }
+}
+
+/// A failed agent call that says nothing about the implementation and is worth retrying
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransientFailure {
+    /// The service throttled the agent
+    RateLimited,
+    /// The agent couldn't reach the service
+    Network,
+}
+
+impl fmt::Display for TransientFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::RateLimited => "rate limited",
+            Self::Network => "network error",
+        })
+    }
+}
+
+/// Whether a failed agent call's output says it was rate limited
+#[must_use]
+pub fn is_rate_limited(output: &str) -> bool {
+    tail_mentions(output, &RATE_LIMIT_SIGNALS)
+}
+
+/// Why a failed agent call failed, if its output shows a transient cause
+#[must_use]
+pub fn transient_failure(output: &str) -> Option<TransientFailure> {
+    if is_rate_limited(output) {
+        Some(TransientFailure::RateLimited)
+    } else if tail_mentions(output, &NETWORK_SIGNALS) {
+        Some(TransientFailure::Network)
+    } else {
+        None
+    }
+}
+
+/// Whether the last lines of `output` contain any of `signals` (case-insensitive)
+fn tail_mentions(output: &str, signals: &[&str]) -> bool {
+    let lines: Vec<&str> = output.lines().collect();
+    let tail = &lines[lines.len().saturating_sub(SIGNAL_TAIL_LINES)..];
+    tail.iter().any(|line| {
+        let line = line.to_lowercase();
+        signals.iter().any(|signal| line.contains(signal))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_is_rate_limited() {
+        assert!(is_rate_limited("Error: HTTP 429 Too Many Requests"));
+        assert!(is_rate_limited("working...\nYou have hit your Rate Limit"));
+        assert!(!is_rate_limited("error[E0308]: mismatched types"));
+        assert!(is_rate_limited("request failed with status code 429"));
+        assert!(!is_rate_limited("error at line 429"));
+        assert!(!is_rate_limited("test result: ok. 429 passed; 0 failed"));
+
+        // Only the end of the output counts, not code the agent printed earlier
+        let mut output = "// handle 429 responses\n".to_string();
+        output.push_str(&"compiling\n".repeat(SIGNAL_TAIL_LINES));
+        assert!(!is_rate_limited(&output));
+    }
+
+    #[test]
+    fn test_transient_failure() {
+        assert_eq!(
+            transient_failure("Error: HTTP 429 Too Many Requests"),
+            Some(TransientFailure::RateLimited)
+        );
+        assert_eq!(
+            transient_failure("request failed: read ECONNRESET"),
+            Some(TransientFailure::Network)
+        );
+        assert_eq!(transient_failure("error[E0308]: mismatched types"), None);
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        assert_eq!(Throttle::backoff(0), BASE_BACKOFF);
+        assert_eq!(Throttle::backoff(1), BASE_BACKOFF * 2);
+        assert_eq!(Throttle::backoff(30), MAX_BACKOFF);
+        for attempt in 0..3 {
+            let delay = Throttle::backoff_with_jitter(attempt);
+            assert!(delay >= Throttle::backoff(attempt) / 2);
+            assert!(delay <= Throttle::backoff(attempt));
+        }
+    }
+
+    #[test]
+    fn test_min_delay_spaces_calls() {
+        let throttle = Throttle::new(Duration::from_millis(50), 0, 0);
+        let start = Instant::now();
+        drop(throttle.acquire());
+        drop(throttle.acquire());
+        drop(throttle.acquire());
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_max_concurrent_caps_calls() {
+        let throttle = Throttle::new(Duration::ZERO, 2, 0);
+        let active = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        std::thread::scope(|scope| {
+            for _ in 0..6 {
+                scope.spawn(|| {
+                    let _permit = throttle.acquire();
+                    let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(20));
+                    active.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[test]
+    fn test_pause_delays_next_call() {
+        let throttle = Throttle::unlimited();
+        throttle.pause(Duration::from_millis(50));
+        let start = Instant::now();
+        drop(throttle.acquire());
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,169 @@
+// ABOUTME: Token usage parsing and cost estimation for copilot invocations
+// ABOUTME: Extracts per-model token counts and the session ID from copilot's output
+
+/// Token usage reported for one copilot invocation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenUsage {
+    /// Model that consumed the tokens
+    pub model: Option<String>,
+    /// Input (prompt) tokens
+    pub prompt_tokens: u64,
+    /// Output (completion) tokens
+    pub completion_tokens: u64,
+}
+
+impl TokenUsage {
+    /// Estimated cost in USD, if the model's pricing is known
+    #[must_use]
+    pub fn estimated_cost(&self) -> Option<f64> {
+        let (input, output) = model_pricing(self.model.as_deref()?)?;
+        Some(
+            (self.prompt_tokens as f64 * input + self.completion_tokens as f64 * output)
+                / 1_000_000.0,
+        )
+    }
+}
+
+/// USD per million (input, output) tokens for known models
+#[must_use]
+pub fn model_pricing(model: &str) -> Option<(f64, f64)> {
+    match model {
+        "claude-haiku-4.5" => Some((1.0, 5.0)),
+        "claude-sonnet-4" | "claude-sonnet-4.5" => Some((3.0, 15.0)),
+        "claude-opus-4.5" => Some((5.0, 25.0)),
+        "gpt-5" | "gpt-5.1" => Some((1.25, 10.0)),
+        "gpt-5-mini" => Some((0.25, 2.0)),
+        _ => None,
+    }
+}
+
+/// Parse copilot's "Usage by model" summary, summing tokens across models
+///
+/// Expects lines like `claude-haiku-4.5   12.3k input, 456 output, ...`.
+/// Returns None if no usage lines are present.
+#[must_use]
+pub fn parse_copilot_usage(output: &str) -> Option<TokenUsage> {
+    let mut usage: Option<TokenUsage> = None;
+
+    for line in output.lines() {
+        let mut words = line.split_whitespace();
+        let Some(model) = words.next() else {
+            continue;
+        };
+        let rest: Vec<&str> = words.collect();
+        let (Some(prompt), Some(completion)) =
+            (count_before(&rest, "input"), count_before(&rest, "output"))
+        else {
+            continue;
+        };
+
+        let entry = usage.get_or_insert_with(TokenUsage::default);
+        entry.prompt_tokens += prompt;
+        entry.completion_tokens += completion;
+        // Cost is attributed to the first model; mixed-model runs keep the first name
+        entry.model.get_or_insert_with(|| model.to_string());
+    }
+    usage
+}
+
+/// Session ID copilot reported, for resuming the session with `--resume <id>`
+///
+/// Looks for a UUID on a line mentioning the session, e.g. `Session ID: 0b5c...`; the last
+/// one wins. Returns None if the output names no session.
+#[must_use]
+pub fn parse_copilot_session(output: &str) -> Option<String> {
+    output
+        .lines()
+        .filter(|line| line.to_lowercase().contains("session"))
+        .flat_map(|line| line.split(|c: char| !c.is_ascii_hexdigit() && c != '-'))
+        .filter(|word| is_uuid(word))
+        .next_back()
+        .map(str::to_lowercase)
+}
+
+/// Whether `word` is a hyphenated UUID (8-4-4-4-12 hex digits)
+fn is_uuid(word: &str) -> bool {
+    let groups: Vec<&str> = word.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Find the token count preceding a label like "input," in a split usage line
+fn count_before(words: &[&str], label: &str) -> Option<u64> {
+    let idx = words
+        .iter()
+        .position(|w| w.trim_end_matches(',') == label)?;
+    parse_count(words.get(idx.checked_sub(1)?)?)
+}
+
+/// Parse counts like "456", "12.3k", or "1.2m"
+fn parse_count(word: &str) -> Option<u64> {
+    let word = word.to_lowercase();
+    let (number, multiplier) = if let Some(n) = word.strip_suffix('k') {
+        (n, 1_000.0)
+    } else if let Some(n) = word.strip_suffix('m') {
+        (n, 1_000_000.0)
+    } else {
+        (word.as_str(), 1.0)
+    };
+    let value: f64 = number.replace(',', "").parse().ok()?;
+    Some((value * multiplier).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_copilot_usage() {
+        let output = "Done.\n\nTotal usage est:       1 Premium request\n\
+                      Usage by model:\n    claude-haiku-4.5     12.3k input, 456 output, 0 cache read, 0 cache write (Est. 0.33 Premium requests)\n";
+        let usage = parse_copilot_usage(output).unwrap();
+        assert_eq!(usage.model.as_deref(), Some("claude-haiku-4.5"));
+        assert_eq!(usage.prompt_tokens, 12_300);
+        assert_eq!(usage.completion_tokens, 456);
+    }
+
+    #[test]
+    fn test_parse_copilot_usage_missing() {
+        assert!(parse_copilot_usage("no usage here").is_none());
+    }
+
+    #[test]
+    fn test_parse_copilot_session() {
+        let output = "Working on REQ-01...\nDone.\n\n\
+                      Resume this session with: copilot --resume 3F2504E0-4F89-11D3-9A0C-0305E82C3301\n";
+        assert_eq!(
+            parse_copilot_session(output).as_deref(),
+            Some("3f2504e0-4f89-11d3-9a0c-0305e82c3301")
+        );
+        assert!(parse_copilot_session("commit 3f2504e0-4f89-11d3-9a0c-0305e82c3301").is_none());
+        assert!(parse_copilot_session("session abc-123").is_none());
+    }
+
+    #[test]
+    fn test_estimated_cost() {
+        let usage = TokenUsage {
+            model: Some("claude-haiku-4.5".to_string()),
+            prompt_tokens: 1_000_000,
+            completion_tokens: 100_000,
+        };
+        assert!((usage.estimated_cost().unwrap() - 1.5).abs() < 1e-9);
+
+        let unknown = TokenUsage {
+            model: Some("mystery".to_string()),
+            ..usage
+        };
+        assert!(unknown.estimated_cost().is_none());
+    }
+
+    #[test]
+    fn test_parse_count() {
+        assert_eq!(parse_count("1.2m"), Some(1_200_000));
+        assert_eq!(parse_count("1,024"), Some(1_024));
+        assert_eq!(parse_count("abc"), None);
+    }
+}